@@ -6,10 +6,16 @@ use crate::shader_program::{ShaderProgram};
 
 use ogl33::{glClearColor};
 // External dependencies.
-use timer::Stopwatch;
+use timer::{Stopwatch, TimeSource};
 use window::window::*;
 use serializers::json::lexer::*;
 use serializers::json::parser::*;
+use serializers::json::typed::FromJson;
+use serializers::json::patch::LayeredDocument;
+use serializers::json::schema::JsonSchema;
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
 
 /// Each implementation of the framework needs to 
 /// create a module to load resources. While the calling of this
@@ -62,6 +68,8 @@ pub struct GameTick {
     /// 0 indicates no limits.
     update_tick_time: f64,
     current_tick_time: f64,
+    /// The game time covered by the updates so far.
+    elapsed_time: f64,
     timer: Stopwatch
 }
 
@@ -75,6 +83,7 @@ impl GameTick {
         Self { 
             update_tick_time: actual_tick_time,
             current_tick_time: 0.0,
+            elapsed_time: 0.0,
             timer: Stopwatch::new()
         }
     }
@@ -82,6 +91,8 @@ impl GameTick {
     /// Ticks the game loop and returns the number of updates which should take place.
     pub fn tick(&mut self) -> i32 {
         if self.update_tick_time == 0.0 {
+            self.elapsed_time += self.timer.elapsed_seconds();
+            self.timer.start();
             return 1;
         }
 
@@ -98,6 +109,7 @@ impl GameTick {
             assert!(update_remainder < self.update_tick_time);
 
             self.current_tick_time = update_remainder;
+            self.elapsed_time += update_count as f64 * self.update_tick_time;
 
             return update_count
         }
@@ -106,6 +118,14 @@ impl GameTick {
     }
 }
 
+/// The time the updates have covered, which only moves in whole ticks so animations driven by it
+/// stay in step with the game state.
+impl TimeSource for GameTick {
+    fn elapsed_seconds(&self) -> f64 {
+        self.elapsed_time
+    }
+}
+
 pub struct GameManager {
     /// Holds a render pipeline object.
     render_pipelines: Vec<Box<dyn RenderPipelineHandler>>,
@@ -157,41 +177,89 @@ impl GameManager {
         resource_loader.load_meshes(&mut self.resources.mesh_resources);
     }
 
-    /// Loads the game manager from an app config file.
-    pub fn from_conf(res_path: &str, config_file_name: &str) -> Option<Self> {
-        let full_conf_name = res_path.to_string() + "/" + config_file_name;
-        
-        let json_lexer = JsonLexer::new(&full_conf_name);
-
-        match json_lexer {
-            Ok(mut json_lexer) => {
-                let user_config = parse_json(&mut json_lexer);
-
-                if let Some(user_config) = user_config {
-                    let config = load_window_config(&user_config);
-                    let engine = Self::new(config);
-
-                    // Load game resources.
-                    match engine {
-                        Some(mut game_manager) => {
-                            game_manager.resources.res_path = res_path.to_string();
-
-                            // Load data from the "resources" object into the resource manager.
-                            load_config_resources(&user_config, &mut game_manager.resources);
-                            let game_config = load_game_config(&user_config);
-                            game_manager.game_tick = GameTick::new(game_config.update_tick_rate);
-                            return Some(game_manager);
-                        }
-                        None => return None
-                    }
+    /// Loads the game manager from a list of app config layers in the res folder.
+    /// Each layer is merge patched over the layers before it, so it only needs the values it overrides.
+    /// The first layer must exist, the rest are skipped when they are missing.
+    /// Any other error reading a layer, ex: a permission error, stops the load so overrides aren't silently dropped.
+    pub fn from_conf(res_path: &str, config_layers: &[&str]) -> Option<Self> {
+        let mut layered_config = LayeredDocument::new();
+
+        for (index, config_file_name) in config_layers.iter().enumerate() {
+            let full_conf_name = res_path.to_string() + "/" + config_file_name;
+
+            let mut json_lexer = match JsonLexer::new(&full_conf_name) {
+                Ok(json_lexer) => json_lexer,
+                Err(error) if index > 0 && error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => {
+                    println!("Unable to load config file: {} ({})", &full_conf_name, error);
+                    return None;
                 }
+            };
+
+            // Config files are edited by hand, so comments and trailing commas are allowed.
+            json_lexer.set_options(JsonOptions::relaxed());
 
-                None 
+            match parse_json(&mut json_lexer) {
+                Ok(layer) => layered_config.add_layer(config_file_name, &layer),
+                Err(error) => {
+                    println!("Unable to parse config file: {}", &full_conf_name);
+                    print!("{}", error.render_snippet(json_lexer.source()));
+                    return None;
+                }
             }
-            _ => {
-                println!("Unable to load config file: {}", &full_conf_name);
-                None
+        }
+
+        // Report where every value came from once overrides are in play.
+        if layered_config.layer_names().len() > 1 {
+            println!("Loaded config layers: {}", layered_config.layer_names().join(", "));
+
+            for (pointer, layer) in layered_config.sources() {
+                println!("    {} set by {}", pointer, layer);
+            }
+        }
+
+        // Check the whole config up front so every mistake is reported at once.
+        let violations = app_config_schema().validate(layered_config.node());
+
+        if !violations.is_empty() {
+            println!("Invalid config: {}", layered_config.layer_names().join(", "));
+
+            for violation in violations.iter() {
+                match layered_config.source_of(&violation.path) {
+                    Some(layer) => println!("    {} (set by {})", violation, layer),
+                    None => println!("    {}", violation)
+                }
             }
+
+            return None;
+        }
+
+        let app_config = match AppConfig::from_json(layered_config.node()) {
+            Ok(app_config) => app_config,
+            Err(error) => {
+                match layered_config.source_of(&error.pointer()) {
+                    Some(layer) => println!("Invalid config value set by {}", layer),
+                    None => println!("Invalid config: {}", layered_config.layer_names().join(", "))
+                }
+
+                println!("{}", error);
+                return None;
+            }
+        };
+
+        let engine = Self::new(app_config.window_config());
+
+        // Load game resources.
+        match engine {
+            Some(mut game_manager) => {
+                game_manager.resources.res_path = res_path.to_string();
+
+                // Load data from the "resources" object into the resource manager.
+                load_config_resources(&app_config.resources, &mut game_manager.resources);
+                game_manager.game_tick = GameTick::new(app_config.game.update_tick_rate);
+                Some(game_manager)
+            }
+            None => None
         }
     }
 
@@ -264,204 +332,159 @@ impl GameManager {
     }
 }
 
-#[derive(Default, Clone, Copy)]
-struct GameConfig {
-    update_tick_rate: i32
+/// The schema every app config is checked against before it is loaded.
+const APP_CONFIG_SCHEMA: &str = include_str!("app_config.schema.json");
+
+pub(crate) fn app_config_schema() -> JsonSchema {
+    // The schema is bundled with the engine, so failing to load it is a bug in the engine itself.
+    let mut json_lexer = JsonLexer::from_raw_json(APP_CONFIG_SCHEMA).unwrap();
+    let schema = parse_json(&mut json_lexer).expect("the bundled app config schema is not valid json");
+
+    JsonSchema::from_json(&schema).expect("the bundled app config schema is not a valid schema")
 }
 
-/// Loads data about the game config.
-fn load_game_config(game_config: &JsonNode) -> GameConfig {
-    let mut loaded_game_config = GameConfig::default();
-
-    match game_config {
-        JsonNode::Object(entire_object) => {
-            match entire_object.get("game") {
-                Some(JsonNode::Object(game_object)) => {
-                    match game_object.get("update_tick_rate") {
-                        Some(JsonNode::Number(update_tick_rate)) => {
-                            loaded_game_config.update_tick_rate = *update_tick_rate.get() as i32;
-                        },
-                        _ => {}
-                    }
-                },
-                _ => {}
-            }
-        },
-        _ => {}
+/// The layout of the app config file.
+/// Every section is optional, anything left out uses the engine defaults.
+#[derive(Default, FromJson)]
+#[json(default)]
+pub(crate) struct AppConfig {
+    window_config: WindowSection,
+    graphics: GraphicsSection,
+    resources: ResourcesSection,
+    pub(crate) game: GameConfig
+}
+
+impl AppConfig {
+    /// Combines the window and graphics sections into the config used to open the window.
+    fn window_config(&self) -> WindowConfig {
+        let mut config = WindowConfig::default();
+
+        config.xres = self.window_config.xres;
+        config.yres = self.window_config.yres;
+        config.title = self.window_config.title.clone();
+        config.resizable = self.window_config.resizable;
+
+        config.clear_color.r = self.graphics.clear_color.r;
+        config.clear_color.g = self.graphics.clear_color.g;
+        config.clear_color.b = self.graphics.clear_color.b;
+        config.vsync = self.graphics.vsync;
+
+        config
     }
+}
 
-    loaded_game_config
+#[derive(FromJson)]
+#[json(default)]
+struct WindowSection {
+    xres: u32,
+    yres: u32,
+    title: String,
+    resizable: bool
 }
 
-/// Parses resources from the config file into named game resources.
-fn load_config_resources(user_config: &JsonNode, game_resources: &mut GameResources) {
-    match user_config {
-        JsonNode::Object(entire_object) => {
-            match entire_object.get("resources") {
-                Some(JsonNode::Object(resources_object)) => {
-                    // Load textures.
-                    match resources_object.get("textures") {
-                        Some(JsonNode::Object(textures_object)) => {
-                            load_textures(textures_object, &mut game_resources.texture_resources, &game_resources.res_path);
-                        }
-                        _ => {}
-                    }
+impl Default for WindowSection {
+    fn default() -> Self {
+        let config = WindowConfig::default();
 
-                    // Load shaders.
-                    match resources_object.get("shaders") {
-                        Some(JsonNode::Object(shaders_object)) => {
-                            load_shaders(shaders_object, &mut game_resources.shader_resouces, &game_resources.res_path);
-                        }
-                        _ => {}
-                    }
-                    // Load framebuffers.
-                    // Load models.
-                }
-                _ => {}
-            }
-        },
-        // No object specified: nothing to load.
-        _ => {}
+        WindowSection {
+            xres: config.xres,
+            yres: config.yres,
+            title: config.title,
+            resizable: config.resizable
+        }
     }
 }
 
-/// load textures in textures_object into memory with the given name.
-fn load_textures(textures_object: &JsonObject, texture_resources: &mut ResourceManager<Texture>, res_path: &str) {
-    let stopwatch = Stopwatch::new();
+#[derive(FromJson)]
+#[json(default)]
+struct GraphicsSection {
+    clear_color: ClearColorSection,
+    vsync: bool
+}
 
-    for (texture, path) in textures_object.iter() {
-        match path {
-            JsonNode::String(texture_file_path) => {
-                // Load the texture, otherwise warning.
-                let texture_path = res_path.to_string() + "/" + texture_file_path.get();
-                let loaded_texture = Texture::open(&texture_path);
-
-                match loaded_texture {
-                    Ok(loaded_texture) => {
-                        // Transfer ownership of the loaded texture to the registry.
-                        texture_resources.add_registry(texture, loaded_texture);
-                        //println!("Loaded texture: {} {}", texture, texture_path);
-                    }
-                    _ => {
-                        println!("Failed to load texture: {} {}", texture, texture_file_path.get());
-                    }
-                }
-            },
-            _ => {}
+impl Default for GraphicsSection {
+    fn default() -> Self {
+        GraphicsSection {
+            clear_color: ClearColorSection::default(),
+            vsync: WindowConfig::default().vsync
         }
     }
+}
 
-    println!("Loaded game textures in {} seconds", stopwatch.elapsed_seconds());
+#[derive(Default, FromJson)]
+#[json(default)]
+struct ClearColorSection {
+    r: f32,
+    g: f32,
+    b: f32
+}
+
+#[derive(Default, FromJson)]
+#[json(default)]
+struct ResourcesSection {
+    /// Maps the name of each texture to its path in the res folder.
+    textures: HashMap<String, String>,
+    shaders: HashMap<String, ShaderSection>
 }
 
-fn load_shaders(shaders_object: &JsonObject, shader_resources: &mut ResourceManager<ShaderProgram>, res_path: &str) {
+#[derive(FromJson)]
+struct ShaderSection {
+    vertex: String,
+    fragment: String
+}
+
+#[derive(Default, Clone, Copy, FromJson)]
+#[json(default)]
+pub(crate) struct GameConfig {
+    pub(crate) update_tick_rate: i32
+}
+
+/// Parses resources from the config file into named game resources.
+fn load_config_resources(resources: &ResourcesSection, game_resources: &mut GameResources) {
+    load_textures(&resources.textures, &mut game_resources.texture_resources, &game_resources.res_path);
+    load_shaders(&resources.shaders, &mut game_resources.shader_resouces, &game_resources.res_path);
+    // Load framebuffers.
+    // Load models.
+}
+
+/// load textures in textures_object into memory with the given name.
+fn load_textures(textures: &HashMap<String, String>, texture_resources: &mut ResourceManager<Texture>, res_path: &str) {
     let stopwatch = Stopwatch::new();
 
-    for (shader_name, shader_data) in shaders_object.iter() {
-        match shader_data {
-            JsonNode::Object(shader_data_object) => {
-                // Load the shader program if we can.
-                if let Some(JsonNode::String(vertex_shader_path)) = shader_data_object.get("vertex") {
-                    if let Some(JsonNode::String(fragment_shader_path)) = shader_data_object.get("fragment") {
-                        let vs_shader = res_path.to_string() + "/" + vertex_shader_path.get();
-                        let fs_shader = res_path.to_string() + "/" + fragment_shader_path.get();
-                        let shader_program = ShaderProgram::open_shaders(&vs_shader, &fs_shader);
-
-                        // If we successfully load a shader, attempt to load the next one.
-                        match shader_program {
-                            Ok(shader_program) => {
-                                shader_resources.add_registry(shader_name, shader_program);
-                                continue;
-                            }
-                            _ => {}
-                        }
-                    }
-                }
+    for (texture, texture_file_path) in textures.iter() {
+        // Load the texture, otherwise warning.
+        let texture_path = res_path.to_string() + "/" + texture_file_path;
+        let loaded_texture = Texture::open(&texture_path);
+
+        match loaded_texture {
+            Ok(loaded_texture) => {
+                // Transfer ownership of the loaded texture to the registry.
+                texture_resources.add_registry(texture, loaded_texture);
+                //println!("Loaded texture: {} {}", texture, texture_path);
+            }
+            _ => {
+                println!("Failed to load texture: {} {}", texture, texture_file_path);
             }
-            _ => {}
         }
-
-        println!("Expected JsonObject with \"vertex\": path, and \"fragment\": path in loaded shader definition in {}", shader_name);
     }
 
-    println!("Loaded game shaders in {} seconds", stopwatch.elapsed_seconds());
+    println!("Loaded game textures in {} seconds", stopwatch.elapsed_seconds());
 }
 
-/// Loads an app config. When I learn how macros work, determine a way
-/// to set the following attributes 
-/// path: ex("/window/xres")
-/// valid_types: ex("bool, Number") -> should be implemented as multiple struct members.
-///     ex: Option<xpos_number>: i32,
-///         Option<xpos_string>: String
-///     This becomes important when setting it to a boolean value negates the 
-///     need for an integer value. 
-/// required: ex(true) -> simply tells the system whether the setting must be included in the file.
-/// default_value: ex(1920)
-/// ^^ TODO
-fn load_window_config(user_config: &JsonNode) -> WindowConfig {
-    let mut config = WindowConfig::default();
-
-    match user_config {
-        // At the base layer, we expect to see just an object.
-        // It should be an object of its own with a window_config key holding all the info about the window.
-        JsonNode::Object(file_object) => {
-            match file_object.get("window_config") { 
-                Some(JsonNode::Object(window_object)) => {
-                    // Load all the window data.
-                    if let Some(JsonNode::Number(xres)) = window_object.get("xres") {
-                        config.xres = *xres.get() as u32;
-                    }
-
-                    if let Some(JsonNode::Number(yres)) = window_object.get("yres") {
-                        config.yres = *yres.get() as u32;
-                    }
-
-                    if let Some(JsonNode::String(title)) = window_object.get("title") {
-                        config.title = title.get().clone();
-                    }
-
-                    if let Some(JsonNode::Bool(resizable)) = window_object.get("resizable") {
-                        config.resizable = *resizable.get();
-                    }
-                }
-                // The window config is not required.
-                _ => {}
-            }
+fn load_shaders(shaders: &HashMap<String, ShaderSection>, shader_resources: &mut ResourceManager<ShaderProgram>, res_path: &str) {
+    let stopwatch = Stopwatch::new();
 
-            // Load the graphics configuration.
-            match file_object.get("graphics") {
-                Some(JsonNode::Object(graphics_object)) => {
-                    // Load the clear color attribute.
-                    match graphics_object.get("clear_color") {
-                        Some(JsonNode::Object(clear_color_object)) => {
-                            if let Some(JsonNode::Float(r)) = clear_color_object.get("r") {
-                                config.clear_color.r = *r.get() as f32;
-                            }
-
-                            if let Some(JsonNode::Float(g)) = clear_color_object.get("g") {
-                                config.clear_color.g = *g.get() as f32;
-                            }
-
-                            if let Some(JsonNode::Float(b)) = clear_color_object.get("b") {
-                                config.clear_color.b = *b.get() as f32;
-                            }
-                        }
-                        _ => {}
-                    }
-
-                    match graphics_object.get("vsync") {
-                        Some(JsonNode::Bool(vsync_setting)) => {
-                            config.vsync = *vsync_setting.get()
-                        },
-                        _ => {}
-                    }
-                },
-                // If we don't have a graphics branch, don't try to load it.
-                _ => {}
-            }
-        },
-        _ => {}
+    for (shader_name, shader_data) in shaders.iter() {
+        // Load the shader program if we can.
+        let vs_shader = res_path.to_string() + "/" + &shader_data.vertex;
+        let fs_shader = res_path.to_string() + "/" + &shader_data.fragment;
+        let shader_program = ShaderProgram::open_shaders(&vs_shader, &fs_shader);
+
+        match shader_program {
+            Ok(shader_program) => shader_resources.add_registry(shader_name, shader_program),
+            _ => println!("Failed to load shader: {} {} {}", shader_name, shader_data.vertex, shader_data.fragment)
+        }
     }
 
-    config
+    println!("Loaded game shaders in {} seconds", stopwatch.elapsed_seconds());
 }
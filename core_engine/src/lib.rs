@@ -7,4 +7,6 @@ pub mod render_pipeline;
 
 pub use window::window::*;
 pub use window::Key;
-pub use window::MouseButton;
\ No newline at end of file
+pub use window::MouseButton;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,33 @@
+use serializers::json::lexer::JsonLexer;
+use serializers::json::parser::{JsonNode, parse_json};
+use serializers::json::typed::FromJson;
+
+use crate::engine::{AppConfig, app_config_schema};
+
+fn parse(raw_json: &str) -> JsonNode {
+    let mut json_lexer = JsonLexer::from_raw_json(raw_json).unwrap();
+    parse_json(&mut json_lexer).unwrap()
+}
+
+fn violations(raw_json: &str) -> usize {
+    app_config_schema().validate(&parse(raw_json)).len()
+}
+
+#[test]
+fn test_app_config_schema() {
+    assert_eq!(violations("{}"), 0);
+
+    // A tick rate of 0 means the updates aren't limited.
+    assert_eq!(violations(r#"{"game": {"update_tick_rate": 0}}"#), 0);
+    assert_eq!(violations(r#"{"game": {"update_tick_rate": 60}}"#), 0);
+    assert_eq!(violations(r#"{"game": {"update_tick_rate": -1}}"#), 1);
+    assert_eq!(violations(r#"{"game": {"update_tick_rate": 1.5}}"#), 1);
+}
+
+#[test]
+fn test_app_config_loads_what_the_schema_accepts() {
+    for (raw_json, tick_rate) in [(r#"{"game": {"update_tick_rate": 60}}"#, 60), (r#"{"game": {"update_tick_rate": 60.0}}"#, 60)] {
+        assert_eq!(violations(raw_json), 0);
+        assert_eq!(AppConfig::from_json(&parse(raw_json)).unwrap().game.update_tick_rate, tick_rate);
+    }
+}
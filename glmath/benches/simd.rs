@@ -0,0 +1,98 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use glmath::glmath::{simd, Mat44f, Quatf, Transformf, Vec3f, Vec4f, StandardMat, StandardQuat, StandardVec};
+
+/// A transform with rotation, scale and translation, the common case for inverting.
+fn test_matrix(seed: f32) -> Mat44f {
+    Transformf::new(
+        Vec3f::new(seed * 3.0, -seed, 12.5),
+        Quatf::from_axis_angle(Vec3f::new(1.0, seed, -2.0).get_normalized(), seed),
+        Vec3f::new(2.0, 2.0, 2.0)).to_matrix()
+}
+
+fn bench_mat44(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mat44");
+    let a = test_matrix(0.7);
+    let b = test_matrix(1.9);
+
+    group.bench_function("mul/scalar", |bench| bench.iter(|| black_box(a) * black_box(b)));
+    group.bench_function("mul/simd", |bench| bench.iter(|| simd::mat44_mul(black_box(&a), black_box(&b))));
+
+    group.bench_function("inverse/scalar", |bench| bench.iter(|| black_box(a).get_inverted()));
+    group.bench_function("inverse/simd", |bench| bench.iter(|| simd::mat44_inverse(black_box(&a))));
+
+    group.bench_function("transpose/scalar", |bench| bench.iter(|| black_box(a).get_transposed()));
+    group.bench_function("transpose/simd", |bench| bench.iter(|| simd::mat44_transpose(black_box(&a))));
+
+    group.finish();
+}
+
+fn bench_vec4(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transform_vec4s");
+    let mat = test_matrix(0.7);
+
+    for count in [16, 1000, 100_000] {
+        let vectors: Vec<Vec4f> = (0..count)
+            .map(|index| Vec4f::new(index as f32, -(index as f32) * 0.5, 3.0, 1.0))
+            .collect();
+
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("scalar", count), &vectors, |bench, vectors| {
+            let mut output = vectors.clone();
+            bench.iter(|| {
+                for (out, v) in output.iter_mut().zip(vectors.iter()) {
+                    *out = black_box(mat) * *v;
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("simd", count), &vectors, |bench, vectors| {
+            let mut output = vectors.clone();
+            bench.iter(|| {
+                output.copy_from_slice(vectors);
+                simd::transform_vec4s(black_box(&mat), &mut output);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_quat(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quat_mul");
+
+    // Batches, like chaining the rotations of a skeleton.
+    let pairs: Vec<(Quatf, Quatf)> = (0..1000)
+        .map(|index| {
+            let angle = index as f32 * 0.01;
+            (Quatf::from_axis_angle(Vec3f::X, angle),
+                Quatf::from_axis_angle(Vec3f::new(1.0, -2.0, angle).get_normalized(), 2.5))
+        })
+        .collect();
+
+    group.throughput(Throughput::Elements(pairs.len() as u64));
+
+    group.bench_with_input("scalar", &pairs, |bench, pairs| {
+        let mut output = vec![Quatf::IDENTITY; pairs.len()];
+        bench.iter(|| {
+            for (out, (a, b)) in output.iter_mut().zip(black_box(pairs).iter()) {
+                *out = *a * *b;
+            }
+        })
+    });
+
+    group.bench_with_input("simd", &pairs, |bench, pairs| {
+        let mut output = vec![Quatf::IDENTITY; pairs.len()];
+        bench.iter(|| {
+            for (out, (a, b)) in output.iter_mut().zip(black_box(pairs).iter()) {
+                *out = simd::quat_mul(*a, *b);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_mat44, bench_vec4, bench_quat);
+criterion_main!(benches);
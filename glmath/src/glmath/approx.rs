@@ -0,0 +1,242 @@
+use std::fmt::Debug;
+
+use crate::glmath::*;
+use crate::glmath::geometry::{
+    aabb::{Aabb2, Aabb3}, sphere::Sphere, plane::Plane, ray::Ray, segment::Segment, obb::Obb, frustum::Frustum};
+
+/// How close two floats have to be to count as equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance<T> {
+    /// The difference is at most the epsilon, for values near a known scale, ex: normalized vectors.
+    Absolute(T),
+    /// The difference is at most this fraction of the larger magnitude, for values of any scale.
+    /// Nothing is relatively close to zero except zero, compare with `Absolute` when expecting zeros.
+    Relative(T),
+    /// At most this many representable floats apart, ex: 4 allows for the rounding of a few operations.
+    Ulps(u32)
+}
+
+/// The float types the comparisons are made on.
+pub trait ApproxScalar: Copy + PartialOrd + Debug {
+    /// The number of representable floats between a and b, none when they have different signs or are NaN.
+    fn ulps_between(self, other: Self) -> Option<u64>;
+
+    /// Whether the two values are within the tolerance, NaN is never equal to anything.
+    fn within(self, other: Self, tolerance: Tolerance<Self>) -> bool;
+}
+
+macro_rules! impl_approx_scalar {
+    ($($float:ty),*) => {
+        $(
+            impl ApproxScalar for $float {
+                fn ulps_between(self, other: $float) -> Option<u64> {
+                    if self.is_nan() || other.is_nan() {
+                        None
+                    }
+                    else if self == other {
+                        Some(0)
+                    }
+                    else if self.is_sign_negative() != other.is_sign_negative() {
+                        None
+                    }
+                    else {
+                        // Floats of the same sign are ordered the same as their bits.
+                        Some(self.to_bits().abs_diff(other.to_bits()) as u64)
+                    }
+                }
+
+                fn within(self, other: $float, tolerance: Tolerance<$float>) -> bool {
+                    if self == other {
+                        return true;
+                    }
+
+                    // An infinity is only equal to itself, its difference to anything else is meaningless.
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+
+                    let difference = (self - other).abs();
+
+                    match tolerance {
+                        Tolerance::Absolute(epsilon) => difference <= epsilon,
+                        Tolerance::Relative(fraction) => difference <= fraction * self.abs().max(other.abs()),
+                        Tolerance::Ulps(ulps) => self.ulps_between(other).is_some_and(|between| between <= ulps as u64)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_scalar!(f32, f64);
+
+/// Approximate equality for floats and every type built from them.
+/// Values are equal when every pair of components is within the tolerance.
+pub trait ApproxEq {
+    type Scalar: ApproxScalar;
+
+    /// Calls f with every pair of components in memory order, ex: column major for matrices.
+    /// Stops and returns false as soon as f does.
+    fn zip_components<F: FnMut(Self::Scalar, Self::Scalar) -> bool>(&self, other: &Self, f: &mut F) -> bool;
+
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance<Self::Scalar>) -> bool {
+        self.zip_components(other, &mut |a, b| a.within(b, tolerance))
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Scalar) -> bool {
+        self.approx_eq(other, Tolerance::Absolute(epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, fraction: Self::Scalar) -> bool {
+        self.approx_eq(other, Tolerance::Relative(fraction))
+    }
+
+    fn ulps_eq(&self, other: &Self, ulps: u32) -> bool {
+        self.approx_eq(other, Tolerance::Ulps(ulps))
+    }
+}
+
+impl ApproxEq for f32 {
+    type Scalar = f32;
+
+    fn zip_components<F: FnMut(f32, f32) -> bool>(&self, other: &f32, f: &mut F) -> bool {
+        f(*self, *other)
+    }
+}
+
+impl ApproxEq for f64 {
+    type Scalar = f64;
+
+    fn zip_components<F: FnMut(f64, f64) -> bool>(&self, other: &f64, f: &mut F) -> bool {
+        f(*self, *other)
+    }
+}
+
+impl<A: ApproxEq, const N: usize> ApproxEq for [A; N] {
+    type Scalar = A::Scalar;
+
+    fn zip_components<F: FnMut(A::Scalar, A::Scalar) -> bool>(&self, other: &[A; N], f: &mut F) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.zip_components(b, f))
+    }
+}
+
+/// Implements `ApproxEq` for a type generic over its float by comparing each of the fields.
+macro_rules! impl_approx_eq {
+    ($($type:ident { $($field:ident),* }),*) => {
+        $(
+            impl<T: ApproxScalar + ApproxEq<Scalar = T>> ApproxEq for $type<T> {
+                type Scalar = T;
+
+                fn zip_components<F: FnMut(T, T) -> bool>(&self, other: &$type<T>, f: &mut F) -> bool {
+                    $(self.$field.zip_components(&other.$field, f))&&*
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_eq!(
+    Vec2 { x, y },
+    Vec3 { x, y, z },
+    Vec4 { x, y, z, w },
+    Quat { x, y, z, w },
+    Transform { translation, rotation, scale },
+    Aabb2 { min, max },
+    Aabb3 { min, max },
+    Sphere { center, radius },
+    Plane { normal, distance },
+    Ray { origin, direction },
+    Segment { start, end },
+    Obb { center, half_extents, axes },
+    Frustum { planes });
+
+impl<T: ApproxScalar + ApproxEq<Scalar = T>, const N: usize> ApproxEq for Vector<T, N> {
+    type Scalar = T;
+
+    fn zip_components<F: FnMut(T, T) -> bool>(&self, other: &Vector<T, N>, f: &mut F) -> bool {
+        self.data.zip_components(&other.data, f)
+    }
+}
+
+impl<T: ApproxScalar + ApproxEq<Scalar = T>, const R: usize, const C: usize> ApproxEq for Matrix<T, R, C> {
+    type Scalar = T;
+
+    fn zip_components<F: FnMut(T, T) -> bool>(&self, other: &Matrix<T, R, C>, f: &mut F) -> bool {
+        self.data.zip_components(&other.data, f)
+    }
+}
+
+/// Describes why two values are not approximately equal, used by the assert macros.
+/// Returns none when they are equal.
+pub fn approx_eq_failure<A: ApproxEq + Debug>(left: &A, right: &A, tolerance: Tolerance<A::Scalar>) -> Option<String> {
+    let mut index = 0;
+    let mut differences = String::new();
+
+    left.zip_components(right, &mut |a, b| {
+        if !a.within(b, tolerance) {
+            let ulps = a.ulps_between(b).map_or("different signs".to_string(), |ulps| format!("{} ulps apart", ulps));
+            differences += &format!("\n  component {}: {:?} != {:?} ({})", index, a, b, ulps);
+        }
+
+        index += 1;
+        true
+    });
+
+    if differences.is_empty() {
+        None
+    }
+    else {
+        Some(format!("assertion `left ≈ right` failed with {:?}\n  left: {:?}\n right: {:?}{}",
+            tolerance, left, right, differences))
+    }
+}
+
+/// Asserts two glmath values are approximately equal, showing the components which differ when they aren't.
+/// The tolerance defaults to 4 ulps, enough for the rounding of a few operations.
+///
+/// ```
+/// use glmath::assert_approx_eq;
+/// use glmath::glmath::{Vec3f, approx::Tolerance};
+///
+/// assert_approx_eq!(Vec3f::new(0.1, 0.2, 0.3) * 3.0, Vec3f::new(0.3, 0.6, 0.9));
+/// assert_approx_eq!(1000.0f32, 1000.01, Tolerance::Relative(1e-4));
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr) => {
+        $crate::assert_approx_eq!($left, $right, $crate::glmath::approx::Tolerance::Ulps(4))
+    };
+    ($left:expr, $right:expr, $tolerance:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(message) = $crate::glmath::approx::approx_eq_failure(left, right, $tolerance) {
+                    panic!("{}", message);
+                }
+            }
+        }
+    };
+}
+
+/// Asserts the difference of every component is at most epsilon.
+#[macro_export]
+macro_rules! assert_abs_diff_eq {
+    ($left:expr, $right:expr, $epsilon:expr) => {
+        $crate::assert_approx_eq!($left, $right, $crate::glmath::approx::Tolerance::Absolute($epsilon))
+    };
+}
+
+/// Asserts the difference of every component is at most a fraction of the larger magnitude.
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($left:expr, $right:expr, $fraction:expr) => {
+        $crate::assert_approx_eq!($left, $right, $crate::glmath::approx::Tolerance::Relative($fraction))
+    };
+}
+
+/// Asserts every component is at most this many representable floats apart.
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($left:expr, $right:expr, $ulps:expr) => {
+        $crate::assert_approx_eq!($left, $right, $crate::glmath::approx::Tolerance::Ulps($ulps))
+    };
+}
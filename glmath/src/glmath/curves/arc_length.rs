@@ -0,0 +1,74 @@
+use crate::glmath::geometry::{Scalar, clamp};
+use crate::glmath::curves::{Curve, integrate_speed};
+
+/// The distance along a curve at evenly spaced values of t, to move along the curve at a constant speed.
+/// Distances between the samples are interpolated linearly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLength<T> {
+    /// The distance from the start at t = i / (len - 1).
+    distances: Vec<T>
+}
+
+impl<T: Scalar> ArcLength<T> {
+    /// The samples `Curve::arc_length` takes of each segment, enough that the distances are off by less
+    /// than 1e-3 of a segment's length unless it bends sharply.
+    pub const SAMPLES_PER_SEGMENT: usize = 32;
+
+    /// Measures a curve at the given number of intervals.
+    pub fn new<C: Curve<T> + ?Sized>(curve: &C, intervals: usize) -> ArcLength<T> {
+        let intervals = intervals.max(1);
+        let mut distances = Vec::with_capacity(intervals + 1);
+        let mut distance = T::ZERO;
+        let mut start = T::ZERO;
+
+        distances.push(distance);
+
+        for i in 1..=intervals {
+            let end = T::from_f64(i as f64 / intervals as f64);
+            distance += integrate_speed(curve, start, end, 1);
+            distances.push(distance);
+            start = end;
+        }
+
+        ArcLength::<T> { distances }
+    }
+
+    /// The length of the whole curve.
+    pub fn length(&self) -> T {
+        self.distances[self.distances.len() - 1]
+    }
+
+    /// The value of t at a distance from the start, clamped to the ends of the curve.
+    pub fn parameter_at(&self, distance: T) -> T {
+        let distance = clamp(distance, T::ZERO, self.length());
+        let intervals = self.distances.len() - 1;
+        let end = self.distances.partition_point(|sample| *sample < distance).clamp(1, intervals);
+
+        let start_distance = self.distances[end - 1];
+        let interval_length = self.distances[end] - start_distance;
+
+        let fraction = if interval_length > T::ZERO {
+            (distance - start_distance) / interval_length
+        }
+        else {
+            T::ZERO
+        };
+
+        (T::from_f64((end - 1) as f64) + fraction) / T::from_f64(intervals as f64)
+    }
+
+    /// The distance from the start at a value of t.
+    pub fn distance_at(&self, t: T) -> T {
+        let intervals = self.distances.len() - 1;
+        let scaled = clamp(t, T::ZERO, T::ONE) * T::from_f64(intervals as f64);
+        let start = (scaled.to_f64() as usize).min(intervals - 1);
+        let fraction = scaled - T::from_f64(start as f64);
+
+        self.distances[start] + (self.distances[start + 1] - self.distances[start]) * fraction
+    }
+
+    /// The value of t a fraction of the length along the curve, ex: 0.5 is halfway along however the curve speeds up.
+    pub fn parameter_at_fraction(&self, fraction: T) -> T {
+        self.parameter_at(fraction * self.length())
+    }
+}
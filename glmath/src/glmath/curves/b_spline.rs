@@ -0,0 +1,94 @@
+use crate::glmath::geometry::Scalar;
+use crate::glmath::curves::*;
+
+/// A uniform cubic B-spline, a smooth curve pulled towards the points without passing through them.
+/// Smoother than a `CatmullRom` through the same points, the curvature changes without jumps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<P> {
+    points: Vec<P>
+}
+
+impl<P: Copy + Add<Output = P> + Sub<Output = P>> BSpline<P> {
+    /// Returns None when there are fewer than 4 points.
+    pub fn new(points: Vec<P>) -> Option<BSpline<P>> {
+        if points.len() < 4 {
+            return None;
+        }
+
+        Some(BSpline::<P> { points })
+    }
+
+    /// A spline starting at the first point and ending at the last, towards the second and second last points.
+    /// Adds a point before the first mirroring the second through it, and one after the last mirroring the second last.
+    /// Returns None when there are fewer than 2 points.
+    pub fn clamped(points: &[P]) -> Option<BSpline<P>> {
+        if points.len() < 2 {
+            return None;
+        }
+
+        let first = points[0];
+        let last = points[points.len() - 1];
+        let mut all = Vec::with_capacity(points.len() + 2);
+
+        all.push(first + (first - points[1]));
+        all.extend_from_slice(points);
+        all.push(last + (last - points[points.len() - 2]));
+
+        Some(BSpline::<P> { points: all })
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for BSpline<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        spline_at(&self.points, t, 0, |t| {
+            let s = T::ONE - t;
+            let (t2, t3) = (t * t, t * t * t);
+            let (three, four, sixth) = (T::from_f64(3.0), T::from_f64(4.0), T::from_f64(1.0 / 6.0));
+
+            [
+                s * s * s * sixth,
+                (three * t3 - T::TWO * three * t2 + four) * sixth,
+                (three * (t2 + t - t3) + T::ONE) * sixth,
+                t3 * sixth
+            ]
+        })
+    }
+
+    fn derivative(&self, t: T) -> P {
+        spline_at(&self.points, t, 1, |t| {
+            let s = T::ONE - t;
+            let t2 = t * t;
+            let three_halves = T::from_f64(1.5);
+
+            [
+                -s * s * T::HALF,
+                three_halves * t2 - T::TWO * t,
+                t + T::HALF - three_halves * t2,
+                t2 * T::HALF
+            ]
+        })
+    }
+
+    fn second_derivative(&self, t: T) -> P {
+        spline_at(&self.points, t, 2, |t| {
+            let three = T::from_f64(3.0);
+
+            [
+                T::ONE - t,
+                three * t - T::TWO,
+                T::ONE - three * t,
+                t
+            ]
+        })
+    }
+
+    fn segment_count(&self) -> usize {
+        self.points.len() - 3
+    }
+}
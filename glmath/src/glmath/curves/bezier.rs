@@ -0,0 +1,113 @@
+use crate::glmath::geometry::{Scalar, clamp};
+use crate::glmath::curves::*;
+
+/// A straight line between two points, moving at a constant speed.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct LinearBezier<P> {
+    pub start: P,
+    pub end: P
+}
+
+/// A curve from start to end bending towards a control point, a parabola.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct QuadraticBezier<P> {
+    pub start: P,
+    pub control: P,
+    pub end: P
+}
+
+/// A curve from start to end, leaving start towards the first control point
+/// and arriving at end from the direction of the second.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct CubicBezier<P> {
+    pub start: P,
+    pub start_control: P,
+    pub end_control: P,
+    pub end: P
+}
+
+impl<P> LinearBezier<P> {
+    pub fn new(start: P, end: P) -> LinearBezier<P> {
+        LinearBezier::<P> { start, end }
+    }
+}
+
+impl<P> QuadraticBezier<P> {
+    pub fn new(start: P, control: P, end: P) -> QuadraticBezier<P> {
+        QuadraticBezier::<P> { start, control, end }
+    }
+}
+
+impl<P> CubicBezier<P> {
+    pub fn new(start: P, start_control: P, end_control: P, end: P) -> CubicBezier<P> {
+        CubicBezier::<P> { start, start_control, end_control, end }
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for LinearBezier<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        self.start + (self.end - self.start) * clamp(t, T::ZERO, T::ONE)
+    }
+
+    fn derivative(&self, _t: T) -> P {
+        self.end - self.start
+    }
+
+    fn second_derivative(&self, _t: T) -> P {
+        (self.end - self.start) * T::ZERO
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for QuadraticBezier<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let s = T::ONE - t;
+
+        weighted_sum(&[self.start, self.control, self.end], [s * s, T::TWO * s * t, t * t])
+    }
+
+    fn derivative(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        (self.control - self.start) * (T::TWO * (T::ONE - t)) + (self.end - self.control) * (T::TWO * t)
+    }
+
+    fn second_derivative(&self, _t: T) -> P {
+        (self.end - self.control - (self.control - self.start)) * T::TWO
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for CubicBezier<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let s = T::ONE - t;
+        let three = T::from_f64(3.0);
+
+        weighted_sum(&[self.start, self.start_control, self.end_control, self.end],
+            [s * s * s, three * s * s * t, three * s * t * t, t * t * t])
+    }
+
+    /// A quadratic bezier through the differences of the control points.
+    fn derivative(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let s = T::ONE - t;
+        let three = T::from_f64(3.0);
+
+        weighted_sum(&[self.start_control - self.start, self.end_control - self.start_control, self.end - self.end_control],
+            [three * s * s, three * T::TWO * s * t, three * t * t])
+    }
+
+    fn second_derivative(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let six = T::from_f64(6.0);
+        let start_bend = self.end_control - self.start_control - (self.start_control - self.start);
+        let end_bend = self.end - self.end_control - (self.end_control - self.start_control);
+
+        start_bend * (six * (T::ONE - t)) + end_bend * (six * t)
+    }
+}
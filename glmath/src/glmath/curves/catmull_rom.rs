@@ -0,0 +1,93 @@
+use crate::glmath::geometry::Scalar;
+use crate::glmath::curves::*;
+
+/// A spline passing through every point except the first and last, which only set the direction it starts and ends in.
+/// Each segment between two points is shaped by the points either side of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<P> {
+    points: Vec<P>
+}
+
+impl<P: Copy + Add<Output = P> + Sub<Output = P>> CatmullRom<P> {
+    /// Returns None when there are fewer than 4 points.
+    pub fn new(points: Vec<P>) -> Option<CatmullRom<P>> {
+        if points.len() < 4 {
+            return None;
+        }
+
+        Some(CatmullRom::<P> { points })
+    }
+
+    /// A spline passing through every point, including the first and last.
+    /// Adds a point before the first mirroring the second through it, and one after the last mirroring the second last.
+    /// Returns None when there are fewer than 2 points.
+    pub fn through(points: &[P]) -> Option<CatmullRom<P>> {
+        if points.len() < 2 {
+            return None;
+        }
+
+        let first = points[0];
+        let last = points[points.len() - 1];
+        let mut all = Vec::with_capacity(points.len() + 2);
+
+        all.push(first + (first - points[1]));
+        all.extend_from_slice(points);
+        all.push(last + (last - points[points.len() - 2]));
+
+        Some(CatmullRom::<P> { points: all })
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for CatmullRom<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        spline_at(&self.points, t, 0, |t| {
+            let (t2, t3) = (t * t, t * t * t);
+            let (three, four, five) = (T::from_f64(3.0), T::from_f64(4.0), T::from_f64(5.0));
+
+            [
+                (T::TWO * t2 - t - t3) * T::HALF,
+                (T::TWO - five * t2 + three * t3) * T::HALF,
+                (t + four * t2 - three * t3) * T::HALF,
+                (t3 - t2) * T::HALF
+            ]
+        })
+    }
+
+    fn derivative(&self, t: T) -> P {
+        spline_at(&self.points, t, 1, |t| {
+            let t2 = t * t;
+            let (three, four, eight, nine, ten) =
+                (T::from_f64(3.0), T::from_f64(4.0), T::from_f64(8.0), T::from_f64(9.0), T::from_f64(10.0));
+
+            [
+                (four * t - T::ONE - three * t2) * T::HALF,
+                (nine * t2 - ten * t) * T::HALF,
+                (T::ONE + eight * t - nine * t2) * T::HALF,
+                (three * t2 - T::TWO * t) * T::HALF
+            ]
+        })
+    }
+
+    fn second_derivative(&self, t: T) -> P {
+        spline_at(&self.points, t, 2, |t| {
+            let (three, four, five, nine) = (T::from_f64(3.0), T::from_f64(4.0), T::from_f64(5.0), T::from_f64(9.0));
+
+            [
+                T::TWO - three * t,
+                nine * t - five,
+                four - nine * t,
+                three * t - T::ONE
+            ]
+        })
+    }
+
+    fn segment_count(&self) -> usize {
+        self.points.len() - 3
+    }
+}
@@ -0,0 +1,66 @@
+use crate::glmath::geometry::{Scalar, clamp};
+use crate::glmath::curves::*;
+
+/// A cubic curve from start to end with the given derivatives at each end.
+/// The same curve as a `CubicBezier` with control points a third of each tangent in from the ends.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Hermite<P> {
+    pub start: P,
+    pub start_tangent: P,
+    pub end: P,
+    pub end_tangent: P
+}
+
+impl<P> Hermite<P> {
+    pub fn new(start: P, start_tangent: P, end: P, end_tangent: P) -> Hermite<P> {
+        Hermite::<P> { start, start_tangent, end, end_tangent }
+    }
+}
+
+impl<P: Copy> Hermite<P> {
+    fn points(&self) -> [P; 4] {
+        [self.start, self.start_tangent, self.end, self.end_tangent]
+    }
+}
+
+impl<T: Scalar, P: CurvePoint<T>> Curve<T> for Hermite<P> {
+    type Point = P;
+
+    fn at(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let (t2, t3) = (t * t, t * t * t);
+        let (two, three) = (T::TWO, T::from_f64(3.0));
+
+        weighted_sum(&self.points(), [
+            two * t3 - three * t2 + T::ONE,
+            t3 - two * t2 + t,
+            three * t2 - two * t3,
+            t3 - t2
+        ])
+    }
+
+    fn derivative(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let t2 = t * t;
+        let (three, four, six) = (T::from_f64(3.0), T::from_f64(4.0), T::from_f64(6.0));
+
+        weighted_sum(&self.points(), [
+            six * t2 - six * t,
+            three * t2 - four * t + T::ONE,
+            six * t - six * t2,
+            three * t2 - T::TWO * t
+        ])
+    }
+
+    fn second_derivative(&self, t: T) -> P {
+        let t = clamp(t, T::ZERO, T::ONE);
+        let (four, six, twelve) = (T::from_f64(4.0), T::from_f64(6.0), T::from_f64(12.0));
+
+        weighted_sum(&self.points(), [
+            twelve * t - six,
+            six * t - four,
+            six - twelve * t,
+            six * t - T::TWO
+        ])
+    }
+}
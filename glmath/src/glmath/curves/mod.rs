@@ -0,0 +1,163 @@
+pub mod bezier;
+pub mod hermite;
+pub mod catmull_rom;
+pub mod b_spline;
+pub mod arc_length;
+pub mod squad;
+
+use std::ops::{Add, Sub, Mul};
+use crate::glmath::*;
+use crate::glmath::geometry::{Scalar, clamp};
+use arc_length::ArcLength;
+
+/// Every type a curve can pass through, the vectors of any size.
+pub trait CurvePoint<T: Scalar>: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<T, Output = Self> + StandardVec<T> {}
+
+impl<T: Scalar, P: Copy + Add<Output = P> + Sub<Output = P> + Mul<T, Output = P> + StandardVec<T>> CurvePoint<T> for P {}
+
+/// The steps each segment is split into when measuring its length, each measured at 5 points.
+const LENGTH_STEPS: usize = 8;
+/// The samples taken along each segment to start the search for the closest point.
+const CLOSEST_SAMPLES: usize = 16;
+const CLOSEST_ITERATIONS: usize = 8;
+
+/// A curve which runs from t = 0 at its start to t = 1 at its end, values of t outside of that are clamped.
+/// Splines made of several segments spend an equal range of t in each segment, so the speed changes
+/// along them, use an `ArcLength` table to move along them at a constant speed.
+pub trait Curve<T: Scalar> {
+    type Point: CurvePoint<T>;
+
+    fn at(&self, t: T) -> Self::Point;
+
+    /// The rate of change of the point with t, the velocity when t is time.
+    fn derivative(&self, t: T) -> Self::Point;
+
+    fn second_derivative(&self, t: T) -> Self::Point;
+
+    /// The number of polynomial pieces, the measurements sample each of them equally.
+    fn segment_count(&self) -> usize {
+        1
+    }
+
+    /// The direction of travel, zero where the curve stops.
+    fn tangent(&self, t: T) -> Self::Point {
+        self.derivative(t).get_normalized()
+    }
+
+    fn length(&self) -> T {
+        self.length_between(T::ZERO, T::ONE)
+    }
+
+    /// The distance along the curve between two values of t, negative when end is before start.
+    /// Integrates the speed with Gauss-Legendre quadrature, which is accurate to around 1e-6 of the length
+    /// unless the curve almost turns back on itself.
+    fn length_between(&self, start: T, end: T) -> T {
+        integrate_speed(self, start, end, self.segment_count() * LENGTH_STEPS)
+    }
+
+    /// Measures the curve at evenly spaced values of t, to convert between distances and values of t.
+    fn arc_length(&self) -> ArcLength<T> {
+        ArcLength::<T>::new(self, self.segment_count() * ArcLength::<T>::SAMPLES_PER_SEGMENT)
+    }
+
+    /// The value of t of the point on the curve closest to a point.
+    /// Samples the curve and refines the closest sample with Newton's method, a curve passing near the point
+    /// several times returns the closest pass, unless the passes are closer together than the samples.
+    fn closest_parameter(&self, point: Self::Point) -> T {
+        let samples = self.segment_count() * CLOSEST_SAMPLES;
+        let mut closest = T::ZERO;
+        let mut closest_distance_sq = (self.at(T::ZERO) - point).length_sq();
+
+        for i in 1..=samples {
+            let t = T::from_f64(i as f64 / samples as f64);
+            let distance_sq = (self.at(t) - point).length_sq();
+
+            if distance_sq < closest_distance_sq {
+                closest = t;
+                closest_distance_sq = distance_sq;
+            }
+        }
+
+        // The distance is smallest where the offset to the point is perpendicular to the curve.
+        let mut t = closest;
+
+        for _ in 0..CLOSEST_ITERATIONS {
+            let offset = self.at(t) - point;
+            let derivative = self.derivative(t);
+            let slope = derivative * derivative + offset * self.second_derivative(t);
+
+            if slope <= T::ZERO {
+                break;
+            }
+
+            let next = clamp(t - (offset * derivative) / slope, T::ZERO, T::ONE);
+
+            if next == t {
+                break;
+            }
+
+            t = next;
+        }
+
+        if (self.at(t) - point).length_sq() < closest_distance_sq {
+            t
+        }
+        else {
+            closest
+        }
+    }
+
+    fn closest_point(&self, point: Self::Point) -> Self::Point {
+        self.at(self.closest_parameter(point))
+    }
+}
+
+/// Integrates the speed of a curve with 5 point Gauss-Legendre quadrature over each of the steps.
+fn integrate_speed<T: Scalar, C: Curve<T> + ?Sized>(curve: &C, start: T, end: T, steps: usize) -> T {
+    let nodes = [0.0, -0.5384693101056831, 0.5384693101056831, -0.906179845938664, 0.906179845938664];
+    let weights = [0.5688888888888889, 0.4786286704993665, 0.4786286704993665, 0.2369268850561891, 0.2369268850561891];
+
+    let step = (end - start) / T::from_f64(steps as f64);
+    let mut length = T::ZERO;
+
+    for i in 0..steps {
+        let middle = start + step * (T::from_f64(i as f64) + T::HALF);
+
+        for (node, weight) in nodes.iter().zip(weights) {
+            let t = middle + step * T::HALF * T::from_f64(*node);
+            length += T::from_f64(weight) * curve.derivative(t).length();
+        }
+    }
+
+    length * step * T::HALF
+}
+
+/// The points scaled by the weights and summed, the weights are the basis functions of a curve at some t.
+fn weighted_sum<T: Scalar, P: CurvePoint<T>, const N: usize>(points: &[P; N], weights: [T; N]) -> P {
+    let mut sum = points[0] * weights[0];
+
+    for (point, weight) in points.iter().zip(weights).skip(1) {
+        sum = sum + *point * weight;
+    }
+
+    sum
+}
+
+/// Splits a t running over every segment of a spline into the segment it falls in and the t within it.
+fn locate_segment<T: Scalar>(t: T, segments: usize) -> (usize, T) {
+    let scaled = clamp(t, T::ZERO, T::ONE) * T::from_f64(segments as f64);
+    let index = (scaled.to_f64() as usize).min(segments - 1);
+
+    (index, scaled - T::from_f64(index as f64))
+}
+
+/// Evaluates a spline where each segment is built from 4 consecutive points, ex: Catmull-Rom and B-splines.
+/// The basis gives the weights of a derivative of the given order, which is scaled from the segment's t
+/// to the spline's t.
+fn spline_at<T: Scalar, P: CurvePoint<T>>(points: &[P], t: T, order: i32, basis: fn(T) -> [T; 4]) -> P {
+    let segments = points.len() - 3;
+    let (index, local) = locate_segment(t, segments);
+    let segment = [points[index], points[index + 1], points[index + 2], points[index + 3]];
+
+    weighted_sum(&segment, basis(local)) * T::from_f64((segments as f64).powi(order))
+}
@@ -0,0 +1,48 @@
+use crate::glmath::*;
+use crate::glmath::geometry::Scalar;
+use crate::glmath::curves::locate_segment;
+
+/// A rotation passing smoothly through a list of keys with `Quat::squad`, ex: the orientation along a camera path.
+/// t runs from 0 at the first key to 1 at the last, spending an equal range of t between each pair of keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Squad<T: PartialOrd + Copy> {
+    keys: Vec<Quat<T>>,
+    controls: Vec<Quat<T>>
+}
+
+impl<T: Scalar> Squad<T> {
+    /// Returns None when there are fewer than 2 keys.
+    /// Keys are negated where needed so each is within 180 degrees of the last, so the rotation always takes the short way.
+    pub fn new(keys: &[Quat<T>]) -> Option<Squad<T>> {
+        if keys.len() < 2 {
+            return None;
+        }
+
+        let mut aligned = Vec::with_capacity(keys.len());
+        aligned.push(keys[0].get_normalized());
+
+        for key in &keys[1..] {
+            let previous = aligned[aligned.len() - 1];
+            let key = key.get_normalized();
+
+            let dot = previous.x * key.x + previous.y * key.y + previous.z * key.z + previous.w * key.w;
+            aligned.push(if dot < T::ZERO { -key } else { key });
+        }
+
+        let last = aligned.len() - 1;
+        let controls = (0..aligned.len())
+            .map(|i| Quat::<T>::squad_control(aligned[i.saturating_sub(1)], aligned[i], aligned[(i + 1).min(last)]))
+            .collect();
+
+        Some(Squad::<T> { keys: aligned, controls })
+    }
+
+    pub fn keys(&self) -> &[Quat<T>] {
+        &self.keys
+    }
+
+    pub fn at(&self, t: T) -> Quat<T> {
+        let (i, blend) = locate_segment(t, self.keys.len() - 1);
+        Quat::<T>::squad(self.keys[i], self.keys[i + 1], self.controls[i], self.controls[i + 1], blend)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// An axis aligned rectangle between two corners.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Aabb2<T: PartialOrd + Copy> {
+    pub min: Vec2<T>,
+    pub max: Vec2<T>
+}
+
+impl<T: Scalar> Aabb2<T> {
+    /// Every component of min should be smaller than or equal to the same component of max.
+    pub fn new(min: Vec2<T>, max: Vec2<T>) -> Aabb2<T> {
+        Aabb2::<T> { min, max }
+    }
+
+    pub fn from_center(center: Vec2<T>, half_extents: Vec2<T>) -> Aabb2<T> {
+        Aabb2::<T> {
+            min: center - half_extents,
+            max: center + half_extents
+        }
+    }
+
+    /// The smallest box around all of the points, or None when there are no points.
+    pub fn from_points(points: &[Vec2<T>]) -> Option<Aabb2<T>> {
+        let (first, rest) = points.split_first()?;
+        let mut aabb = Aabb2::<T>::new(*first, *first);
+
+        for point in rest {
+            aabb.min = aabb.min.min(*point);
+            aabb.max = aabb.max.max(*point);
+        }
+
+        Some(aabb)
+    }
+
+    pub fn center(&self) -> Vec2<T> {
+        (self.min + self.max) * T::HALF
+    }
+
+    pub fn half_extents(&self) -> Vec2<T> {
+        (self.max - self.min) * T::HALF
+    }
+
+    pub fn size(&self) -> Vec2<T> {
+        self.max - self.min
+    }
+
+    /// Points on the edges are contained.
+    pub fn contains_point(&self, point: Vec2<T>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x &&
+            point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    pub fn contains(&self, other: &Aabb2<T>) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Boxes which only touch on an edge overlap.
+    pub fn overlaps(&self, other: &Aabb2<T>) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+            self.min.y <= other.max.y && self.max.y >= other.min.y
+    }
+
+    /// The closest point inside the box, which is the point itself when it is inside.
+    pub fn closest_point(&self, point: Vec2<T>) -> Vec2<T> {
+        point.clamp(self.min, self.max)
+    }
+
+    /// The smallest box around both boxes.
+    pub fn merged(&self, other: &Aabb2<T>) -> Aabb2<T> {
+        Aabb2::<T> {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max)
+        }
+    }
+}
+
+/// An axis aligned box between two corners.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Aabb3<T: PartialOrd + Copy> {
+    pub min: Vec3<T>,
+    pub max: Vec3<T>
+}
+
+impl<T: Scalar> Aabb3<T> {
+    /// Every component of min should be smaller than or equal to the same component of max.
+    pub fn new(min: Vec3<T>, max: Vec3<T>) -> Aabb3<T> {
+        Aabb3::<T> { min, max }
+    }
+
+    pub fn from_center(center: Vec3<T>, half_extents: Vec3<T>) -> Aabb3<T> {
+        Aabb3::<T> {
+            min: center - half_extents,
+            max: center + half_extents
+        }
+    }
+
+    /// The smallest box around all of the points, or None when there are no points.
+    pub fn from_points(points: &[Vec3<T>]) -> Option<Aabb3<T>> {
+        let (first, rest) = points.split_first()?;
+        let mut aabb = Aabb3::<T>::new(*first, *first);
+
+        for point in rest {
+            aabb.min = aabb.min.min(*point);
+            aabb.max = aabb.max.max(*point);
+        }
+
+        Some(aabb)
+    }
+
+    pub fn center(&self) -> Vec3<T> {
+        (self.min + self.max) * T::HALF
+    }
+
+    pub fn half_extents(&self) -> Vec3<T> {
+        (self.max - self.min) * T::HALF
+    }
+
+    pub fn size(&self) -> Vec3<T> {
+        self.max - self.min
+    }
+
+    /// Points on the faces are contained.
+    pub fn contains_point(&self, point: Vec3<T>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x &&
+            point.y >= self.min.y && point.y <= self.max.y &&
+            point.z >= self.min.z && point.z <= self.max.z
+    }
+
+    pub fn contains(&self, other: &Aabb3<T>) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Boxes which only touch on a face overlap.
+    pub fn overlaps(&self, other: &Aabb3<T>) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+            self.min.y <= other.max.y && self.max.y >= other.min.y &&
+            self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    pub fn overlaps_sphere(&self, sphere: &Sphere<T>) -> bool {
+        sphere.overlaps_aabb(self)
+    }
+
+    /// The closest point inside the box, which is the point itself when it is inside.
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        point.clamp(self.min, self.max)
+    }
+
+    /// The smallest box around both boxes.
+    pub fn merged(&self, other: &Aabb3<T>) -> Aabb3<T> {
+        Aabb3::<T> {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max)
+        }
+    }
+}
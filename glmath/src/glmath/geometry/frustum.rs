@@ -0,0 +1,107 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// The volume a camera can see, bounded by six planes with their normals pointing inwards.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Frustum<T: PartialOrd + Copy> {
+    /// The left, right, bottom, top, near and far planes.
+    pub planes: [Plane<T>; 6]
+}
+
+impl<T: Scalar> Frustum<T> {
+    pub const LEFT: usize = 0;
+    pub const RIGHT: usize = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize = 3;
+    pub const NEAR: usize = 4;
+    pub const FAR: usize = 5;
+
+    /// Extracts the planes from a `projection * view` matrix with a -1..1 clip depth,
+    /// such as `Mat44::perspective` or `Mat44::orthographic`. A projection matrix on its own gives a view space frustum.
+    pub fn from_matrix(mat: &Mat44<T>) -> Frustum<T> {
+        Frustum::<T>::from_matrix_with_depth(mat, -T::ONE, T::ONE)
+    }
+
+    /// Extracts the planes from a reversed-z `projection * view` matrix, such as `Mat44::perspective_reversed_z`.
+    pub fn from_matrix_reversed_z(mat: &Mat44<T>) -> Frustum<T> {
+        Frustum::<T>::from_matrix_with_depth(mat, T::ONE, T::ZERO)
+    }
+
+    /// Extracts the planes from a `projection * view` matrix which maps the near plane to the
+    /// near clip depth and the far plane to the far clip depth.
+    pub fn from_matrix_with_depth(mat: &Mat44<T>, near_depth: T, far_depth: T) -> Frustum<T> {
+        let row = |i: usize| Vec4::<T>::new(mat.data[0][i], mat.data[1][i], mat.data[2][i], mat.data[3][i]);
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+
+        // A point is in front of the plane a * x + b * y + c * z + d = 0 when the sum is positive.
+        let plane = |p: Vec4<T>| Plane::<T>::new(Vec3::<T>::new(p.x, p.y, p.z), -p.w);
+
+        let (near, far) = if near_depth < far_depth {
+            (z - w * near_depth, w * far_depth - z)
+        }
+        else {
+            (w * near_depth - z, z - w * far_depth)
+        };
+
+        Frustum::<T> {
+            planes: [
+                plane(w + x),
+                plane(w - x),
+                plane(w + y),
+                plane(w - y),
+                plane(near),
+                plane(far)
+            ]
+        }
+    }
+
+    /// Points on the planes are contained.
+    pub fn contains_point(&self, point: Vec3<T>) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= T::ZERO)
+    }
+
+    /// Conservative, a sphere near a corner outside of the frustum can be reported as intersecting.
+    pub fn classify_sphere(&self, sphere: &Sphere<T>) -> Containment {
+        combine(self.planes.iter().map(|plane| plane.classify_sphere(sphere)))
+    }
+
+    /// Conservative, a box near a corner outside of the frustum can be reported as intersecting.
+    pub fn classify_aabb(&self, aabb: &Aabb3<T>) -> Containment {
+        combine(self.planes.iter().map(|plane| plane.classify_aabb(aabb)))
+    }
+
+    /// Conservative, a box near a corner outside of the frustum can be reported as intersecting.
+    pub fn classify_obb(&self, obb: &Obb<T>) -> Containment {
+        combine(self.planes.iter().map(|plane| plane.classify_obb(obb)))
+    }
+
+    /// Whether the sphere should be drawn.
+    pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+        self.classify_sphere(sphere) != Containment::Outside
+    }
+
+    /// Whether the box should be drawn.
+    pub fn intersects_aabb(&self, aabb: &Aabb3<T>) -> bool {
+        self.classify_aabb(aabb) != Containment::Outside
+    }
+
+    /// Whether the box should be drawn.
+    pub fn intersects_obb(&self, obb: &Obb<T>) -> bool {
+        self.classify_obb(obb) != Containment::Outside
+    }
+}
+
+/// A shape is outside when it is behind any plane and inside when it is in front of all of them.
+fn combine<I: Iterator<Item = Containment>>(classes: I) -> Containment {
+    let mut result = Containment::Inside;
+
+    for class in classes {
+        match class {
+            Containment::Outside => return Containment::Outside,
+            Containment::Intersecting => result = Containment::Intersecting,
+            Containment::Inside => ()
+        }
+    }
+
+    result
+}
@@ -0,0 +1,62 @@
+pub mod aabb;
+pub mod sphere;
+pub mod plane;
+pub mod ray;
+pub mod segment;
+pub mod obb;
+pub mod frustum;
+
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
+use crate::glmath::vectorable::Vectorable;
+use aabb::{Aabb2, Aabb3};
+use sphere::Sphere;
+use plane::Plane;
+use ray::Ray;
+use segment::Segment;
+use obb::Obb;
+use frustum::Frustum;
+
+pub type Aabb2f = Aabb2<f32>;
+pub type Aabb3f = Aabb3<f32>;
+pub type Spheref = Sphere<f32>;
+pub type Planef = Plane<f32>;
+pub type Rayf = Ray<f32>;
+pub type Segmentf = Segment<f32>;
+pub type Obbf = Obb<f32>;
+pub type Frustumf = Frustum<f32>;
+
+/// Every type the shapes can be built from, the vectorable types along with their arithmetic.
+pub trait Scalar: Vectorable<Self> + PartialOrd + Copy +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self> +
+    AddAssign + SubAssign + MulAssign + DivAssign {}
+
+impl<T: Vectorable<T> + PartialOrd + Copy +
+    Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> +
+    AddAssign + SubAssign + MulAssign + DivAssign> Scalar for T {}
+
+/// Where a shape lies relative to a plane or frustum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Containment {
+    /// Entirely behind the plane or outside the frustum.
+    Outside,
+    /// Crossing a plane.
+    Intersecting,
+    /// Entirely in front of the plane or inside the frustum.
+    Inside
+}
+
+pub(crate) fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if a < b { a } else { b }
+}
+
+pub(crate) fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a > b { a } else { b }
+}
+
+pub(crate) fn clamp<T: PartialOrd>(value: T, low: T, high: T) -> T {
+    min(max(value, low), high)
+}
+
+pub(crate) fn abs<T: Scalar>(value: T) -> T {
+    if value < T::ZERO { -value } else { value }
+}
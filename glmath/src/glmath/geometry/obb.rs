@@ -0,0 +1,138 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// A box rotated around its center.
+/// The axes are the box's local x, y and z directions and must be normalized and perpendicular to each other.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Obb<T: PartialOrd + Copy> {
+    pub center: Vec3<T>,
+    pub half_extents: Vec3<T>,
+    pub axes: [Vec3<T>; 3]
+}
+
+impl<T: Scalar> Obb<T> {
+    pub fn new(center: Vec3<T>, half_extents: Vec3<T>, axes: [Vec3<T>; 3]) -> Obb<T> {
+        Obb::<T> { center, half_extents, axes }
+    }
+
+    /// A box with its axes rotated the same way the quaternion rotates vectors.
+    pub fn from_rotation(center: Vec3<T>, half_extents: Vec3<T>, rotation: Quat<T>) -> Obb<T> {
+        Obb::<T> {
+            center,
+            half_extents,
+            axes: [ rotation * Vec3::<T>::X, rotation * Vec3::<T>::Y, rotation * Vec3::<T>::Z ]
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb3<T>) -> Obb<T> {
+        Obb::<T> {
+            center: aabb.center(),
+            half_extents: aabb.half_extents(),
+            axes: [ Vec3::<T>::X, Vec3::<T>::Y, Vec3::<T>::Z ]
+        }
+    }
+
+    /// Moves a world space point into the box's space, where the box is centered on the origin.
+    pub fn to_local(&self, point: Vec3<T>) -> Vec3<T> {
+        let offset = point - self.center;
+        Vec3::<T>::new(offset * self.axes[0], offset * self.axes[1], offset * self.axes[2])
+    }
+
+    pub fn from_local(&self, point: Vec3<T>) -> Vec3<T> {
+        self.center + self.axes[0] * point.x + self.axes[1] * point.y + self.axes[2] * point.z
+    }
+
+    /// Points on the faces are contained.
+    pub fn contains_point(&self, point: Vec3<T>) -> bool {
+        let local = self.to_local(point);
+
+        abs(local.x) <= self.half_extents.x &&
+            abs(local.y) <= self.half_extents.y &&
+            abs(local.z) <= self.half_extents.z
+    }
+
+    /// The closest point inside the box, which is the point itself when it is inside.
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        let local = self.to_local(point);
+
+        self.from_local(Vec3::<T>::new(
+            clamp(local.x, -self.half_extents.x, self.half_extents.x),
+            clamp(local.y, -self.half_extents.y, self.half_extents.y),
+            clamp(local.z, -self.half_extents.z, self.half_extents.z)))
+    }
+
+    pub fn overlaps_sphere(&self, sphere: &Sphere<T>) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Tests the separating axis theorem on the 15 axes which can separate two boxes.
+    pub fn overlaps(&self, other: &Obb<T>) -> bool {
+        let a = [ self.half_extents.x, self.half_extents.y, self.half_extents.z ];
+        let b = [ other.half_extents.x, other.half_extents.y, other.half_extents.z ];
+
+        // The other box's axes and center in this box's space.
+        let mut rot = [[T::ZERO; 3]; 3];
+        let mut abs_rot = [[T::ZERO; 3]; 3];
+
+        for i in 0..3 {
+            for j in 0..3 {
+                rot[i][j] = self.axes[i] * other.axes[j];
+                // The epsilon stops parallel edges from producing a zero cross product axis which separates nothing.
+                abs_rot[i][j] = abs(rot[i][j]) + T::EPSILON;
+            }
+        }
+
+        let offset = other.center - self.center;
+        let t = [ offset * self.axes[0], offset * self.axes[1], offset * self.axes[2] ];
+
+        // This box's axes.
+        for i in 0..3 {
+            let rb = b[0] * abs_rot[i][0] + b[1] * abs_rot[i][1] + b[2] * abs_rot[i][2];
+
+            if abs(t[i]) > a[i] + rb {
+                return false;
+            }
+        }
+
+        // The other box's axes.
+        for j in 0..3 {
+            let ra = a[0] * abs_rot[0][j] + a[1] * abs_rot[1][j] + a[2] * abs_rot[2][j];
+            let distance = t[0] * rot[0][j] + t[1] * rot[1][j] + t[2] * rot[2][j];
+
+            if abs(distance) > ra + b[j] {
+                return false;
+            }
+        }
+
+        // The cross products of every pair of axes.
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+
+                let ra = a[i1] * abs_rot[i2][j] + a[i2] * abs_rot[i1][j];
+                let rb = b[j1] * abs_rot[i][j2] + b[j2] * abs_rot[i][j1];
+                let distance = t[i2] * rot[i1][j] - t[i1] * rot[i2][j];
+
+                if abs(distance) > ra + rb {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// The smallest axis aligned box around this box.
+    pub fn to_aabb(&self) -> Aabb3<T> {
+        let extent = |axis: fn(&Vec3<T>) -> T| {
+            self.half_extents.x * abs(axis(&self.axes[0])) +
+                self.half_extents.y * abs(axis(&self.axes[1])) +
+                self.half_extents.z * abs(axis(&self.axes[2]))
+        };
+
+        let half_extents = Vec3::<T>::new(extent(|v| v.x), extent(|v| v.y), extent(|v| v.z));
+        Aabb3::<T>::from_center(self.center, half_extents)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// The points where `normal * point == distance`.
+/// The side the normal points to is the front of the plane.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Plane<T: PartialOrd + Copy> {
+    pub normal: Vec3<T>,
+    pub distance: T
+}
+
+impl<T: Scalar> Plane<T> {
+    /// Normalizes the plane so signed distances are in world units.
+    /// A zero normal is kept as is, which makes every point in front of the plane when the distance is negative.
+    pub fn new(normal: Vec3<T>, distance: T) -> Plane<T> {
+        let len = normal.length();
+
+        if len == T::ZERO {
+            return Plane::<T> { normal, distance };
+        }
+
+        Plane::<T> {
+            normal: normal / len,
+            distance: distance / len
+        }
+    }
+
+    pub fn from_point_normal(point: Vec3<T>, normal: Vec3<T>) -> Plane<T> {
+        let normal = normal.get_normalized();
+
+        Plane::<T> {
+            normal,
+            distance: normal * point
+        }
+    }
+
+    /// The plane through three points, the front is the side they appear counter clockwise from.
+    pub fn from_points(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Plane<T> {
+        Plane::<T>::from_point_normal(a, (b - a) % (c - a))
+    }
+
+    /// Positive in front of the plane and negative behind it.
+    pub fn signed_distance(&self, point: Vec3<T>) -> T {
+        self.normal * point - self.distance
+    }
+
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    pub fn classify_sphere(&self, sphere: &Sphere<T>) -> Containment {
+        classify(self.signed_distance(sphere.center), sphere.radius)
+    }
+
+    pub fn classify_aabb(&self, aabb: &Aabb3<T>) -> Containment {
+        let half_extents = aabb.half_extents();
+        let radius = half_extents.x * abs(self.normal.x) +
+            half_extents.y * abs(self.normal.y) +
+            half_extents.z * abs(self.normal.z);
+
+        classify(self.signed_distance(aabb.center()), radius)
+    }
+
+    pub fn classify_obb(&self, obb: &Obb<T>) -> Containment {
+        let radius = obb.half_extents.x * abs(self.normal * obb.axes[0]) +
+            obb.half_extents.y * abs(self.normal * obb.axes[1]) +
+            obb.half_extents.z * abs(self.normal * obb.axes[2]);
+
+        classify(self.signed_distance(obb.center), radius)
+    }
+}
+
+/// Classifies a shape from the distance of its center and its radius projected onto the normal.
+fn classify<T: Scalar>(distance: T, radius: T) -> Containment {
+    if distance < -radius {
+        Containment::Outside
+    }
+    else if distance > radius {
+        Containment::Inside
+    }
+    else {
+        Containment::Intersecting
+    }
+}
@@ -0,0 +1,113 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// A half line starting at the origin, with a normalized direction.
+/// Intersections return the distance along the ray to the first hit, which is 0 when the ray starts inside the shape.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Ray<T: PartialOrd + Copy> {
+    pub origin: Vec3<T>,
+    pub direction: Vec3<T>
+}
+
+impl<T: Scalar> Ray<T> {
+    /// Normalizes the direction.
+    pub fn new(origin: Vec3<T>, direction: Vec3<T>) -> Ray<T> {
+        Ray::<T> {
+            origin,
+            direction: direction.get_normalized()
+        }
+    }
+
+    /// The point at a distance along the ray.
+    pub fn at(&self, distance: T) -> Vec3<T> {
+        self.origin + self.direction * distance
+    }
+
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        self.at(max((point - self.origin) * self.direction, T::ZERO))
+    }
+
+    /// Rays parallel to the plane never hit it, even when they lie on it.
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<T> {
+        let facing = plane.normal * self.direction;
+
+        if facing == T::ZERO {
+            return None;
+        }
+
+        let distance = -plane.signed_distance(self.origin) / facing;
+
+        if distance >= T::ZERO { Some(distance) } else { None }
+    }
+
+    pub fn intersect_sphere(&self, sphere: &Sphere<T>) -> Option<T> {
+        let offset = self.origin - sphere.center;
+        let b = offset * self.direction;
+        let c = offset.length_sq() - sphere.radius * sphere.radius;
+
+        // Starting outside and pointing away.
+        if c > T::ZERO && b > T::ZERO {
+            return None;
+        }
+
+        let discriminant = b * b - c;
+
+        if discriminant < T::ZERO {
+            return None;
+        }
+
+        Some(max(-b - discriminant.sqrt(), T::ZERO))
+    }
+
+    pub fn intersect_aabb(&self, aabb: &Aabb3<T>) -> Option<T> {
+        intersect_slabs(
+            components(self.origin),
+            components(self.direction),
+            components(aabb.min),
+            components(aabb.max))
+    }
+
+    pub fn intersect_obb(&self, obb: &Obb<T>) -> Option<T> {
+        let offset = self.origin - obb.center;
+        let origin = [ offset * obb.axes[0], offset * obb.axes[1], offset * obb.axes[2] ];
+        let direction = [ self.direction * obb.axes[0], self.direction * obb.axes[1], self.direction * obb.axes[2] ];
+        let half_extents = components(obb.half_extents);
+
+        intersect_slabs(origin, direction, half_extents.map(|extent| -extent), half_extents)
+    }
+}
+
+fn components<T: Scalar>(v: Vec3<T>) -> [T; 3] {
+    [ v.x, v.y, v.z ]
+}
+
+/// Clips the ray against the pair of planes bounding the box on each axis.
+fn intersect_slabs<T: Scalar>(origin: [T; 3], direction: [T; 3], low: [T; 3], high: [T; 3]) -> Option<T> {
+    let mut enter = T::ZERO;
+    let mut exit: Option<T> = None;
+
+    for axis in 0..3 {
+        if direction[axis] == T::ZERO {
+            // Parallel to the slab, so the ray is either always or never between the planes.
+            if origin[axis] < low[axis] || origin[axis] > high[axis] {
+                return None;
+            }
+
+            continue;
+        }
+
+        let near = (low[axis] - origin[axis]) / direction[axis];
+        let far = (high[axis] - origin[axis]) / direction[axis];
+        let (near, far) = if near > far { (far, near) } else { (near, far) };
+
+        enter = max(enter, near);
+        let axis_exit = exit.map_or(far, |exit| min(exit, far));
+        exit = Some(axis_exit);
+
+        if enter > axis_exit {
+            return None;
+        }
+    }
+
+    Some(enter)
+}
@@ -0,0 +1,111 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+/// The part of a line between two points.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Segment<T: PartialOrd + Copy> {
+    pub start: Vec3<T>,
+    pub end: Vec3<T>
+}
+
+impl<T: Scalar> Segment<T> {
+    pub fn new(start: Vec3<T>, end: Vec3<T>) -> Segment<T> {
+        Segment::<T> { start, end }
+    }
+
+    pub fn length(&self) -> T {
+        (self.end - self.start).length()
+    }
+
+    /// The point a fraction of the way from start to end.
+    pub fn at(&self, fraction: T) -> Vec3<T> {
+        self.start + (self.end - self.start) * fraction
+    }
+
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        let direction = self.end - self.start;
+        let len_sq = direction.length_sq();
+
+        if len_sq == T::ZERO {
+            return self.start;
+        }
+
+        self.at(clamp(((point - self.start) * direction) / len_sq, T::ZERO, T::ONE))
+    }
+
+    /// The closest pair of points between two segments, the first on this segment and the second on the other.
+    pub fn closest_points(&self, other: &Segment<T>) -> (Vec3<T>, Vec3<T>) {
+        let d1 = self.end - self.start;
+        let d2 = other.end - other.start;
+        let r = self.start - other.start;
+        let a = d1.length_sq();
+        let e = d2.length_sq();
+        let f = d2 * r;
+
+        if a == T::ZERO {
+            return (self.start, other.closest_point(self.start));
+        }
+
+        let c = d1 * r;
+
+        if e == T::ZERO {
+            return (self.closest_point(other.start), other.start);
+        }
+
+        // Find the closest points on the infinite lines, then clamp them to the segments.
+        let b = d1 * d2;
+        let denominator = a * e - b * b;
+
+        let mut s = if denominator != T::ZERO {
+            clamp((b * f - c * e) / denominator, T::ZERO, T::ONE)
+        }
+        else {
+            T::ZERO
+        };
+
+        let mut t = (b * s + f) / e;
+
+        if t < T::ZERO {
+            t = T::ZERO;
+            s = clamp(-c / a, T::ZERO, T::ONE);
+        }
+        else if t > T::ONE {
+            t = T::ONE;
+            s = clamp((b - c) / a, T::ZERO, T::ONE);
+        }
+
+        (self.at(s), other.at(t))
+    }
+
+    fn to_ray(self) -> Ray<T> {
+        Ray::<T>::new(self.start, self.end - self.start)
+    }
+
+    /// The first point where the segment crosses the plane.
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<Vec3<T>> {
+        self.intersect_with(|ray| ray.intersect_plane(plane))
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// The first point of the segment inside the box.
+    pub fn intersect_aabb(&self, aabb: &Aabb3<T>) -> Option<Vec3<T>> {
+        self.intersect_with(|ray| ray.intersect_aabb(aabb))
+    }
+
+    /// The first point of the segment inside the box.
+    pub fn intersect_obb(&self, obb: &Obb<T>) -> Option<Vec3<T>> {
+        self.intersect_with(|ray| ray.intersect_obb(obb))
+    }
+
+    fn intersect_with<F: Fn(&Ray<T>) -> Option<T>>(&self, intersect: F) -> Option<Vec3<T>> {
+        let ray = self.to_ray();
+
+        match intersect(&ray) {
+            Some(distance) if distance <= self.length() => Some(ray.at(distance)),
+            _ => None
+        }
+    }
+}
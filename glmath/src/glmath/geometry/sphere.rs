@@ -0,0 +1,43 @@
+use crate::glmath::*;
+use crate::glmath::geometry::*;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Sphere<T: PartialOrd + Copy> {
+    pub center: Vec3<T>,
+    pub radius: T
+}
+
+impl<T: Scalar> Sphere<T> {
+    pub fn new(center: Vec3<T>, radius: T) -> Sphere<T> {
+        Sphere::<T> { center, radius }
+    }
+
+    /// Points on the surface are contained.
+    pub fn contains_point(&self, point: Vec3<T>) -> bool {
+        (point - self.center).length_sq() <= self.radius * self.radius
+    }
+
+    pub fn contains(&self, other: &Sphere<T>) -> bool {
+        other.radius <= self.radius &&
+            (other.center - self.center).length() + other.radius <= self.radius
+    }
+
+    /// Spheres which only touch overlap.
+    pub fn overlaps(&self, other: &Sphere<T>) -> bool {
+        let radii = self.radius + other.radius;
+        (other.center - self.center).length_sq() <= radii * radii
+    }
+
+    pub fn overlaps_aabb(&self, aabb: &Aabb3<T>) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+
+    /// The closest point inside the sphere, which is the point itself when it is inside.
+    pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+        if self.contains_point(point) {
+            return point;
+        }
+
+        self.center + (point - self.center).get_normalized() * self.radius
+    }
+}
@@ -1,19 +1,10 @@
-use std::{fmt::Display, ops::{Add, AddAssign, SubAssign, Sub, MulAssign, Neg}};
+use std::ops::{Add, AddAssign, SubAssign, Sub, Neg};
 
 use crate::glmath::*;
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone, Default, PartialEq)]
-pub struct Mat44<T: PartialOrd + Copy> {
-    pub data: [[T; 4]; 4]
-}
+pub type Mat44<T> = Matrix<T, 4, 4>;
 
 impl<T: Vectorable<T> + PartialOrd + Copy> Mat44<T> {
-    /// Returns an identity matrix.
-    pub fn new() -> Mat44<T> {
-        Self::IDENTITY
-    }
-
     /// Constructs a matrix from axis values.
     pub fn from_axes(x: Vec4<T>, y: Vec4<T>, z: Vec4<T>, w: Vec4<T>) -> Mat44<T> {
         Mat44::<T> {
@@ -25,233 +16,125 @@ impl<T: Vectorable<T> + PartialOrd + Copy> Mat44<T> {
             ]
         }
     }
-
-    pub const IDENTITY: Mat44<T> = Mat44::<T> {
-        data: [
-            [ T::ONE, T::ZERO, T::ZERO, T::ZERO ],
-            [ T::ZERO, T::ONE, T::ZERO, T::ZERO ],
-            [ T::ZERO, T::ZERO, T::ONE, T::ZERO ],
-            [ T::ZERO, T::ZERO, T::ZERO, T::ONE ],
-        ]
-    };
-
-    pub const ZERO: Mat44<T> = Mat44::<T> {
-        data: [
-            [ T::ZERO, T::ZERO, T::ZERO, T::ZERO ],
-            [ T::ZERO, T::ZERO, T::ZERO, T::ZERO ],
-            [ T::ZERO, T::ZERO, T::ZERO, T::ZERO ],
-            [ T::ZERO, T::ZERO, T::ZERO, T::ZERO ]
-        ]
-    };
-}
-
-impl<T: PartialOrd + Copy + Display> Display for Mat44<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}, {}]\n[{}, {}, {}]\n[{}, {}, {}]", 
-            self.data[0][0], self.data[1][0], self.data[2][0], 
-            self.data[0][1], self.data[1][1], self.data[2][1], 
-            self.data[0][2], self.data[1][2], self.data[2][2])
-    }
 }
 
-impl<T: PartialOrd + Copy + Neg<Output = T>> Neg for Mat44<T> {
-    type Output = Mat44<T>;
+/// Projection and view matrices, using the OpenGL conventions of a right handed view space
+/// looking down -z and column vectors, so a point is projected with `projection * view * point`.
+impl<T: Vectorable<T> + PartialOrd + Copy +
+    Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> +
+    Neg<Output = T> + DivAssign<T>> Mat44<T>
+{
+    /// Maps the box between the planes to the -1..1 clip cube.
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat44<T> {
+        let width = right - left;
+        let height = top - bottom;
+        let depth = far - near;
 
-    fn neg(self) -> Self::Output {
         Mat44::<T> {
-            data:[
-                [ -self.data[0][0], -self.data[0][1], -self.data[0][2], -self.data[0][3] ],
-                [ -self.data[1][0], -self.data[1][1], -self.data[1][2], -self.data[1][3] ],
-                [ -self.data[2][0], -self.data[2][1], -self.data[2][2], -self.data[2][3] ],
-                [ -self.data[3][0], -self.data[3][1], -self.data[3][2], -self.data[3][3] ]
+            data: [
+                [ T::TWO / width, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::TWO / height, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::ZERO, -T::TWO / depth, T::ZERO ],
+                [ -(right + left) / width, -(top + bottom) / height, -(far + near) / depth, T::ONE ]
             ]
         }
     }
-}
 
-impl<T: PartialOrd + Copy + Add<Output = T>> Add<Mat44<T>> for Mat44<T> {
-    type Output = Mat44<T>;
+    /// Maps the frustum between the planes to the -1..1 clip cube.
+    /// The left, right, bottom and top planes are measured on the near plane.
+    pub fn frustum(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat44<T> {
+        let width = right - left;
+        let height = top - bottom;
+        let depth = far - near;
 
-    fn add(self, rhs: Mat44<T>) -> Self::Output {
         Mat44::<T> {
             data: [
-                [
-                    self.data[0][0] + rhs.data[0][0],
-                    self.data[0][1] + rhs.data[0][1],
-                    self.data[0][2] + rhs.data[0][2],
-                    self.data[0][2] + rhs.data[0][2]
-                ],
-                [
-                    self.data[1][0] + rhs.data[1][0],
-                    self.data[1][1] + rhs.data[1][1],
-                    self.data[1][2] + rhs.data[1][2],
-                    self.data[1][3] + rhs.data[1][3]
-                ],
-                [
-                    self.data[2][0] + rhs.data[2][0],
-                    self.data[2][1] + rhs.data[2][1],
-                    self.data[2][2] + rhs.data[2][2],
-                    self.data[2][3] + rhs.data[2][3]
-                ],
-                [
-                    self.data[3][0] + rhs.data[3][0],
-                    self.data[3][1] + rhs.data[3][1],
-                    self.data[3][2] + rhs.data[3][2],
-                    self.data[3][3] + rhs.data[3][3]
-                ]
+                [ T::TWO * near / width, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::TWO * near / height, T::ZERO, T::ZERO ],
+                [ (right + left) / width, (top + bottom) / height, -(far + near) / depth, -T::ONE ],
+                [ T::ZERO, T::ZERO, -T::TWO * far * near / depth, T::ZERO ]
             ]
         }
     }
-}
 
-impl<T: PartialOrd + Copy + Add<Output = T>> AddAssign for Mat44<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.data[0][0] = self.data[0][0] + rhs.data[0][0];
-        self.data[0][1] = self.data[0][1] + rhs.data[0][1];
-        self.data[0][2] = self.data[0][2] + rhs.data[0][2];
-        self.data[0][3] = self.data[0][3] + rhs.data[0][3];
-        self.data[1][0] = self.data[1][0] + rhs.data[1][0];
-        self.data[1][1] = self.data[1][1] + rhs.data[1][1];
-        self.data[1][2] = self.data[1][2] + rhs.data[1][2];
-        self.data[1][3] = self.data[1][3] + rhs.data[1][3];
-        self.data[2][0] = self.data[2][0] + rhs.data[2][0];
-        self.data[2][1] = self.data[2][1] + rhs.data[2][1];
-        self.data[2][2] = self.data[2][2] + rhs.data[2][2];
-        self.data[2][3] = self.data[2][3] + rhs.data[2][3];
-        self.data[3][0] = self.data[3][0] + rhs.data[3][0];
-        self.data[3][1] = self.data[3][1] + rhs.data[3][1];
-        self.data[3][2] = self.data[3][2] + rhs.data[3][2];
-        self.data[3][3] = self.data[3][3] + rhs.data[3][3];
+    /// A symmetric perspective projection with a vertical field of view in radians.
+    /// Depth is mapped from -1 at the near plane to 1 at the far plane.
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Mat44<T> {
+        let focal = T::ONE / (fov_y * T::HALF).tan();
+        let depth = near - far;
+
+        Mat44::<T> {
+            data: [
+                [ focal / aspect, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, focal, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::ZERO, (far + near) / depth, -T::ONE ],
+                [ T::ZERO, T::ZERO, T::TWO * far * near / depth, T::ZERO ]
+            ]
+        }
     }
-}
 
-impl<T: PartialOrd + Copy + Sub<Output = T>> Sub<Mat44<T>> for Mat44<T> {
-    type Output = Mat44<T>;
+    /// A perspective projection with depth mapped from 1 at the near plane to 0 at the far plane.
+    /// Spreads float depth precision evenly over the view distance, use with a 0..1 clip depth
+    /// range and a greater than depth test.
+    pub fn perspective_reversed_z(fov_y: T, aspect: T, near: T, far: T) -> Mat44<T> {
+        let focal = T::ONE / (fov_y * T::HALF).tan();
+        let depth = far - near;
 
-    fn sub(self, rhs: Mat44<T>) -> Self::Output {
         Mat44::<T> {
             data: [
-                [
-                    self.data[0][0] - rhs.data[0][0],
-                    self.data[0][1] - rhs.data[0][1],
-                    self.data[0][2] - rhs.data[0][2],
-                    self.data[0][2] - rhs.data[0][2]
-                ],
-                [
-                    self.data[1][0] - rhs.data[1][0],
-                    self.data[1][1] - rhs.data[1][1],
-                    self.data[1][2] - rhs.data[1][2],
-                    self.data[1][3] - rhs.data[1][3]
-                ],
-                [
-                    self.data[2][0] - rhs.data[2][0],
-                    self.data[2][1] - rhs.data[2][1],
-                    self.data[2][2] - rhs.data[2][2],
-                    self.data[2][3] - rhs.data[2][3]
-                ],
-                [
-                    self.data[3][0] - rhs.data[3][0],
-                    self.data[3][1] - rhs.data[3][1],
-                    self.data[3][2] - rhs.data[3][2],
-                    self.data[3][3] - rhs.data[3][3]
-                ]
+                [ focal / aspect, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, focal, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::ZERO, near / depth, -T::ONE ],
+                [ T::ZERO, T::ZERO, far * near / depth, T::ZERO ]
             ]
         }
     }
-}
 
-impl<T: PartialOrd + Copy + Sub<Output = T>> SubAssign for Mat44<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.data[0][0] = self.data[0][0] - rhs.data[0][0];
-        self.data[0][1] = self.data[0][1] - rhs.data[0][1];
-        self.data[0][2] = self.data[0][2] - rhs.data[0][2];
-        self.data[0][3] = self.data[0][3] - rhs.data[0][3];
-        self.data[1][0] = self.data[1][0] - rhs.data[1][0];
-        self.data[1][1] = self.data[1][1] - rhs.data[1][1];
-        self.data[1][2] = self.data[1][2] - rhs.data[1][2];
-        self.data[1][3] = self.data[1][3] - rhs.data[1][3];
-        self.data[2][0] = self.data[2][0] - rhs.data[2][0];
-        self.data[2][1] = self.data[2][1] - rhs.data[2][1];
-        self.data[2][2] = self.data[2][2] - rhs.data[2][2];
-        self.data[2][3] = self.data[2][3] - rhs.data[2][3];
-        self.data[3][0] = self.data[3][0] - rhs.data[3][0];
-        self.data[3][1] = self.data[3][1] - rhs.data[3][1];
-        self.data[3][2] = self.data[3][2] - rhs.data[3][2];
-        self.data[3][3] = self.data[3][3] - rhs.data[3][3];
+    /// A perspective projection without a far plane.
+    /// Depth is mapped from -1 at the near plane and approaches 1 as the distance grows.
+    pub fn infinite_perspective(fov_y: T, aspect: T, near: T) -> Mat44<T> {
+        let focal = T::ONE / (fov_y * T::HALF).tan();
+
+        Mat44::<T> {
+            data: [
+                [ focal / aspect, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, focal, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::ZERO, -T::ONE, -T::ONE ],
+                [ T::ZERO, T::ZERO, -T::TWO * near, T::ZERO ]
+            ]
+        }
     }
-}
 
-impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>>
-    Mul<Mat44<T>> for Mat44<T> 
-{
-    type Output = Mat44<T>;
-
-    fn mul(self, rhs: Mat44<T>) -> Self::Output {
-        let t00 = self.data[0][0] * rhs.data[0][0] + self.data[1][0] * rhs.data[0][1] + self.data[2][0] * rhs.data[0][2] + self.data[3][0] * rhs.data[0][3];
-        let t01 = self.data[0][1] * rhs.data[0][0] + self.data[1][1] * rhs.data[0][1] + self.data[2][1] * rhs.data[0][2] + self.data[3][1] * rhs.data[0][3];
-        let t02 = self.data[0][2] * rhs.data[0][0] + self.data[1][2] * rhs.data[0][1] + self.data[2][2] * rhs.data[0][2] + self.data[3][2] * rhs.data[0][3];
-        let t03 = self.data[0][3] * rhs.data[0][0] + self.data[1][3] * rhs.data[0][1] + self.data[2][3] * rhs.data[0][2] + self.data[3][3] * rhs.data[0][3];
-        let t10 = self.data[0][0] * rhs.data[1][0] + self.data[1][0] * rhs.data[1][1] + self.data[2][0] * rhs.data[1][2] + self.data[3][0] * rhs.data[1][3];
-        let t11 = self.data[0][1] * rhs.data[1][0] + self.data[1][1] * rhs.data[1][1] + self.data[2][1] * rhs.data[1][2] + self.data[3][1] * rhs.data[1][3];
-        let t12 = self.data[0][2] * rhs.data[1][0] + self.data[1][2] * rhs.data[1][1] + self.data[2][2] * rhs.data[1][2] + self.data[3][2] * rhs.data[1][3];
-        let t13 = self.data[0][3] * rhs.data[1][0] + self.data[1][3] * rhs.data[1][1] + self.data[2][3] * rhs.data[1][2] + self.data[3][3] * rhs.data[1][3];
-        let t20 = self.data[0][0] * rhs.data[2][0] + self.data[1][0] * rhs.data[2][1] + self.data[2][0] * rhs.data[2][2] + self.data[3][0] * rhs.data[2][3];
-        let t21 = self.data[0][1] * rhs.data[2][0] + self.data[1][1] * rhs.data[2][1] + self.data[2][1] * rhs.data[2][2] + self.data[3][1] * rhs.data[2][3];
-        let t22 = self.data[0][2] * rhs.data[2][0] + self.data[1][2] * rhs.data[2][1] + self.data[2][2] * rhs.data[2][2] + self.data[3][2] * rhs.data[2][3];
-        let t23 = self.data[0][3] * rhs.data[2][0] + self.data[1][3] * rhs.data[2][1] + self.data[2][3] * rhs.data[2][2] + self.data[3][3] * rhs.data[2][3];
-        let t30 = self.data[0][0] * rhs.data[3][0] + self.data[1][0] * rhs.data[3][1] + self.data[2][0] * rhs.data[3][2] + self.data[3][0] * rhs.data[3][3];
-        let t31 = self.data[0][1] * rhs.data[3][0] + self.data[1][1] * rhs.data[3][1] + self.data[2][1] * rhs.data[3][2] + self.data[3][1] * rhs.data[3][3];
-        let t32 = self.data[0][2] * rhs.data[3][0] + self.data[1][2] * rhs.data[3][1] + self.data[2][2] * rhs.data[3][2] + self.data[3][2] * rhs.data[3][3];
-        let t33 = self.data[0][3] * rhs.data[3][0] + self.data[1][3] * rhs.data[3][1] + self.data[2][3] * rhs.data[3][2] + self.data[3][3] * rhs.data[3][3];
+    /// A reversed-z perspective projection without a far plane.
+    /// Depth is mapped from 1 at the near plane and approaches 0 as the distance grows.
+    pub fn infinite_perspective_reversed_z(fov_y: T, aspect: T, near: T) -> Mat44<T> {
+        let focal = T::ONE / (fov_y * T::HALF).tan();
 
         Mat44::<T> {
             data: [
-                [ t00, t01, t02, t03 ],
-                [ t10, t11, t12, t13 ],
-                [ t20, t21, t22, t23 ],
-                [ t30, t31, t32, t33 ]
+                [ focal / aspect, T::ZERO, T::ZERO, T::ZERO ],
+                [ T::ZERO, focal, T::ZERO, T::ZERO ],
+                [ T::ZERO, T::ZERO, T::ZERO, -T::ONE ],
+                [ T::ZERO, T::ZERO, near, T::ZERO ]
             ]
         }
     }
-}
 
-impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>>
-    MulAssign for Mat44<T> 
-{
-    fn mul_assign(&mut self, rhs: Self) {
-        let t00 = self.data[0][0] * rhs.data[0][0] + self.data[1][0] * rhs.data[0][1] + self.data[2][0] * rhs.data[0][2] + self.data[3][0] * rhs.data[0][3];
-        let t01 = self.data[0][1] * rhs.data[0][0] + self.data[1][1] * rhs.data[0][1] + self.data[2][1] * rhs.data[0][2] + self.data[3][1] * rhs.data[0][3];
-        let t02 = self.data[0][2] * rhs.data[0][0] + self.data[1][2] * rhs.data[0][1] + self.data[2][2] * rhs.data[0][2] + self.data[3][2] * rhs.data[0][3];
-        let t03 = self.data[0][3] * rhs.data[0][0] + self.data[1][3] * rhs.data[0][1] + self.data[2][3] * rhs.data[0][2] + self.data[3][3] * rhs.data[0][3];
-        let t10 = self.data[0][0] * rhs.data[1][0] + self.data[1][0] * rhs.data[1][1] + self.data[2][0] * rhs.data[1][2] + self.data[3][0] * rhs.data[1][3];
-        let t11 = self.data[0][1] * rhs.data[1][0] + self.data[1][1] * rhs.data[1][1] + self.data[2][1] * rhs.data[1][2] + self.data[3][1] * rhs.data[1][3];
-        let t12 = self.data[0][2] * rhs.data[1][0] + self.data[1][2] * rhs.data[1][1] + self.data[2][2] * rhs.data[1][2] + self.data[3][2] * rhs.data[1][3];
-        let t13 = self.data[0][3] * rhs.data[1][0] + self.data[1][3] * rhs.data[1][1] + self.data[2][3] * rhs.data[1][2] + self.data[3][3] * rhs.data[1][3];
-        let t20 = self.data[0][0] * rhs.data[2][0] + self.data[1][0] * rhs.data[2][1] + self.data[2][0] * rhs.data[2][2] + self.data[3][0] * rhs.data[2][3];
-        let t21 = self.data[0][1] * rhs.data[2][0] + self.data[1][1] * rhs.data[2][1] + self.data[2][1] * rhs.data[2][2] + self.data[3][1] * rhs.data[2][3];
-        let t22 = self.data[0][2] * rhs.data[2][0] + self.data[1][2] * rhs.data[2][1] + self.data[2][2] * rhs.data[2][2] + self.data[3][2] * rhs.data[2][3];
-        let t23 = self.data[0][3] * rhs.data[2][0] + self.data[1][3] * rhs.data[2][1] + self.data[2][3] * rhs.data[2][2] + self.data[3][3] * rhs.data[2][3];
-        let t30 = self.data[0][0] * rhs.data[3][0] + self.data[1][0] * rhs.data[3][1] + self.data[2][0] * rhs.data[3][2] + self.data[3][0] * rhs.data[3][3];
-        let t31 = self.data[0][1] * rhs.data[3][0] + self.data[1][1] * rhs.data[3][1] + self.data[2][1] * rhs.data[3][2] + self.data[3][1] * rhs.data[3][3];
-        let t32 = self.data[0][2] * rhs.data[3][0] + self.data[1][2] * rhs.data[3][1] + self.data[2][2] * rhs.data[3][2] + self.data[3][2] * rhs.data[3][3];
-        let t33 = self.data[0][3] * rhs.data[3][0] + self.data[1][3] * rhs.data[3][1] + self.data[2][3] * rhs.data[3][2] + self.data[3][3] * rhs.data[3][3];
+    /// A view matrix for a camera at eye looking towards target.
+    /// The eye ends up at the origin and target on the -z axis.
+    pub fn look_at(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Mat44<T> {
+        let forward = (target - eye).get_normalized();
+        let right = (forward % up).get_normalized();
+        let up = right % forward;
 
-        self.data[0][0] = t00;
-        self.data[0][1] = t01;
-        self.data[0][2] = t02;
-        self.data[0][3] = t03;
-        self.data[1][0] = t10;
-        self.data[1][1] = t11;
-        self.data[1][2] = t12;
-        self.data[1][3] = t13;
-        self.data[2][0] = t20;
-        self.data[2][1] = t21;
-        self.data[2][2] = t22;
-        self.data[2][3] = t23;
-        self.data[3][0] = t30;
-        self.data[3][1] = t31;
-        self.data[3][2] = t32;
-        self.data[3][3] = t33;
+        Mat44::<T> {
+            data: [
+                [ right.x, up.x, -forward.x, T::ZERO ],
+                [ right.y, up.y, -forward.y, T::ZERO ],
+                [ right.z, up.z, -forward.z, T::ZERO ],
+                [ -(right * eye), -(up * eye), forward * eye, T::ONE ]
+            ]
+        }
     }
 }
 
@@ -261,14 +144,7 @@ impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>>
     type Output = Vec4<T>;
 
     fn mul(self, rhs: Vec4<T>) -> Self::Output {
-        let x = self.data[0][0] * rhs.x + self.data[1][0] * rhs.y + self.data[2][0] * rhs.z + self.data[3][0] * rhs.w;
-        let y = self.data[0][1] * rhs.x + self.data[1][1] * rhs.y + self.data[2][1] * rhs.z + self.data[3][1] * rhs.w;
-        let z = self.data[0][2] * rhs.x + self.data[1][2] * rhs.y + self.data[2][2] * rhs.z + self.data[3][2] * rhs.w;
-        let w = self.data[0][3] * rhs.x + self.data[1][3] * rhs.y + self.data[2][3] * rhs.z + self.data[3][3] * rhs.w;
-
-        Vec4::<T> {
-            x, y, z, w
-        }
+        (self * Vector::from(rhs)).into()
     }
 }
 
@@ -279,39 +155,7 @@ impl<T: PartialOrd + Copy + Vectorable<T> +
     StandardMat<T> for Mat44<T> 
 {
     fn transpose(&mut self) {
-        let t00 = self.data[0][0];
-        let t01 = self.data[1][0];
-        let t02 = self.data[2][0];
-        let t03 = self.data[3][0];
-        let t10 = self.data[0][1];
-        let t11 = self.data[1][1];
-        let t12 = self.data[2][1];
-        let t13 = self.data[3][1];
-        let t20 = self.data[0][2];
-        let t21 = self.data[1][2];
-        let t22 = self.data[2][2];
-        let t23 = self.data[3][2];
-        let t30 = self.data[0][3];
-        let t31 = self.data[1][3];
-        let t32 = self.data[2][3];
-        let t33 = self.data[3][3];
-
-        self.data[0][0] = t00;
-        self.data[0][1] = t01;
-        self.data[0][2] = t02;
-        self.data[0][3] = t03;
-        self.data[1][0] = t10;
-        self.data[1][1] = t11;
-        self.data[1][2] = t12;
-        self.data[1][3] = t13;
-        self.data[2][0] = t20;
-        self.data[2][1] = t21;
-        self.data[2][2] = t22;
-        self.data[2][3] = t23;
-        self.data[3][0] = t30;
-        self.data[3][1] = t31;
-        self.data[3][2] = t32;
-        self.data[3][3] = t33;
+        *self = self.get_transposed();
     }
 
     fn invert(&mut self) {
@@ -0,0 +1,181 @@
+use std::{fmt::Display, ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg}};
+
+use crate::glmath::*;
+
+/// A matrix with R rows and C columns, the arithmetic shared by every matrix type is written once here.
+/// The data is stored as C columns of R values, the layout OpenGL expects.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix<T: PartialOrd + Copy, const R: usize, const C: usize> {
+    pub data: [[T; R]; C]
+}
+
+/// 3 rows by 4 columns, an affine transform without the constant last row.
+pub type Mat34<T> = Matrix<T, 3, 4>;
+
+impl<T: PartialOrd + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn from_columns(columns: [Vector<T, R>; C]) -> Matrix<T, R, C> {
+        Matrix::<T, R, C> { data: columns.map(|column| column.data) }
+    }
+
+    pub fn column(&self, index: usize) -> Vector<T, R> {
+        Vector::new(self.data[index])
+    }
+
+    pub fn row(&self, index: usize) -> Vector<T, C> {
+        Vector::new(self.data.map(|column| column[index]))
+    }
+
+    /// Returns the transpose, a square matrix can also be transposed in place with `StandardMat`.
+    pub fn get_transposed(&self) -> Matrix<T, C, R> {
+        Matrix::<T, C, R> { data: std::array::from_fn(|row| self.data.map(|column| column[row])) }
+    }
+}
+
+impl<T: Number<T> + PartialOrd + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub const ZERO: Matrix<T, R, C> = Matrix::<T, R, C> { data: [[T::ZERO; R]; C] };
+}
+
+impl<T: Number<T> + PartialOrd + Copy, const N: usize> Matrix<T, N, N> {
+    /// Returns an identity matrix.
+    pub fn new() -> Matrix<T, N, N> {
+        Self::IDENTITY
+    }
+
+    pub const IDENTITY: Matrix<T, N, N> = {
+        let mut data = [[T::ZERO; N]; N];
+        let mut i = 0;
+
+        while i < N {
+            data[i][i] = T::ONE;
+            i += 1;
+        }
+
+        Matrix::<T, N, N> { data }
+    };
+}
+
+impl<T: PartialOrd + Copy + Default, const R: usize, const C: usize> Default for Matrix<T, R, C> {
+    fn default() -> Self {
+        Matrix::<T, R, C> { data: [[T::default(); R]; C] }
+    }
+}
+
+/// Prints a line per row.
+impl<T: PartialOrd + Copy + Display, const R: usize, const C: usize> Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..R {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            self.row(row).fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: PartialOrd + Copy + Neg<Output = T>, const R: usize, const C: usize> Neg for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn neg(self) -> Self::Output {
+        Matrix::<T, R, C> { data: self.data.map(|column| column.map(|value| -value)) }
+    }
+}
+
+impl<T: PartialOrd + Copy + Add<Output = T>, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn add(mut self, rhs: Matrix<T, R, C>) -> Self::Output {
+        for (column, rhs) in self.data.iter_mut().zip(rhs.data) {
+            for (value, rhs) in column.iter_mut().zip(rhs) {
+                *value = *value + rhs;
+            }
+        }
+
+        self
+    }
+}
+
+impl<T: PartialOrd + Copy + AddAssign, const R: usize, const C: usize> AddAssign for Matrix<T, R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (column, rhs) in self.data.iter_mut().zip(rhs.data) {
+            for (value, rhs) in column.iter_mut().zip(rhs) {
+                *value += rhs;
+            }
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy + Sub<Output = T>, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn sub(mut self, rhs: Matrix<T, R, C>) -> Self::Output {
+        for (column, rhs) in self.data.iter_mut().zip(rhs.data) {
+            for (value, rhs) in column.iter_mut().zip(rhs) {
+                *value = *value - rhs;
+            }
+        }
+
+        self
+    }
+}
+
+impl<T: PartialOrd + Copy + SubAssign, const R: usize, const C: usize> SubAssign for Matrix<T, R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (column, rhs) in self.data.iter_mut().zip(rhs.data) {
+            for (value, rhs) in column.iter_mut().zip(rhs) {
+                *value -= rhs;
+            }
+        }
+    }
+}
+
+/// An R x C matrix times a C x K matrix gives an R x K matrix.
+/// Every element is summed from the first column of self, so the results are the same for every size.
+impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>, const R: usize, const C: usize, const K: usize>
+    Mul<Matrix<T, C, K>> for Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        Matrix::<T, R, K> { data: rhs.data.map(|column| (self * Vector::new(column)).data) }
+    }
+}
+
+impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>, const R: usize, const C: usize>
+    MulAssign<Matrix<T, C, C>> for Matrix<T, R, C>
+{
+    fn mul_assign(&mut self, rhs: Matrix<T, C, C>) {
+        *self = *self * rhs;
+    }
+}
+
+/// Transforms a vector, summed from the first column. A matrix without columns fails to compile:
+///
+/// ```compile_fail
+/// use glmath::glmath::{matrix::Matrix, vector::Vector};
+///
+/// let empty = Matrix::<f32, 2, 0> { data: [] };
+/// let _ = empty * Vector::<f32, 0>::new([]);
+/// ```
+impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>, const R: usize, const C: usize>
+    Mul<Vector<T, C>> for Matrix<T, R, C>
+{
+    type Output = Vector<T, R>;
+
+    fn mul(self, rhs: Vector<T, C>) -> Self::Output {
+        const { assert!(C > 0, "a matrix needs at least one column to transform a vector") };
+
+        let mut result = Vector::new(self.data[0].map(|value| value * rhs.data[0]));
+
+        for (column, scale) in self.data.iter().zip(rhs.data).skip(1) {
+            for (value, column_value) in result.data.iter_mut().zip(column) {
+                *value = *value + *column_value * scale;
+            }
+        }
+
+        result
+    }
+}
@@ -1,4 +1,6 @@
 pub mod vectorable;
+pub mod vector;
+pub mod matrix;
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
@@ -6,8 +8,15 @@ pub mod quat;
 pub mod mat22;
 pub mod mat33;
 pub mod mat44;
+pub mod geometry;
+pub mod transform;
+pub mod simd;
+pub mod approx;
+pub mod curves;
 
 use std::ops::{Mul, Div, DivAssign};
+use vector::Vector;
+use matrix::{Matrix, Mat34};
 use vec2::Vec2;
 use vec3::Vec3;
 use vec4::Vec4;
@@ -15,7 +24,8 @@ use quat::Quat;
 use mat22::Mat22;
 use mat33::Mat33;
 use mat44::Mat44;
-use self::vectorable::Vectorable;
+use transform::Transform;
+use self::vectorable::{Vectorable, Number};
 
 pub type Vec2f = Vec2<f32>;
 pub type Vec3f = Vec3<f32>;
@@ -24,6 +34,48 @@ pub type Quatf = Quat<f32>;
 pub type Mat22f = Mat22<f32>;
 pub type Mat33f = Mat33<f32>;
 pub type Mat44f = Mat44<f32>;
+pub type Mat34f = Mat34<f32>;
+pub type Transformf = Transform<f32>;
+
+pub type Vec2i = Vec2<i32>;
+pub type Vec3i = Vec3<i32>;
+pub type Vec4i = Vec4<i32>;
+pub type Vec2u = Vec2<u32>;
+pub type Vec3u = Vec3<u32>;
+pub type Vec4u = Vec4<u32>;
+pub type Vec2b = Vec2<bool>;
+pub type Vec3b = Vec3<bool>;
+pub type Vec4b = Vec4<bool>;
+
+/// Integer vectors convert to float vectors when every value fits exactly.
+macro_rules! impl_lossless_from {
+    ($($from:ty => $to:ty),*) => {
+        $(
+            impl From<Vec2<$from>> for Vec2<$to> {
+                fn from(v: Vec2<$from>) -> Self {
+                    Vec2::<$to> { x: v.x.into(), y: v.y.into() }
+                }
+            }
+
+            impl From<Vec3<$from>> for Vec3<$to> {
+                fn from(v: Vec3<$from>) -> Self {
+                    Vec3::<$to> { x: v.x.into(), y: v.y.into(), z: v.z.into() }
+                }
+            }
+
+            impl From<Vec4<$from>> for Vec4<$to> {
+                fn from(v: Vec4<$from>) -> Self {
+                    Vec4::<$to> { x: v.x.into(), y: v.y.into(), z: v.z.into(), w: v.w.into() }
+                }
+            }
+        )*
+    };
+}
+
+impl_lossless_from!(
+    i8 => f32, i16 => f32, u8 => f32, u16 => f32,
+    i8 => f64, i16 => f64, i32 => f64, u8 => f64, u16 => f64, u32 => f64,
+    f32 => f64);
 
 pub trait StandardMat<T: PartialOrd + Copy + Vectorable<T>> 
     where Self: Sized + Copy
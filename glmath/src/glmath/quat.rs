@@ -1,5 +1,6 @@
 use std::{ops::{Add, MulAssign, AddAssign, SubAssign, Sub, Neg}, fmt::Display};
 use crate::glmath::*;
+use crate::glmath::geometry::Scalar;
 
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Quat<T: PartialOrd + Copy> {
@@ -328,6 +329,86 @@ impl<T: PartialOrd + Copy + Vectorable<T>> Quat<T> {
     }
 }
 
+impl<T: Scalar> Quat<T> {
+    /// The inverse of a unit quaternion.
+    pub fn get_conjugated(&self) -> Quat<T> {
+        Quat::<T> { x: -self.x, y: -self.y, z: -self.z, w: self.w }
+    }
+
+    /// The logarithm of a unit quaternion, its axis scaled by half its angle with a w of zero.
+    pub fn log(&self) -> Quat<T> {
+        let axis_len = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+
+        if axis_len <= T::EPSILON {
+            return Quat::<T> { w: T::ZERO, ..*self };
+        }
+
+        let scale = T::atan2(axis_len, self.w) / axis_len;
+        Quat::<T> { x: self.x * scale, y: self.y * scale, z: self.z * scale, w: T::ZERO }
+    }
+
+    /// The inverse of `log`, the unit quaternion rotating around x, y and z by twice their length.
+    pub fn exp(&self) -> Quat<T> {
+        let half_angle = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+
+        if half_angle <= T::EPSILON {
+            return Quat::<T> { w: T::ONE, ..*self };
+        }
+
+        let scale = half_angle.sin() / half_angle;
+        Quat::<T> { x: self.x * scale, y: self.y * scale, z: self.z * scale, w: half_angle.cos() }
+    }
+
+    /// The control point of a key for `squad`, which makes the rotation change smoothly through the key
+    /// given the keys before and after it. The first and last keys can use themselves as the missing neighbour.
+    pub fn squad_control(previous: Quat<T>, current: Quat<T>, next: Quat<T>) -> Quat<T> {
+        let inverse = current.get_conjugated();
+        let previous = if dot(current, previous) < T::ZERO { -previous } else { previous };
+        let next = if dot(current, next) < T::ZERO { -next } else { next };
+
+        let sum = (inverse * next).log() + (inverse * previous).log();
+        (current * (sum * -T::QUARTER).exp()).get_normalized()
+    }
+
+    /// Spherical quadrangle interpolation from a to b, smooth across a chain of keys unlike slerping between each pair of keys.
+    /// The controls come from `squad_control`, `Squad` builds them for a list of keys.
+    pub fn squad(a: Quat<T>, b: Quat<T>, a_control: Quat<T>, b_control: Quat<T>, blend: T) -> Quat<T> {
+        constant_speed_slerp(
+            constant_speed_slerp(a, b, blend),
+            constant_speed_slerp(a_control, b_control, blend),
+            T::TWO * blend * (T::ONE - blend))
+    }
+}
+
+/// Rotates from a to b at a constant speed, taking the short way around.
+/// Squad needs the constant speed to stay smooth through its keys, which blending linearly doesn't keep.
+fn constant_speed_slerp<T: Scalar>(a: Quat<T>, b: Quat<T>, blend: T) -> Quat<T> {
+    let mut dot = dot(a, b);
+    let mut b = b;
+
+    if dot < T::ZERO {
+        dot = -dot;
+        b = -b;
+    }
+
+    // Nearly the same rotation, the angle is too small to divide by and blending linearly is as accurate.
+    let result = if dot > T::from_f64(0.9995) {
+        a * (T::ONE - blend) + b * blend
+    }
+    else {
+        let angle = dot.acos();
+        let sin = angle.sin();
+
+        a * (((T::ONE - blend) * angle).sin() / sin) + b * ((blend * angle).sin() / sin)
+    };
+
+    result.get_normalized()
+}
+
+fn dot<T: Scalar>(a: Quat<T>, b: Quat<T>) -> T {
+    a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w
+}
+
 impl<T: PartialOrd + Copy + Neg<Output = T>> Neg for Quat<T> {
     type Output = Quat<T>;
 
@@ -0,0 +1,174 @@
+//! Single precision operations on sse2 or neon, for the hot loops where the generic scalar code is too slow,
+//! ex: skinning or particle updates. Targets without either, or builds with the `scalar-math` feature,
+//! use a scalar fallback running the same code.
+//!
+//! Results compared to the operators on `Mat44f`, `Vec4f` and `Quatf`:
+//! - `mat44_mul`, `mat44_mul_vec4`, `transform_vec4s` and `mat44_transpose` are bit identical,
+//!   the products are summed in the same order and multiplies are never fused with adds.
+//! - `quat_mul` sums the terms of some components in a different order, so each component can differ
+//!   by a rounding error of up to `4 * f32::EPSILON * a.length() * b.length()`.
+//! - `mat44_inverse` computes the cofactors from 2x2 minors instead of 3x3 determinants. For transforms
+//!   built from rotations, translations and scales within 1e-3..1e3 every element is within `1e-5`
+//!   of the scalar result, relative to the largest element. Ill conditioned matrices can differ by more, the same way both results
+//!   differ from the exact inverse.
+
+#[cfg(all(not(feature = "scalar-math"), any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod sse2;
+#[cfg(all(not(feature = "scalar-math"), any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+use sse2::F32x4;
+
+#[cfg(all(not(feature = "scalar-math"), target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+#[cfg(all(not(feature = "scalar-math"), target_arch = "aarch64", target_feature = "neon"))]
+use neon::F32x4;
+
+#[cfg(not(all(not(feature = "scalar-math"), any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")))))]
+mod scalar;
+#[cfg(not(all(not(feature = "scalar-math"), any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")))))]
+use scalar::F32x4;
+
+use crate::glmath::*;
+
+/// Whether the operations run on sse2 or neon rather than the scalar fallback.
+pub const ACCELERATED: bool = cfg!(all(not(feature = "scalar-math"), any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"))));
+
+#[inline(always)]
+fn load_columns(mat: &Mat44f) -> [F32x4; 4] {
+    mat.data.map(F32x4::load)
+}
+
+#[inline(always)]
+fn store_columns(columns: [F32x4; 4]) -> Mat44f {
+    Mat44f { data: columns.map(F32x4::store) }
+}
+
+#[inline(always)]
+fn store_vec4(v: F32x4) -> Vec4f {
+    let [x, y, z, w] = v.store();
+    Vec4f { x, y, z, w }
+}
+
+/// Sums the columns scaled by the components of v, in the same order as the scalar code.
+#[inline(always)]
+fn mul_columns(columns: &[F32x4; 4], v: [f32; 4]) -> F32x4 {
+    columns[0] * F32x4::splat(v[0]) +
+        columns[1] * F32x4::splat(v[1]) +
+        columns[2] * F32x4::splat(v[2]) +
+        columns[3] * F32x4::splat(v[3])
+}
+
+/// The same as `a * b`.
+#[inline]
+pub fn mat44_mul(a: &Mat44f, b: &Mat44f) -> Mat44f {
+    let columns = load_columns(a);
+    store_columns(b.data.map(|column| mul_columns(&columns, column)))
+}
+
+/// The same as `mat * v`.
+#[inline]
+pub fn mat44_mul_vec4(mat: &Mat44f, v: Vec4f) -> Vec4f {
+    store_vec4(mul_columns(&load_columns(mat), [v.x, v.y, v.z, v.w]))
+}
+
+/// Transforms every vector by the matrix in place, loading the matrix once.
+#[inline]
+pub fn transform_vec4s(mat: &Mat44f, vectors: &mut [Vec4f]) {
+    let columns = load_columns(mat);
+
+    for v in vectors {
+        *v = store_vec4(mul_columns(&columns, [v.x, v.y, v.z, v.w]));
+    }
+}
+
+/// The same as `mat.get_transposed()`.
+#[inline]
+pub fn mat44_transpose(mat: &Mat44f) -> Mat44f {
+    store_columns(F32x4::transpose(load_columns(mat)))
+}
+
+/// The same as `mat.get_inverted()`, which returns the matrix unchanged when it can't be inverted.
+#[inline]
+pub fn mat44_inverse(mat: &Mat44f) -> Mat44f {
+    // Cramer's rule on the transpose, the cofactors are built from products of pairs of rows
+    // swizzled so every lane holds a different 2x2 minor. Inverting the transpose gives the transpose
+    // of the inverse, so the rows of the result are the columns of the inverse.
+    let [row0, row1, row2, row3] = F32x4::transpose(load_columns(mat));
+    let row1 = row1.zwxy();
+    let row3 = row3.zwxy();
+
+    let mut tmp = (row2 * row3).yxwz();
+    let mut minor0 = row1 * tmp;
+    let mut minor1 = row0 * tmp;
+    tmp = tmp.zwxy();
+    minor0 = row1 * tmp - minor0;
+    minor1 = (row0 * tmp - minor1).zwxy();
+
+    tmp = (row1 * row2).yxwz();
+    minor0 = row3 * tmp + minor0;
+    let mut minor3 = row0 * tmp;
+    tmp = tmp.zwxy();
+    minor0 = minor0 - row3 * tmp;
+    minor3 = (row0 * tmp - minor3).zwxy();
+
+    tmp = (row1.zwxy() * row3).yxwz();
+    let row2 = row2.zwxy();
+    minor0 = row2 * tmp + minor0;
+    let mut minor2 = row0 * tmp;
+    tmp = tmp.zwxy();
+    minor0 = minor0 - row2 * tmp;
+    minor2 = (row0 * tmp - minor2).zwxy();
+
+    tmp = (row0 * row1).yxwz();
+    minor2 = row3 * tmp + minor2;
+    minor3 = row2 * tmp - minor3;
+    tmp = tmp.zwxy();
+    minor2 = row3 * tmp - minor2;
+    minor3 = minor3 - row2 * tmp;
+
+    tmp = (row0 * row3).yxwz();
+    minor1 = minor1 - row2 * tmp;
+    minor2 = row1 * tmp + minor2;
+    tmp = tmp.zwxy();
+    minor1 = row2 * tmp + minor1;
+    minor2 = minor2 - row1 * tmp;
+
+    tmp = (row0 * row2).yxwz();
+    minor1 = row3 * tmp + minor1;
+    minor3 = minor3 - row1 * tmp;
+    tmp = tmp.zwxy();
+    minor1 = minor1 - row3 * tmp;
+    minor3 = row1 * tmp + minor3;
+
+    let mut det = row0 * minor0;
+    det = det.zwxy() + det;
+    det = det.yxwz() + det;
+    let determinant = det.store()[0];
+
+    if determinant == 0.0 {
+        return *mat;
+    }
+
+    let determinant_inv = F32x4::splat(1.0 / determinant);
+    store_columns([minor0, minor1, minor2, minor3].map(|minor| minor * determinant_inv))
+}
+
+/// The same as `a * b`. Every term needs a shuffle, so this is only slightly faster than the scalar product,
+/// it is here so chains of rotations can stay on the same path as the matrices.
+#[inline]
+pub fn quat_mul(a: Quatf, b: Quatf) -> Quatf {
+    let rhs = F32x4::load([b.x, b.y, b.z, b.w]);
+
+    let result = F32x4::splat(a.w) * rhs +
+        F32x4::splat(a.x) * rhs.wzyx() * F32x4::load([1.0, -1.0, 1.0, -1.0]) +
+        F32x4::splat(a.y) * rhs.zwxy() * F32x4::load([1.0, 1.0, -1.0, -1.0]) +
+        F32x4::splat(a.z) * rhs.yxwz() * F32x4::load([-1.0, 1.0, 1.0, -1.0]);
+
+    let [x, y, z, w] = result.store();
+    Quatf { x, y, z, w }
+}
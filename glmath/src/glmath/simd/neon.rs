@@ -0,0 +1,86 @@
+use std::arch::aarch64::*;
+use std::ops::{Add, Sub, Mul};
+
+// Every intrinsic used here only needs neon, which this module is only compiled with,
+// and the loads and stores go through arrays of four floats.
+// Multiplies and adds are kept separate instead of using vfmaq_f32 so the rounding matches the scalar code.
+
+/// Four floats in a neon register.
+#[derive(Clone, Copy)]
+pub(crate) struct F32x4(float32x4_t);
+
+impl F32x4 {
+    #[inline(always)]
+    pub fn load(values: [f32; 4]) -> F32x4 {
+        F32x4(unsafe { vld1q_f32(values.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn store(self) -> [f32; 4] {
+        let mut values = [0.0; 4];
+        unsafe { vst1q_f32(values.as_mut_ptr(), self.0) };
+        values
+    }
+
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        F32x4(unsafe { vdupq_n_f32(value) })
+    }
+
+    #[inline(always)]
+    pub fn yxwz(self) -> F32x4 {
+        F32x4(unsafe { vrev64q_f32(self.0) })
+    }
+
+    #[inline(always)]
+    pub fn zwxy(self) -> F32x4 {
+        F32x4(unsafe { vextq_f32::<2>(self.0, self.0) })
+    }
+
+    #[inline(always)]
+    pub fn wzyx(self) -> F32x4 {
+        self.zwxy().yxwz()
+    }
+
+    #[inline(always)]
+    pub fn transpose(columns: [F32x4; 4]) -> [F32x4; 4] {
+        unsafe {
+            let t01 = vtrnq_f32(columns[0].0, columns[1].0);
+            let t23 = vtrnq_f32(columns[2].0, columns[3].0);
+
+            [
+                F32x4(vcombine_f32(vget_low_f32(t01.0), vget_low_f32(t23.0))),
+                F32x4(vcombine_f32(vget_low_f32(t01.1), vget_low_f32(t23.1))),
+                F32x4(vcombine_f32(vget_high_f32(t01.0), vget_high_f32(t23.0))),
+                F32x4(vcombine_f32(vget_high_f32(t01.1), vget_high_f32(t23.1)))
+            ]
+        }
+    }
+}
+
+impl Add for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn add(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { vaddq_f32(self.0, rhs.0) })
+    }
+}
+
+impl Sub for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn sub(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { vsubq_f32(self.0, rhs.0) })
+    }
+}
+
+impl Mul for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn mul(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { vmulq_f32(self.0, rhs.0) })
+    }
+}
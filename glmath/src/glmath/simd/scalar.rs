@@ -0,0 +1,79 @@
+use std::ops::{Add, Sub, Mul};
+
+/// Four floats operated on one at a time, for targets without sse2 or neon.
+#[derive(Clone, Copy)]
+pub(crate) struct F32x4([f32; 4]);
+
+impl F32x4 {
+    #[inline(always)]
+    pub fn load(values: [f32; 4]) -> F32x4 {
+        F32x4(values)
+    }
+
+    #[inline(always)]
+    pub fn store(self) -> [f32; 4] {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        F32x4([value; 4])
+    }
+
+    #[inline(always)]
+    pub fn yxwz(self) -> F32x4 {
+        let [x, y, z, w] = self.0;
+        F32x4([y, x, w, z])
+    }
+
+    #[inline(always)]
+    pub fn zwxy(self) -> F32x4 {
+        let [x, y, z, w] = self.0;
+        F32x4([z, w, x, y])
+    }
+
+    #[inline(always)]
+    pub fn wzyx(self) -> F32x4 {
+        let [x, y, z, w] = self.0;
+        F32x4([w, z, y, x])
+    }
+
+    #[inline(always)]
+    pub fn transpose(columns: [F32x4; 4]) -> [F32x4; 4] {
+        let [a, b, c, d] = columns.map(|column| column.0);
+
+        [
+            F32x4([a[0], b[0], c[0], d[0]]),
+            F32x4([a[1], b[1], c[1], d[1]]),
+            F32x4([a[2], b[2], c[2], d[2]]),
+            F32x4([a[3], b[3], c[3], d[3]])
+        ]
+    }
+}
+
+impl Add for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn add(self, rhs: F32x4) -> F32x4 {
+        F32x4([self.0[0] + rhs.0[0], self.0[1] + rhs.0[1], self.0[2] + rhs.0[2], self.0[3] + rhs.0[3]])
+    }
+}
+
+impl Sub for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn sub(self, rhs: F32x4) -> F32x4 {
+        F32x4([self.0[0] - rhs.0[0], self.0[1] - rhs.0[1], self.0[2] - rhs.0[2], self.0[3] - rhs.0[3]])
+    }
+}
+
+impl Mul for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn mul(self, rhs: F32x4) -> F32x4 {
+        F32x4([self.0[0] * rhs.0[0], self.0[1] * rhs.0[1], self.0[2] * rhs.0[2], self.0[3] * rhs.0[3]])
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::ops::{Add, Sub, Mul};
+
+// Every intrinsic used here only needs sse or sse2, which this module is only compiled with,
+// and the loads and stores go through arrays of four floats.
+
+/// Four floats in an sse register.
+#[derive(Clone, Copy)]
+pub(crate) struct F32x4(__m128);
+
+impl F32x4 {
+    #[inline(always)]
+    pub fn load(values: [f32; 4]) -> F32x4 {
+        F32x4(unsafe { _mm_loadu_ps(values.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn store(self) -> [f32; 4] {
+        let mut values = [0.0; 4];
+        unsafe { _mm_storeu_ps(values.as_mut_ptr(), self.0) };
+        values
+    }
+
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        F32x4(unsafe { _mm_set1_ps(value) })
+    }
+
+    #[inline(always)]
+    pub fn yxwz(self) -> F32x4 {
+        F32x4(unsafe { _mm_shuffle_ps::<0b10_11_00_01>(self.0, self.0) })
+    }
+
+    #[inline(always)]
+    pub fn zwxy(self) -> F32x4 {
+        F32x4(unsafe { _mm_shuffle_ps::<0b01_00_11_10>(self.0, self.0) })
+    }
+
+    #[inline(always)]
+    pub fn wzyx(self) -> F32x4 {
+        F32x4(unsafe { _mm_shuffle_ps::<0b00_01_10_11>(self.0, self.0) })
+    }
+
+    #[inline(always)]
+    pub fn transpose(columns: [F32x4; 4]) -> [F32x4; 4] {
+        unsafe {
+            let t0 = _mm_unpacklo_ps(columns[0].0, columns[1].0);
+            let t1 = _mm_unpacklo_ps(columns[2].0, columns[3].0);
+            let t2 = _mm_unpackhi_ps(columns[0].0, columns[1].0);
+            let t3 = _mm_unpackhi_ps(columns[2].0, columns[3].0);
+
+            [
+                F32x4(_mm_movelh_ps(t0, t1)),
+                F32x4(_mm_movehl_ps(t1, t0)),
+                F32x4(_mm_movelh_ps(t2, t3)),
+                F32x4(_mm_movehl_ps(t3, t2))
+            ]
+        }
+    }
+}
+
+impl Add for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn add(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { _mm_add_ps(self.0, rhs.0) })
+    }
+}
+
+impl Sub for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn sub(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { _mm_sub_ps(self.0, rhs.0) })
+    }
+}
+
+impl Mul for F32x4 {
+    type Output = F32x4;
+
+    #[inline(always)]
+    fn mul(self, rhs: F32x4) -> F32x4 {
+        F32x4(unsafe { _mm_mul_ps(self.0, rhs.0) })
+    }
+}
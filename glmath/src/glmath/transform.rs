@@ -0,0 +1,172 @@
+use std::ops::Mul;
+
+use crate::glmath::*;
+use crate::glmath::geometry::Scalar;
+
+/// A translation, rotation and scale, applied to points in the reverse order: scale first and translation last.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform<T: PartialOrd + Copy> {
+    pub translation: Vec3<T>,
+    pub rotation: Quat<T>,
+    pub scale: Vec3<T>
+}
+
+impl<T: Scalar> Transform<T> {
+    pub fn new(translation: Vec3<T>, rotation: Quat<T>, scale: Vec3<T>) -> Transform<T> {
+        Transform::<T> { translation, rotation, scale }
+    }
+
+    pub fn from_translation(translation: Vec3<T>) -> Transform<T> {
+        Transform::<T> { translation, ..Self::IDENTITY }
+    }
+
+    pub fn from_rotation(rotation: Quat<T>) -> Transform<T> {
+        Transform::<T> { rotation, ..Self::IDENTITY }
+    }
+
+    pub fn from_scale(scale: Vec3<T>) -> Transform<T> {
+        Transform::<T> { scale, ..Self::IDENTITY }
+    }
+
+    pub const IDENTITY: Transform<T> = Transform::<T> {
+        translation: Vec3::<T>::ZERO,
+        rotation: Quat::<T>::IDENTITY,
+        scale: Vec3::<T> { x: T::ONE, y: T::ONE, z: T::ONE }
+    };
+
+    pub fn transform_point(&self, point: Vec3<T>) -> Vec3<T> {
+        self.translation + self.rotation * mul_components(point, self.scale)
+    }
+
+    /// Transforms a direction or offset, which ignores the translation.
+    pub fn transform_vector(&self, vector: Vec3<T>) -> Vec3<T> {
+        self.rotation * mul_components(vector, self.scale)
+    }
+
+    /// Moves a point back into the space this transform transforms from.
+    /// Exact even when the scale isn't uniform, unlike transforming by the inverse.
+    pub fn inverse_transform_point(&self, point: Vec3<T>) -> Vec3<T> {
+        div_components(self.rotation.get_conjugated() * (point - self.translation), self.scale)
+    }
+
+    pub fn inverse_transform_vector(&self, vector: Vec3<T>) -> Vec3<T> {
+        div_components(self.rotation.get_conjugated() * vector, self.scale)
+    }
+
+    /// Combines a child transform with this one as its parent, the result transforms from the child's space
+    /// to the parent's parent space. The same as `self * child`.
+    /// Rotated non uniform scales would shear, which a transform can't hold, so the scales are only multiplied.
+    pub fn compose(&self, child: &Transform<T>) -> Transform<T> {
+        Transform::<T> {
+            translation: self.transform_point(child.translation),
+            rotation: then_rotate(child.rotation, self.rotation),
+            scale: mul_components(self.scale, child.scale)
+        }
+    }
+
+    /// The transform which undoes this one. Exact when the scale is uniform.
+    pub fn inverse(&self) -> Transform<T> {
+        let scale = div_components(Vec3::<T>::new(T::ONE, T::ONE, T::ONE), self.scale);
+        let rotation = self.rotation.get_conjugated();
+
+        Transform::<T> {
+            translation: mul_components(rotation * -self.translation, scale),
+            rotation,
+            scale
+        }
+    }
+
+    /// The local transform of this world transform under a parent, so `parent.compose(&local)` gives this transform back.
+    /// Exact when the parent's scale is uniform.
+    pub fn relative_to(&self, parent: &Transform<T>) -> Transform<T> {
+        Transform::<T> {
+            translation: parent.inverse_transform_point(self.translation),
+            rotation: then_rotate(self.rotation, parent.rotation.get_conjugated()),
+            scale: div_components(self.scale, parent.scale)
+        }
+    }
+
+    /// Builds the matrix which scales, rotates and then translates.
+    pub fn to_matrix(&self) -> Mat44<T> {
+        let mut matrix = self.rotation.to_matrix();
+
+        for (column, scale) in [self.scale.x, self.scale.y, self.scale.z].into_iter().enumerate() {
+            for row in 0..3 {
+                matrix.data[column][row] *= scale;
+            }
+        }
+
+        matrix.data[3] = [ self.translation.x, self.translation.y, self.translation.z, T::ONE ];
+        matrix
+    }
+
+    /// Decomposes an affine matrix into a transform, or None when an axis is scaled to zero.
+    /// Mirroring is kept as a negative x scale and any shear is lost.
+    pub fn from_matrix(mat: &Mat44<T>) -> Option<Transform<T>> {
+        let column = |i: usize| Vec3::<T>::new(mat.data[i][0], mat.data[i][1], mat.data[i][2]);
+        let mut axes = [ column(0), column(1), column(2) ];
+        let mut scale = Vec3::<T>::new(axes[0].length(), axes[1].length(), axes[2].length());
+
+        if scale.x == T::ZERO || scale.y == T::ZERO || scale.z == T::ZERO {
+            return None;
+        }
+
+        if (axes[0] % axes[1]) * axes[2] < T::ZERO {
+            scale.x = -scale.x;
+        }
+
+        axes[0] /= scale.x;
+        axes[1] /= scale.y;
+        axes[2] /= scale.z;
+
+        let rotation = Mat44::<T>::from_axes(
+            Vec4::<T>::new(axes[0].x, axes[0].y, axes[0].z, T::ZERO),
+            Vec4::<T>::new(axes[1].x, axes[1].y, axes[1].z, T::ZERO),
+            Vec4::<T>::new(axes[2].x, axes[2].y, axes[2].z, T::ZERO),
+            Vec4::<T>::new(T::ZERO, T::ZERO, T::ZERO, T::ONE));
+
+        Some(Transform::<T> {
+            translation: Vec3::<T>::new(mat.data[3][0], mat.data[3][1], mat.data[3][2]),
+            rotation: Quat::<T>::from_matrix(&rotation).get_normalized(),
+            scale
+        })
+    }
+
+    /// Blends the translation and scale linearly and the rotation with `Quat::slerp`.
+    pub fn interpolate(a: &Transform<T>, b: &Transform<T>, blend: T) -> Transform<T> {
+        Transform::<T> {
+            translation: a.translation + (b.translation - a.translation) * blend,
+            rotation: Quat::<T>::slerp(a.rotation, b.rotation, blend),
+            scale: a.scale + (b.scale - a.scale) * blend
+        }
+    }
+}
+
+impl<T: Scalar> Default for Transform<T> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Composes the transforms the same way as matrices, the right hand side is applied first.
+impl<T: Scalar> Mul<Transform<T>> for Transform<T> {
+    type Output = Transform<T>;
+
+    fn mul(self, rhs: Transform<T>) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+/// The quaternion which rotates by first and then by second.
+/// The left hand side of a quaternion product is the rotation applied to vectors first.
+fn then_rotate<T: Scalar>(first: Quat<T>, second: Quat<T>) -> Quat<T> {
+    first * second
+}
+
+fn mul_components<T: Scalar>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
+    Vec3::<T>::new(a.x * b.x, a.y * b.y, a.z * b.z)
+}
+
+fn div_components<T: Scalar>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
+    Vec3::<T>::new(a.x / b.x, a.y / b.y, a.z / b.z)
+}
@@ -1,4 +1,4 @@
-use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg}, fmt::Display};
+use std::ops::{Not, BitAnd, BitOr, Neg};
 use crate::glmath::*;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -7,12 +7,6 @@ pub struct Vec2<T: PartialOrd + Copy> {
     pub y: T
 }
 
-impl<T: PartialOrd + Copy + Display> Display for Vec2<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}]", self.x, self.y)
-    }
-}
-
 impl
     <T: PartialOrd + Copy + Vectorable<T> + 
         std::ops::Mul<Output = T> + 
@@ -27,11 +21,13 @@ impl
     }
 }
 
-impl<T: PartialOrd + Copy + Vectorable<T>> Vec2<T> {
+impl<T: PartialOrd + Copy> Vec2<T> {
     pub fn new(x: T, y: T) -> Vec2<T> {
         Vec2::<T> { x, y }
     }
+}
 
+impl<T: Number<T> + PartialOrd + Copy> Vec2<T> {
     pub const ZERO: Vec2<T> = Vec2::<T> {
         x: T::ZERO,
         y: T::ZERO
@@ -48,116 +44,119 @@ impl<T: PartialOrd + Copy + Vectorable<T>> Vec2<T> {
     };
 }
 
-impl<T: PartialOrd + Copy + std::ops::Neg<Output = T>> Neg for Vec2<T> {
-    type Output = Vec2<T>;
-
-    fn neg(self) -> Self::Output {
-        Vec2::<T> { x: -self.x, y: -self.y }
+impl<T: PartialOrd + Copy +
+    std::ops::Div<Output = T>> TwoDimSwizzle<T> for Vec2<T> {
+    fn x(&self) -> &T {
+        &self.x
     }
-}
 
-impl<T: Vectorable<T> + PartialOrd + Copy + std::ops::Add<Output = T>> Add<Vec2<T>> for Vec2<T> {
-    type Output = Vec2<T>;
+    fn y(&self) -> &T {
+        &self.y
+    }
 
-    fn add(self, rhs: Vec2<T>) -> Vec2<T> {
-        let x: T = self.x + rhs.x;
-        let y: T = self.y + rhs.y;
+    fn xy(&self) -> Vec2<T> {
+        self.clone()
+    }
 
-        Vec2::<T>::new(x, y)
+    fn yx(&self) -> Vec2<T> {
+        Vec2::<T> { x: self.y, y: self.x }
     }
 }
 
-impl<T: PartialOrd + Copy + std::ops::AddAssign<T>> AddAssign<Vec2<T>> for Vec2<T> {
-    fn add_assign(&mut self, rhs: Vec2<T>) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+/// Component-wise operations, for integer and float vectors.
+impl<T: PartialOrd + Copy> Vec2<T> {
+    pub fn min(&self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::<T> {
+            x: if other.x < self.x { other.x } else { self.x },
+            y: if other.y < self.y { other.y } else { self.y }
+        }
     }
-}
 
-impl<T: Vectorable<T> + PartialOrd + Copy + std::ops::Sub<Output = T>> Sub<Vec2<T>> for Vec2<T> {
-    type Output = Vec2<T>;
+    pub fn max(&self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::<T> {
+            x: if other.x > self.x { other.x } else { self.x },
+            y: if other.y > self.y { other.y } else { self.y }
+        }
+    }
 
-    fn sub(self, rhs: Vec2<T>) -> Vec2<T> {
-        let x: T = self.x - rhs.x;
-        let y: T = self.y - rhs.y;
+    /// Every component of low should be smaller than or equal to the same component of high.
+    pub fn clamp(&self, low: Vec2<T>, high: Vec2<T>) -> Vec2<T> {
+        self.max(low).min(high)
+    }
 
-        Vec2::<T>::new(x, y)
+    pub fn cmp_eq(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x == other.x, y: self.y == other.y }
     }
-}
 
-impl<T: PartialOrd + Copy + std::ops::SubAssign<T>> SubAssign<Vec2<T>> for Vec2<T> {
-    fn sub_assign(&mut self, rhs: Vec2<T>) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+    pub fn cmp_ne(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x != other.x, y: self.y != other.y }
     }
-}
 
-// Dot product.
-impl<T: PartialOrd + Copy + 
-    std::ops::Mul<Output = T> +
-    std::ops::Add<Output = T>> Mul<Vec2<T>> for Vec2<T> 
-{
-    type Output = T;
+    pub fn cmp_lt(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x < other.x, y: self.y < other.y }
+    }
 
-    // Computes and returns the dot product as a scalar.
-    fn mul(self, rhs: Vec2<T>) -> T {
-        self.x * rhs.x + self.y * rhs.y
+    pub fn cmp_le(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x <= other.x, y: self.y <= other.y }
     }
-}
 
-/// Scales the vector by a scalar value.
-impl<T: PartialOrd + Copy + std::ops::Mul<Output = T>> Mul<T> for Vec2<T> {
-    type Output = Vec2<T>;
+    pub fn cmp_gt(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x > other.x, y: self.y > other.y }
+    }
 
-    fn mul(self, rhs: T) -> Vec2<T>{
+    pub fn cmp_ge(&self, other: Vec2<T>) -> Vec2<bool> {
+        Vec2::<bool> { x: self.x >= other.x, y: self.y >= other.y }
+    }
+}
+
+impl<T: Number<T> + PartialOrd + Copy + Neg<Output = T>> Vec2<T> {
+    pub fn abs(&self) -> Vec2<T> {
         Vec2::<T> {
-            x: self.x * rhs,
-            y: self.y * rhs
+            x: if self.x < T::ZERO { -self.x } else { self.x },
+            y: if self.y < T::ZERO { -self.y } else { self.y }
         }
     }
 }
 
-impl<T: PartialOrd + Copy + std::ops::MulAssign<T>> MulAssign<T> for Vec2<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
+/// Masks produced by the component-wise comparisons.
+impl Vec2<bool> {
+    pub fn all(&self) -> bool {
+        self.x && self.y
     }
-}
 
-// Divides the scale by a number.
-impl<T: PartialOrd + Copy + Div<Output = T>> Div<T> for Vec2<T> {
-    type Output = Vec2<T>;
+    pub fn any(&self) -> bool {
+        self.x || self.y
+    }
 
-    fn div(self, rhs: T) -> Self::Output {
+    /// Picks each component from if_true where the mask is set, and from if_false where it isn't.
+    pub fn select<T: PartialOrd + Copy>(&self, if_true: Vec2<T>, if_false: Vec2<T>) -> Vec2<T> {
         Vec2::<T> {
-            x: self.x / rhs,
-            y: self.y / rhs
+            x: if self.x { if_true.x } else { if_false.x },
+            y: if self.y { if_true.y } else { if_false.y }
         }
     }
 }
 
-impl<T: PartialOrd + Copy + DivAssign> DivAssign<T> for Vec2<T> {
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
+impl Not for Vec2<bool> {
+    type Output = Vec2<bool>;
+
+    fn not(self) -> Self::Output {
+        Vec2::<bool> { x: !self.x, y: !self.y }
     }
 }
 
-impl<T: PartialOrd + Copy +
-    std::ops::Div<Output = T>> TwoDimSwizzle<T> for Vec2<T> {
-    fn x(&self) -> &T {
-        &self.x
-    }
+impl BitAnd for Vec2<bool> {
+    type Output = Vec2<bool>;
 
-    fn y(&self) -> &T {
-        &self.y
+    fn bitand(self, rhs: Vec2<bool>) -> Self::Output {
+        Vec2::<bool> { x: self.x && rhs.x, y: self.y && rhs.y }
     }
+}
 
-    fn xy(&self) -> Vec2<T> {
-        self.clone()
-    }
+impl BitOr for Vec2<bool> {
+    type Output = Vec2<bool>;
 
-    fn yx(&self) -> Vec2<T> {
-        Vec2::<T> { x: self.y, y: self.x }
+    fn bitor(self, rhs: Vec2<bool>) -> Self::Output {
+        Vec2::<bool> { x: self.x || rhs.x, y: self.y || rhs.y }
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-use std::{ops::{Add, AddAssign, Neg, Sub, SubAssign, Mul, MulAssign, Rem}, fmt::Display};
+use std::ops::{Not, BitAnd, BitOr, Neg, Sub, Mul, Rem};
 
 use crate::glmath::*;
 
@@ -9,12 +9,6 @@ pub struct Vec3<T: PartialOrd + Copy> {
     pub z: T
 }
 
-impl<T: PartialOrd + Copy + Display> Display for Vec3<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
-    }
-}
-
 impl
     <T: PartialOrd + Copy + Vectorable<T> + 
         std::ops::Mul<Output = T> + 
@@ -29,11 +23,13 @@ impl
     }
 }
 
-impl<T: Vectorable<T> + PartialOrd + Copy> Vec3<T> {
+impl<T: PartialOrd + Copy> Vec3<T> {
     pub fn new(x: T, y: T, z: T) -> Vec3<T> {
         Vec3::<T> { x, y, z }
     }
+}
 
+impl<T: Number<T> + PartialOrd + Copy> Vec3<T> {
     pub const ZERO: Vec3<T> = Vec3::<T> {
         x: T::ZERO,
         y: T::ZERO,
@@ -59,112 +55,6 @@ impl<T: Vectorable<T> + PartialOrd + Copy> Vec3<T> {
     };
 }
 
-impl<T: PartialOrd + Copy + std::ops::Neg<Output = T>> Neg for Vec3<T> {
-    type Output = Vec3<T>;
-
-    fn neg(self) -> Self::Output {
-        Vec3::<T> {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + Add<Output = T>> Add<Vec3<T>> for Vec3<T> {
-    type Output = Vec3<T>;
-
-    fn add(self, rhs: Vec3<T>) -> Self::Output {
-        Vec3::<T> {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + AddAssign> AddAssign for Vec3<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
-    }
-}
-
-impl<T: PartialOrd + Copy + Sub<Output = T>> Sub<Vec3<T>> for Vec3<T> {
-    type Output = Vec3<T>;
-
-    fn sub(self, rhs: Vec3<T>) -> Self::Output {
-        Vec3::<T> {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + SubAssign> SubAssign for Vec3<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-    }
-}
-
-// Scalar multiples.
-impl<T: PartialOrd + Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
-    type Output = Vec3<T>;
-
-    fn mul(self, rhs: T) -> Self::Output {
-         Vec3::<T> {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs
-        }       
-    }
-}
-
-impl<T: PartialOrd + Copy + MulAssign> MulAssign<T> for Vec3<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
-    }
-}
-
-// Scalar divides.
-impl<T: PartialOrd + Copy + Div<Output = T>> Div<T> for Vec3<T> {
-    type Output = Vec3<T>;
-
-    fn div(self, rhs: T) -> Self::Output {
-         Vec3::<T> {
-            x: self.x / rhs,
-            y: self.y / rhs,
-            z: self.z / rhs
-        }       
-    }
-}
-
-impl<T: PartialOrd + Copy + DivAssign> DivAssign<T> for Vec3<T> {
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
-        self.z /= rhs;
-    }
-}
-
-// Dot product.
-impl<T: PartialOrd + Copy + 
-    Mul<Output = T> + 
-    Add<Output = T>> Mul<Vec3<T>> for Vec3<T> 
-{
-    type Output = T;
-
-    fn mul(self, rhs: Vec3<T>) -> Self::Output {
-        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
-    }
-}
-
 // Cross product.
 impl<T: PartialOrd + Copy + Mul<Output = T> + Sub<Output = T>> Rem<Vec3<T>> for Vec3<T> {
     type Output = Vec3<T>;
@@ -230,4 +120,106 @@ impl<T: PartialOrd + Copy> ThreeDimSwizzle<T> for Vec3<T> {
     fn zyx(&self) -> Vec3<T> {
         Vec3::<T> { x: self.z, y: self.y, z: self.x }
     }
-}
\ No newline at end of file
+}
+
+/// Component-wise operations, for integer and float vectors.
+impl<T: PartialOrd + Copy> Vec3<T> {
+    pub fn min(&self, other: Vec3<T>) -> Vec3<T> {
+        Vec3::<T> {
+            x: if other.x < self.x { other.x } else { self.x },
+            y: if other.y < self.y { other.y } else { self.y },
+            z: if other.z < self.z { other.z } else { self.z }
+        }
+    }
+
+    pub fn max(&self, other: Vec3<T>) -> Vec3<T> {
+        Vec3::<T> {
+            x: if other.x > self.x { other.x } else { self.x },
+            y: if other.y > self.y { other.y } else { self.y },
+            z: if other.z > self.z { other.z } else { self.z }
+        }
+    }
+
+    /// Every component of low should be smaller than or equal to the same component of high.
+    pub fn clamp(&self, low: Vec3<T>, high: Vec3<T>) -> Vec3<T> {
+        self.max(low).min(high)
+    }
+
+    pub fn cmp_eq(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x == other.x, y: self.y == other.y, z: self.z == other.z }
+    }
+
+    pub fn cmp_ne(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x != other.x, y: self.y != other.y, z: self.z != other.z }
+    }
+
+    pub fn cmp_lt(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x < other.x, y: self.y < other.y, z: self.z < other.z }
+    }
+
+    pub fn cmp_le(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x <= other.x, y: self.y <= other.y, z: self.z <= other.z }
+    }
+
+    pub fn cmp_gt(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x > other.x, y: self.y > other.y, z: self.z > other.z }
+    }
+
+    pub fn cmp_ge(&self, other: Vec3<T>) -> Vec3<bool> {
+        Vec3::<bool> { x: self.x >= other.x, y: self.y >= other.y, z: self.z >= other.z }
+    }
+}
+
+impl<T: Number<T> + PartialOrd + Copy + Neg<Output = T>> Vec3<T> {
+    pub fn abs(&self) -> Vec3<T> {
+        Vec3::<T> {
+            x: if self.x < T::ZERO { -self.x } else { self.x },
+            y: if self.y < T::ZERO { -self.y } else { self.y },
+            z: if self.z < T::ZERO { -self.z } else { self.z }
+        }
+    }
+}
+
+/// Masks produced by the component-wise comparisons.
+impl Vec3<bool> {
+    pub fn all(&self) -> bool {
+        self.x && self.y && self.z
+    }
+
+    pub fn any(&self) -> bool {
+        self.x || self.y || self.z
+    }
+
+    /// Picks each component from if_true where the mask is set, and from if_false where it isn't.
+    pub fn select<T: PartialOrd + Copy>(&self, if_true: Vec3<T>, if_false: Vec3<T>) -> Vec3<T> {
+        Vec3::<T> {
+            x: if self.x { if_true.x } else { if_false.x },
+            y: if self.y { if_true.y } else { if_false.y },
+            z: if self.z { if_true.z } else { if_false.z }
+        }
+    }
+}
+
+impl Not for Vec3<bool> {
+    type Output = Vec3<bool>;
+
+    fn not(self) -> Self::Output {
+        Vec3::<bool> { x: !self.x, y: !self.y, z: !self.z }
+    }
+}
+
+impl BitAnd for Vec3<bool> {
+    type Output = Vec3<bool>;
+
+    fn bitand(self, rhs: Vec3<bool>) -> Self::Output {
+        Vec3::<bool> { x: self.x && rhs.x, y: self.y && rhs.y, z: self.z && rhs.z }
+    }
+}
+
+impl BitOr for Vec3<bool> {
+    type Output = Vec3<bool>;
+
+    fn bitor(self, rhs: Vec3<bool>) -> Self::Output {
+        Vec3::<bool> { x: self.x || rhs.x, y: self.y || rhs.y, z: self.z || rhs.z }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{ops::{Add, MulAssign, AddAssign, SubAssign, Sub, Neg}, fmt::Display};
+use std::ops::{Not, BitAnd, BitOr, Neg};
 use crate::glmath::*;
 
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
@@ -23,17 +23,13 @@ impl
     }
 }
 
-impl<T: PartialOrd + Copy + Display> Display for Vec4<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}, {}, {}]", self.x, self.y, self.z, self.w)
-    }
-}
-
-impl<T: Vectorable<T> + PartialOrd + Copy> Vec4<T> {
+impl<T: PartialOrd + Copy> Vec4<T> {
     pub fn new(x: T, y: T, z: T, w: T) -> Vec4<T> {
         Vec4::<T> { x, y, z, w }
     }
+}
 
+impl<T: Number<T> + PartialOrd + Copy> Vec4<T> {
     pub const ZERO: Vec4<T> = Vec4::<T> {
         x: T::ZERO,
         y: T::ZERO,
@@ -70,125 +66,7 @@ impl<T: Vectorable<T> + PartialOrd + Copy> Vec4<T> {
     };
 }
 
-impl<T: PartialOrd + Copy + Neg<Output = T>> Neg for Vec4<T> {
-    type Output = Vec4<T>;
-
-    fn neg(self) -> Self::Output {
-        Vec4::<T> {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z,
-            w: -self.w
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + Add<Output = T>> Add<Vec4<T>> for Vec4<T> {
-    type Output = Vec4<T>;
-
-    fn add(self, rhs: Vec4<T>) -> Self::Output {
-        Vec4::<T> {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-            w: self.w + rhs.w
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + AddAssign> AddAssign for Vec4<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
-        self.w += rhs.w;
-    }
-}
-
-impl<T: PartialOrd + Copy + Sub<Output = T>> Sub<Vec4<T>> for Vec4<T> {
-    type Output = Vec4<T>;
-
-    fn sub(self, rhs: Vec4<T>) -> Self::Output {
-        Vec4::<T> {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-            w: self.w - rhs.w
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + SubAssign> SubAssign for Vec4<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-        self.w -= rhs.w;
-    }
-}
-
-// Scalar multiples.
-impl<T: PartialOrd + Copy + Mul<Output = T>> Mul<T> for Vec4<T> {
-    type Output = Vec4<T>;
-
-    fn mul(self, rhs: T) -> Self::Output {
-        Vec4::<T> {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-            w: self.w * rhs
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + MulAssign> MulAssign<T> for Vec4<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
-        self.w *= rhs;
-    }
-}
-
-// Scalar divides.
-impl<T: PartialOrd + Copy + Div<Output = T>> Div<T> for Vec4<T> {
-    type Output = Vec4<T>;
-
-    fn div(self, rhs: T) -> Self::Output {
-        Vec4::<T> {
-            x: self.x / rhs,
-            y: self.y / rhs,
-            z: self.z / rhs,
-            w: self.w / rhs
-        }
-    }
-}
-
-impl<T: PartialOrd + Copy + DivAssign> DivAssign<T> for Vec4<T> {
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
-        self.z /= rhs;
-        self.w /= rhs;
-    }
-}
-
-// Dot product.
-impl<T: PartialOrd + Copy + 
-    Mul<Output = T> +
-    Add<Output = T>> Mul<Vec4<T>> for Vec4<T> 
-{
-    type Output = T;
-
-    fn mul(self, rhs: Vec4<T>) -> Self::Output {
-        self.x * rhs.x + 
-            self.y * rhs.y + 
-            self.z * rhs.z + 
-            self.w * rhs.w
-    }
-}
-
-impl<T: Vectorable<T> + PartialOrd + Copy> TwoDimSwizzle<T> for Vec4<T> {
+impl<T: PartialOrd + Copy> TwoDimSwizzle<T> for Vec4<T> {
     fn x(&self) -> &T {
         &self.x
     }
@@ -206,7 +84,7 @@ impl<T: Vectorable<T> + PartialOrd + Copy> TwoDimSwizzle<T> for Vec4<T> {
     }
 }
 
-impl<T: Vectorable<T> + PartialOrd + Copy> ThreeDimSwizzle<T> for Vec4<T> {
+impl<T: PartialOrd + Copy> ThreeDimSwizzle<T> for Vec4<T> {
     fn z(&self) -> &T {
         &self.z
     }
@@ -236,7 +114,7 @@ impl<T: Vectorable<T> + PartialOrd + Copy> ThreeDimSwizzle<T> for Vec4<T> {
     }
 }
 
-impl<T: Vectorable<T> + PartialOrd + Copy> FourDimSwizzle<T> for Vec4<T> {
+impl<T: PartialOrd + Copy> FourDimSwizzle<T> for Vec4<T> {
     fn w(&self) -> &T {
         return &self.w;
     }
@@ -336,4 +214,150 @@ impl<T: Vectorable<T> + PartialOrd + Copy> FourDimSwizzle<T> for Vec4<T> {
     fn wxyz(&self) -> Vec4<T> {
         Vec4::<T> { x: self.w, y: self.x, z: self.y, w: self.z }
     }
-}
\ No newline at end of file
+}
+
+/// Component-wise operations, for integer and float vectors.
+impl<T: PartialOrd + Copy> Vec4<T> {
+    pub fn min(&self, other: Vec4<T>) -> Vec4<T> {
+        Vec4::<T> {
+            x: if other.x < self.x { other.x } else { self.x },
+            y: if other.y < self.y { other.y } else { self.y },
+            z: if other.z < self.z { other.z } else { self.z },
+            w: if other.w < self.w { other.w } else { self.w }
+        }
+    }
+
+    pub fn max(&self, other: Vec4<T>) -> Vec4<T> {
+        Vec4::<T> {
+            x: if other.x > self.x { other.x } else { self.x },
+            y: if other.y > self.y { other.y } else { self.y },
+            z: if other.z > self.z { other.z } else { self.z },
+            w: if other.w > self.w { other.w } else { self.w }
+        }
+    }
+
+    /// Every component of low should be smaller than or equal to the same component of high.
+    pub fn clamp(&self, low: Vec4<T>, high: Vec4<T>) -> Vec4<T> {
+        self.max(low).min(high)
+    }
+
+    pub fn cmp_eq(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z,
+            w: self.w == other.w
+        }
+    }
+
+    pub fn cmp_ne(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z,
+            w: self.w != other.w
+        }
+    }
+
+    pub fn cmp_lt(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z,
+            w: self.w < other.w
+        }
+    }
+
+    pub fn cmp_le(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z,
+            w: self.w <= other.w
+        }
+    }
+
+    pub fn cmp_gt(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z,
+            w: self.w > other.w
+        }
+    }
+
+    pub fn cmp_ge(&self, other: Vec4<T>) -> Vec4<bool> {
+        Vec4::<bool> {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z,
+            w: self.w >= other.w
+        }
+    }
+}
+
+impl<T: Number<T> + PartialOrd + Copy + Neg<Output = T>> Vec4<T> {
+    pub fn abs(&self) -> Vec4<T> {
+        Vec4::<T> {
+            x: if self.x < T::ZERO { -self.x } else { self.x },
+            y: if self.y < T::ZERO { -self.y } else { self.y },
+            z: if self.z < T::ZERO { -self.z } else { self.z },
+            w: if self.w < T::ZERO { -self.w } else { self.w }
+        }
+    }
+}
+
+/// Masks produced by the component-wise comparisons.
+impl Vec4<bool> {
+    pub fn all(&self) -> bool {
+        self.x && self.y && self.z && self.w
+    }
+
+    pub fn any(&self) -> bool {
+        self.x || self.y || self.z || self.w
+    }
+
+    /// Picks each component from if_true where the mask is set, and from if_false where it isn't.
+    pub fn select<T: PartialOrd + Copy>(&self, if_true: Vec4<T>, if_false: Vec4<T>) -> Vec4<T> {
+        Vec4::<T> {
+            x: if self.x { if_true.x } else { if_false.x },
+            y: if self.y { if_true.y } else { if_false.y },
+            z: if self.z { if_true.z } else { if_false.z },
+            w: if self.w { if_true.w } else { if_false.w }
+        }
+    }
+}
+
+impl Not for Vec4<bool> {
+    type Output = Vec4<bool>;
+
+    fn not(self) -> Self::Output {
+        Vec4::<bool> { x: !self.x, y: !self.y, z: !self.z, w: !self.w }
+    }
+}
+
+impl BitAnd for Vec4<bool> {
+    type Output = Vec4<bool>;
+
+    fn bitand(self, rhs: Vec4<bool>) -> Self::Output {
+        Vec4::<bool> {
+            x: self.x && rhs.x,
+            y: self.y && rhs.y,
+            z: self.z && rhs.z,
+            w: self.w && rhs.w
+        }
+    }
+}
+
+impl BitOr for Vec4<bool> {
+    type Output = Vec4<bool>;
+
+    fn bitor(self, rhs: Vec4<bool>) -> Self::Output {
+        Vec4::<bool> {
+            x: self.x || rhs.x,
+            y: self.y || rhs.y,
+            z: self.z || rhs.z,
+            w: self.w || rhs.w
+        }
+    }
+}
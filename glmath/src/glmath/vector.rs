@@ -0,0 +1,287 @@
+use std::{fmt::Display, ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg, Index, IndexMut}};
+
+use crate::glmath::*;
+
+/// A vector of any size, the arithmetic shared by every vector type is written once here.
+/// `Vec2`, `Vec3` and `Vec4` keep their named components and convert to and from this for their operators.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Vector<T: PartialOrd + Copy, const N: usize> {
+    pub data: [T; N]
+}
+
+impl<T: PartialOrd + Copy, const N: usize> Vector<T, N> {
+    pub fn new(data: [T; N]) -> Vector<T, N> {
+        Vector::<T, N> { data }
+    }
+}
+
+impl<T: Number<T> + PartialOrd + Copy, const N: usize> Vector<T, N> {
+    pub const ZERO: Vector<T, N> = Vector::<T, N> { data: [T::ZERO; N] };
+}
+
+impl<T: PartialOrd + Copy + Default, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Vector::<T, N> { data: [T::default(); N] }
+    }
+}
+
+impl<T: PartialOrd + Copy + Display, const N: usize> Display for Vector<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+
+        for (index, value) in self.data.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", value)?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+impl<T: PartialOrd + Copy, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T: PartialOrd + Copy, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data[index]
+    }
+}
+
+impl<T: PartialOrd + Copy + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn neg(self) -> Self::Output {
+        Vector::<T, N> { data: self.data.map(|value| -value) }
+    }
+}
+
+impl<T: PartialOrd + Copy + Add<Output = T>, const N: usize> Add for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn add(mut self, rhs: Vector<T, N>) -> Self::Output {
+        for (value, rhs) in self.data.iter_mut().zip(rhs.data) {
+            *value = *value + rhs;
+        }
+
+        self
+    }
+}
+
+impl<T: PartialOrd + Copy + AddAssign, const N: usize> AddAssign for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (value, rhs) in self.data.iter_mut().zip(rhs.data) {
+            *value += rhs;
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy + Sub<Output = T>, const N: usize> Sub for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn sub(mut self, rhs: Vector<T, N>) -> Self::Output {
+        for (value, rhs) in self.data.iter_mut().zip(rhs.data) {
+            *value = *value - rhs;
+        }
+
+        self
+    }
+}
+
+impl<T: PartialOrd + Copy + SubAssign, const N: usize> SubAssign for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (value, rhs) in self.data.iter_mut().zip(rhs.data) {
+            *value -= rhs;
+        }
+    }
+}
+
+// Scalar multiples.
+impl<T: PartialOrd + Copy + Mul<Output = T>, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vector::<T, N> { data: self.data.map(|value| value * rhs) }
+    }
+}
+
+impl<T: PartialOrd + Copy + MulAssign, const N: usize> MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        for value in self.data.iter_mut() {
+            *value *= rhs;
+        }
+    }
+}
+
+// Scalar divides.
+impl<T: PartialOrd + Copy + Div<Output = T>, const N: usize> Div<T> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Vector::<T, N> { data: self.data.map(|value| value / rhs) }
+    }
+}
+
+impl<T: PartialOrd + Copy + DivAssign, const N: usize> DivAssign<T> for Vector<T, N> {
+    fn div_assign(&mut self, rhs: T) {
+        for value in self.data.iter_mut() {
+            *value /= rhs;
+        }
+    }
+}
+
+/// Dot product, summed from the first component so the result matches the sized vectors.
+/// An empty vector has no first component, so its dot product fails to compile:
+///
+/// ```compile_fail
+/// use glmath::glmath::vector::Vector;
+///
+/// let empty = Vector::<f32, 0>::new([]);
+/// let _ = empty * empty;
+/// ```
+impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>, const N: usize> Mul<Vector<T, N>> for Vector<T, N> {
+    type Output = T;
+
+    fn mul(self, rhs: Vector<T, N>) -> Self::Output {
+        const { assert!(N > 0, "the dot product needs at least one component") };
+
+        let mut sum = self.data[0] * rhs.data[0];
+
+        for (value, rhs) in self.data.iter().zip(rhs.data).skip(1) {
+            sum = sum + *value * rhs;
+        }
+
+        sum
+    }
+}
+
+impl
+    <T: PartialOrd + Copy + Vectorable<T> +
+        Mul<Output = T> +
+        Add<Output = T> +
+        Div<Output = T> +
+        DivAssign<T>, const N: usize>
+        StandardVec<T> for Vector<T, N>
+{
+    fn length_sq(&self) -> T {
+        *self * *self
+    }
+}
+
+/// Implements the operators of a sized vector by converting it to a `Vector`.
+macro_rules! impl_vector_operators {
+    ($($vector:ident, $size:literal: $($component:ident: $index:literal),*);*) => {
+        $(
+            impl<T: PartialOrd + Copy> From<$vector<T>> for Vector<T, $size> {
+                fn from(v: $vector<T>) -> Self {
+                    Vector::<T, $size> { data: [$(v.$component),*] }
+                }
+            }
+
+            impl<T: PartialOrd + Copy> From<Vector<T, $size>> for $vector<T> {
+                fn from(v: Vector<T, $size>) -> Self {
+                    $vector::<T> { $($component: v.data[$index]),* }
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Display> Display for $vector<T> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    Vector::from(*self).fmt(f)
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Neg<Output = T>> Neg for $vector<T> {
+                type Output = $vector<T>;
+
+                fn neg(self) -> Self::Output {
+                    (-Vector::from(self)).into()
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Add<Output = T>> Add for $vector<T> {
+                type Output = $vector<T>;
+
+                fn add(self, rhs: $vector<T>) -> Self::Output {
+                    (Vector::from(self) + Vector::from(rhs)).into()
+                }
+            }
+
+            impl<T: PartialOrd + Copy + AddAssign> AddAssign for $vector<T> {
+                fn add_assign(&mut self, rhs: $vector<T>) {
+                    let mut v = Vector::from(*self);
+                    v += Vector::from(rhs);
+                    *self = v.into();
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Sub<Output = T>> Sub for $vector<T> {
+                type Output = $vector<T>;
+
+                fn sub(self, rhs: $vector<T>) -> Self::Output {
+                    (Vector::from(self) - Vector::from(rhs)).into()
+                }
+            }
+
+            impl<T: PartialOrd + Copy + SubAssign> SubAssign for $vector<T> {
+                fn sub_assign(&mut self, rhs: $vector<T>) {
+                    let mut v = Vector::from(*self);
+                    v -= Vector::from(rhs);
+                    *self = v.into();
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Mul<Output = T>> Mul<T> for $vector<T> {
+                type Output = $vector<T>;
+
+                fn mul(self, rhs: T) -> Self::Output {
+                    (Vector::from(self) * rhs).into()
+                }
+            }
+
+            impl<T: PartialOrd + Copy + MulAssign> MulAssign<T> for $vector<T> {
+                fn mul_assign(&mut self, rhs: T) {
+                    let mut v = Vector::from(*self);
+                    v *= rhs;
+                    *self = v.into();
+                }
+            }
+
+            impl<T: PartialOrd + Copy + Div<Output = T>> Div<T> for $vector<T> {
+                type Output = $vector<T>;
+
+                fn div(self, rhs: T) -> Self::Output {
+                    (Vector::from(self) / rhs).into()
+                }
+            }
+
+            impl<T: PartialOrd + Copy + DivAssign> DivAssign<T> for $vector<T> {
+                fn div_assign(&mut self, rhs: T) {
+                    let mut v = Vector::from(*self);
+                    v /= rhs;
+                    *self = v.into();
+                }
+            }
+
+            // Dot product.
+            impl<T: PartialOrd + Copy + Mul<Output = T> + Add<Output = T>> Mul<$vector<T>> for $vector<T> {
+                type Output = T;
+
+                fn mul(self, rhs: $vector<T>) -> Self::Output {
+                    Vector::from(self) * Vector::from(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_vector_operators!(
+    Vec2, 2: x: 0, y: 1;
+    Vec3, 3: x: 0, y: 1, z: 2;
+    Vec4, 4: x: 0, y: 1, z: 2, w: 3);
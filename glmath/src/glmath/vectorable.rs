@@ -1,20 +1,36 @@
-pub trait Vectorable<T> {
+/// The constants every vector component has, implemented for the float and integer types.
+pub trait Number<T> {
+    const ZERO: T;
+    const ONE: T;
+}
+
+/// The float types, needed for lengths, angles and everything built on them.
+pub trait Vectorable<T>: Number<T> {
     fn sqrt(&self) -> T;
     fn acos(&self) -> T;
     fn sin(&self) -> T;
     fn cos(&self) -> T;
+    fn tan(&self) -> T;
 
     fn atan2(a: T, b: T) -> T;
     fn asin(&self) -> T;
 
     fn max(a: Self, b: Self) -> T;
 
-    const ZERO: T;
-    const ONE: T;
+    /// Converts a constant, ex: the weights of a formula or a count of segments.
+    fn from_f64(value: f64) -> T;
+    fn to_f64(&self) -> f64;
+
     const TWO: T;
     const HALF: T;
     const QUARTER: T;
     const PI: T;
+    const EPSILON: T;
+}
+
+impl Number<f32> for f32 {
+    const ZERO: f32 = 0.0;
+    const ONE: f32 = 1.0;
 }
 
 impl Vectorable<f32> for f32 {
@@ -34,6 +50,10 @@ impl Vectorable<f32> for f32 {
         f32::cos(*self)
     }
 
+    fn tan(&self) -> f32 {
+        f32::tan(*self)
+    }
+
     fn atan2(a: f32, b: f32) -> f32 {
         f32::atan2(a, b)
     }
@@ -46,12 +66,24 @@ impl Vectorable<f32> for f32 {
         if a > b { a } else { b }
     }
 
-    const ZERO: f32 = 0.0;
-    const ONE: f32 = 1.0;
+    fn from_f64(value: f64) -> f32 {
+        value as f32
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+
     const TWO: f32 = 2.0;
     const HALF: f32 = 0.5;
     const QUARTER: f32 = 0.25;
     const PI: f32 = 3.1415926;
+    const EPSILON: f32 = f32::EPSILON;
+}
+
+impl Number<f64> for f64 {
+    const ZERO: f64 = 0.0;
+    const ONE: f64 = 1.0;
 }
 
 impl Vectorable<f64> for f64 {
@@ -71,6 +103,10 @@ impl Vectorable<f64> for f64 {
         f64::cos(*self)
     }
 
+    fn tan(&self) -> f64 {
+        f64::tan(*self)
+    }
+
     fn atan2(a: f64, b: f64) -> f64 {
         f64::atan2(a, b)
     }
@@ -83,10 +119,30 @@ impl Vectorable<f64> for f64 {
         if a > b { a } else { b }
     }
 
-    const ZERO: f64 = 0.0;
-    const ONE: f64 = 1.0;
+    fn from_f64(value: f64) -> f64 {
+        value
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+
     const TWO: f64 = 2.0;
     const HALF: f64 = 0.5;
     const QUARTER: f64 = 0.25;
     const PI: f64 = 3.1415926;
+    const EPSILON: f64 = f64::EPSILON;
+}
+
+macro_rules! impl_number {
+    ($($integer:ty),*) => {
+        $(
+            impl Number<$integer> for $integer {
+                const ZERO: $integer = 0;
+                const ONE: $integer = 1;
+            }
+        )*
+    };
 }
+
+impl_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
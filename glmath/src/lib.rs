@@ -1 +1 @@
-pub mod glmath;
\ No newline at end of file
+pub mod glmath;
@@ -1,16 +1,16 @@
 
 #[cfg(test)] 
 mod tests {
-    macro_rules! assert_delta {
-        ($x:expr, $y:expr, $d:expr) => {
-            if !($x - $y < $d || $y - $x < $d) { panic!(); }
-        }
-    }
-
     const DELTA: f64 = 0.00001;
+    const EPSILON: f32 = DELTA as f32;
 
     use std::{f32::consts::PI};
-    use glmath::glmath::{*, quat::Quat, vec3::Vec3};
+    use glmath::glmath::{*, quat::Quat, vec2::Vec2, vec3::Vec3, vec4::Vec4, vector::Vector, matrix::Matrix};
+    use glmath::glmath::geometry::*;
+    use glmath::glmath::simd;
+    use glmath::glmath::approx::*;
+    use glmath::glmath::curves::{*, bezier::*, hermite::Hermite, catmull_rom::CatmullRom, b_spline::BSpline, squad::Squad};
+    use glmath::{assert_approx_eq, assert_abs_diff_eq, assert_relative_eq, assert_ulps_eq};
 
     #[test]
     fn test_vec2() {
@@ -28,7 +28,7 @@ mod tests {
         assert_eq!(zero, zero.get_normalized());
 
         // The angle between any two axes should be 90 degrees.
-        assert_eq!(Vec2f::X.angle_between(&Vec2f::Y), (2.0 * PI) / 4.0);
+        assert_approx_eq!(Vec2f::X.angle_between(&Vec2f::Y), (2.0 * PI) / 4.0);
 
         // Dot product.
         let a = Vec2f::new(1.0, 1.0);
@@ -52,7 +52,7 @@ mod tests {
         assert_eq!(zero, zero.get_normalized());
 
         // The angle between any two axes should be 90 degrees.
-        assert_eq!(Vec3f::X.angle_between(&Vec3f::Y), (2.0 * PI) / 4.0);
+        assert_approx_eq!(Vec3f::X.angle_between(&Vec3f::Y), (2.0 * PI) / 4.0);
 
         // Cross product.
         let cross = v1 % Vec3f::new(1.0, 1.0, 0.0);
@@ -60,7 +60,7 @@ mod tests {
 
         let cross = Vec3f::new(32.0, 1.4, 30.0) %
                                Vec3f::new(10.0, 10.1, 22.0);
-        assert_eq!(Vec3f::new(-272.2, -404.0, 309.2), cross);
+        assert_approx_eq!(Vec3f::new(-272.2, -404.0, 309.2), cross);
 
         assert_eq!(Vec3f::ZERO % Vec3f::ZERO, Vec3f::ZERO);
     }
@@ -81,7 +81,7 @@ mod tests {
         assert_eq!(zero, zero.get_normalized());
 
         // The angle between any two axes should be 90 degrees.
-        assert_eq!(Vec2f::X.angle_between(&Vec2f::Y), (2.0 * PI) / 4.0);
+        assert_approx_eq!(Vec2f::X.angle_between(&Vec2f::Y), (2.0 * PI) / 4.0);
     }
 
     #[test]
@@ -94,38 +94,27 @@ mod tests {
         assert_eq!(q, q.get_inverted());
 
         let q = Quatf::new(10.0, 13.0, 2.0, 1.0);
-        assert_eq!(Quatf::new(0.60412204, -0.7853587, -0.12082442, -0.06041221), q.get_inverted());
+        assert_approx_eq!(Quatf::new(0.60412204, -0.7853587, -0.12082442, -0.06041221), q.get_inverted());
 
         // Test the conversions between types of rotations.
         let q = Quat::<f64>::from_axis_angle(
             Vec3::<f64>::new(1.0, 0.0, 0.0), 0.1);
 
-        assert_delta!(q.x, 0.4997916927067833, DELTA);
-        assert_delta!(q.y, 0.0, DELTA);
-        assert_delta!(q.z, 0.0, DELTA);
-        assert_delta!(q.w, 0.9987502603949663, DELTA);
+        assert_abs_diff_eq!(q, Quat::<f64>::new(0.04997916927067833, 0.0, 0.0, 0.9987502603949663), DELTA);
 
         // Is the matrix representation correct?
         let as_mat = q.to_matrix();
         let q2 = Quat::<f64>::from_matrix(&as_mat);
-        assert_delta!(q.x, q2.x, DELTA);
-        assert_delta!(q.y, q2.y, DELTA);
-        assert_delta!(q.z, q2.z, DELTA);
-        assert_delta!(q.w, q2.w, DELTA);
+        assert_abs_diff_eq!(q, q2, DELTA);
 
         // Check euler angles.
         let euler = q.to_euler();
-        assert_delta!(euler.x, 0.1, DELTA);
-        assert_delta!(euler.y, 0.0, DELTA);
-        assert_delta!(euler.z, 0.0, DELTA);
+        assert_abs_diff_eq!(euler, Vec3::<f64>::new(0.1, 0.0, 0.0), DELTA);
 
         // Make sure the conversion back from euler angles 
         // is the correct quat.
         let q2 = Quat::<f64>::from_euler_angles(euler);
-        assert_delta!(q.x, q2.x, DELTA);
-        assert_delta!(q.y, q2.y, DELTA);
-        assert_delta!(q.z, q2.z, DELTA);
-        assert_delta!(q.w, q2.w, DELTA);
+        assert_abs_diff_eq!(q, q2, DELTA);
     }
 
     #[test]
@@ -206,4 +195,783 @@ mod tests {
         // Idt * scale matrix = scale matrix.
         assert_eq!(Mat44f::IDENTITY * scale_mat, scale_mat);
     }
-}
\ No newline at end of file
+
+    /// Transforms a point and applies the perspective divide.
+    fn project(mat: Mat44f, point: Vec3f) -> Vec3f {
+        let clip = mat * Vec4f::new(point.x, point.y, point.z, 1.0);
+        Vec3f::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w)
+    }
+
+    #[test]
+    fn test_mat44_orthographic() {
+        let ortho = Mat44f::orthographic(-4.0, 4.0, -2.0, 2.0, 1.0, 11.0);
+
+        // The corners of the box map to the corners of the clip cube.
+        assert_abs_diff_eq!(project(ortho, Vec3f::new(-4.0, -2.0, -1.0)), Vec3f::new(-1.0, -1.0, -1.0), EPSILON);
+        assert_abs_diff_eq!(project(ortho, Vec3f::new(4.0, 2.0, -11.0)), Vec3f::new(1.0, 1.0, 1.0), EPSILON);
+        assert_abs_diff_eq!(project(ortho, Vec3f::new(0.0, 0.0, -6.0)), Vec3f::ZERO, EPSILON);
+
+        // Orthographic projections don't touch w.
+        assert_eq!((ortho * Vec4f::new(3.0, 1.0, -5.0, 1.0)).w, 1.0);
+
+        // A scaled 2d screen space camera with y pointing down.
+        let screen = Mat44f::orthographic(0.0, 1920.0, 1080.0, 0.0, -1.0, 1.0);
+        assert_abs_diff_eq!(project(screen, Vec3f::new(0.0, 0.0, 0.0)), Vec3f::new(-1.0, 1.0, 0.0), EPSILON);
+        assert_abs_diff_eq!(project(screen, Vec3f::new(1920.0, 1080.0, 0.0)), Vec3f::new(1.0, -1.0, 0.0), EPSILON);
+    }
+
+    #[test]
+    fn test_mat44_perspective() {
+        let fov = PI / 2.0;
+        let proj = Mat44f::perspective(fov, 2.0, 1.0, 100.0);
+
+        // Near and far planes map to -1 and 1.
+        assert_abs_diff_eq!(project(proj, Vec3f::new(0.0, 0.0, -1.0)), Vec3f::new(0.0, 0.0, -1.0), EPSILON);
+        assert_abs_diff_eq!(project(proj, Vec3f::new(0.0, 0.0, -100.0)), Vec3f::new(0.0, 0.0, 1.0), EPSILON);
+
+        // With a 90 degree fov the top edge of the near plane is 1 unit up, and the side 2 units out.
+        assert_abs_diff_eq!(project(proj, Vec3f::new(2.0, 1.0, -1.0)), Vec3f::new(1.0, 1.0, -1.0), EPSILON);
+        let corner = project(proj, Vec3f::new(-20.0, -10.0, -10.0));
+        assert_abs_diff_eq!(corner.xy(), Vec2f::new(-1.0, -1.0), EPSILON);
+
+        // The same symmetric frustum built from its planes gives the same matrix.
+        let frustum = Mat44f::frustum(-2.0, 2.0, -1.0, 1.0, 1.0, 100.0);
+        assert_abs_diff_eq!(proj, frustum, EPSILON);
+
+        // An off center frustum maps its own edges to the clip edges.
+        let frustum = Mat44f::frustum(0.0, 2.0, -1.0, 3.0, 2.0, 20.0);
+        assert_abs_diff_eq!(project(frustum, Vec3f::new(0.0, -1.0, -2.0)), Vec3f::new(-1.0, -1.0, -1.0), EPSILON);
+        assert_abs_diff_eq!(project(frustum, Vec3f::new(20.0, 30.0, -20.0)), Vec3f::new(1.0, 1.0, 1.0), EPSILON);
+    }
+
+    #[test]
+    fn test_mat44_reversed_z() {
+        let proj = Mat44f::perspective_reversed_z(PI / 2.0, 1.0, 0.5, 50.0);
+
+        // Near maps to 1 and far to 0.
+        assert_abs_diff_eq!(project(proj, Vec3f::new(0.0, 0.0, -0.5)), Vec3f::new(0.0, 0.0, 1.0), EPSILON);
+        assert_abs_diff_eq!(project(proj, Vec3f::new(0.0, 0.0, -50.0)), Vec3f::new(0.0, 0.0, 0.0), EPSILON);
+        assert_abs_diff_eq!(project(proj, Vec3f::new(50.0, -50.0, -50.0)), Vec3f::new(1.0, -1.0, 0.0), EPSILON);
+
+        // Without a far plane depth only approaches the far value.
+        let infinite = Mat44f::infinite_perspective(PI / 2.0, 1.0, 0.5);
+        assert_abs_diff_eq!(project(infinite, Vec3f::new(0.0, 0.0, -0.5)), Vec3f::new(0.0, 0.0, -1.0), EPSILON);
+        let far = project(infinite, Vec3f::new(0.0, 0.0, -100000.0));
+        assert!(far.z < 1.0 && far.z > 0.9999);
+
+        let infinite = Mat44f::infinite_perspective_reversed_z(PI / 2.0, 1.0, 0.5);
+        assert_abs_diff_eq!(project(infinite, Vec3f::new(0.0, 0.0, -0.5)), Vec3f::new(0.0, 0.0, 1.0), EPSILON);
+        let far = project(infinite, Vec3f::new(0.0, 0.0, -100000.0));
+        assert!(far.z > 0.0 && far.z < 0.0001);
+    }
+
+    #[test]
+    fn test_mat44_look_at() {
+        let eye = Vec3f::new(5.0, 3.0, 0.0);
+        let view = Mat44f::look_at(eye, Vec3f::new(5.0, 3.0, -10.0), Vec3f::Y);
+
+        // Looking down -z from a translated eye only translates.
+        assert_eq!(view, Mat44f::IDENTITY.get_translated(-eye));
+
+        // Looking down +x turns the x axis into -z and the z axis into x.
+        let view = Mat44f::look_at(Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(10.0, 0.0, 0.0), Vec3f::Y);
+        assert_abs_diff_eq!(project(view, Vec3f::new(1.0, 0.0, 0.0)), Vec3f::ZERO, EPSILON);
+        assert_abs_diff_eq!(project(view, Vec3f::new(4.0, 0.0, 0.0)), Vec3f::new(0.0, 0.0, -3.0), EPSILON);
+        assert_abs_diff_eq!(project(view, Vec3f::new(1.0, 2.0, 1.0)), Vec3f::new(1.0, 2.0, 0.0), EPSILON);
+
+        // Combined with a projection the target lands in the center of the screen.
+        let proj = Mat44f::perspective(PI / 3.0, 16.0 / 9.0, 0.1, 100.0);
+        let target = Vec3f::new(-3.0, 7.0, 2.0);
+        let centered = project(proj * Mat44f::look_at(Vec3f::new(4.0, 1.0, -6.0), target, Vec3f::Y), target);
+        assert_abs_diff_eq!(Vec3f::new(centered.x, centered.y, 0.0), Vec3f::ZERO, EPSILON);
+        assert!(centered.z > -1.0 && centered.z < 1.0);
+    }
+
+    #[test]
+    fn test_aabb() {
+        // The snake's squares.
+        let tile = Aabb2f::from_center(Vec2f::new(0.5, 0.5), Vec2f::new(0.05, 0.05));
+        assert!(tile.contains_point(Vec2f::new(0.55, 0.45)));
+        assert!(!tile.contains_point(Vec2f::new(0.56, 0.5)));
+        assert!(tile.overlaps(&Aabb2f::from_center(Vec2f::new(0.6, 0.5), Vec2f::new(0.05, 0.05))));
+        assert!(!tile.overlaps(&Aabb2f::from_center(Vec2f::new(0.7, 0.5), Vec2f::new(0.05, 0.05))));
+        assert_eq!(tile.closest_point(Vec2f::new(0.0, 0.5)), Vec2f::new(0.45, 0.5));
+
+        let points = [Vec3f::new(1.0, -2.0, 3.0), Vec3f::new(-1.0, 4.0, 0.0), Vec3f::new(0.0, 0.0, 5.0)];
+        let aabb = Aabb3f::from_points(&points).unwrap();
+        assert_eq!(aabb, Aabb3f::new(Vec3f::new(-1.0, -2.0, 0.0), Vec3f::new(1.0, 4.0, 5.0)));
+        assert_eq!(aabb.center(), Vec3f::new(0.0, 1.0, 2.5));
+        assert_eq!(aabb.size(), Vec3f::new(2.0, 6.0, 5.0));
+        assert_eq!(Aabb3f::from_points(&[]), None);
+
+        let inner = Aabb3f::from_center(Vec3f::ZERO, Vec3f::new(0.5, 0.5, 0.5));
+        let outer = Aabb3f::from_center(Vec3f::ZERO, Vec3f::new(1.0, 1.0, 1.0));
+        assert!(outer.contains(&inner));
+        assert!(!inner.contains(&outer));
+        assert_eq!(inner.merged(&outer), outer);
+        assert_eq!(aabb.merged(&outer), Aabb3f::new(Vec3f::new(-1.0, -2.0, -1.0), Vec3f::new(1.0, 4.0, 5.0)));
+
+        // Boxes which touch on a face overlap.
+        let touching = Aabb3f::from_center(Vec3f::new(2.0, 0.0, 0.0), Vec3f::new(1.0, 1.0, 1.0));
+        assert!(outer.overlaps(&touching));
+        assert!(!inner.overlaps(&touching));
+        assert_eq!(outer.closest_point(Vec3f::new(3.0, 0.5, -2.0)), Vec3f::new(1.0, 0.5, -1.0));
+
+        assert!(outer.overlaps_sphere(&Spheref::new(Vec3f::new(1.5, 1.5, 0.0), 0.75)));
+        assert!(!outer.overlaps_sphere(&Spheref::new(Vec3f::new(1.5, 1.5, 0.0), 0.7)));
+    }
+
+    #[test]
+    fn test_sphere() {
+        let sphere = Spheref::new(Vec3f::new(1.0, 0.0, 0.0), 2.0);
+
+        assert!(sphere.contains_point(Vec3f::new(3.0, 0.0, 0.0)));
+        assert!(!sphere.contains_point(Vec3f::new(3.0, 0.1, 0.0)));
+        assert!(sphere.contains(&Spheref::new(Vec3f::new(2.0, 0.0, 0.0), 1.0)));
+        assert!(!sphere.contains(&Spheref::new(Vec3f::new(2.5, 0.0, 0.0), 1.0)));
+        assert!(sphere.overlaps(&Spheref::new(Vec3f::new(1.0, 5.0, 0.0), 3.0)));
+        assert!(!sphere.overlaps(&Spheref::new(Vec3f::new(1.0, 5.0, 0.0), 2.9)));
+
+        assert_eq!(sphere.closest_point(Vec3f::new(1.0, 0.0, 10.0)), Vec3f::new(1.0, 0.0, 2.0));
+        assert_eq!(sphere.closest_point(Vec3f::new(1.5, 0.0, 0.0)), Vec3f::new(1.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_plane() {
+        // The normal is normalized along with the distance.
+        let plane = Planef::new(Vec3f::new(0.0, 2.0, 0.0), 4.0);
+        assert_eq!(plane, Planef::from_point_normal(Vec3f::new(5.0, 2.0, 5.0), Vec3f::Y));
+        assert_eq!(plane.signed_distance(Vec3f::new(1.0, 5.0, 1.0)), 3.0);
+        assert_eq!(plane.signed_distance(Vec3f::new(1.0, -1.0, 1.0)), -3.0);
+        assert_eq!(plane.closest_point(Vec3f::new(1.0, 5.0, 1.0)), Vec3f::new(1.0, 2.0, 1.0));
+
+        // Counter clockwise points face +z.
+        let plane = Planef::from_points(Vec3f::new(0.0, 0.0, 1.0), Vec3f::new(1.0, 0.0, 1.0), Vec3f::new(0.0, 1.0, 1.0));
+        assert_eq!(plane.normal, Vec3f::Z);
+        assert_eq!(plane.distance, 1.0);
+
+        assert_eq!(plane.classify_sphere(&Spheref::new(Vec3f::new(0.0, 0.0, 3.0), 1.0)), Containment::Inside);
+        assert_eq!(plane.classify_sphere(&Spheref::new(Vec3f::new(0.0, 0.0, 1.5), 1.0)), Containment::Intersecting);
+        assert_eq!(plane.classify_sphere(&Spheref::new(Vec3f::new(0.0, 0.0, -0.5), 1.0)), Containment::Outside);
+
+        let aabb = Aabb3f::from_center(Vec3f::new(5.0, 5.0, 1.5), Vec3f::new(1.0, 1.0, 1.0));
+        assert_eq!(plane.classify_aabb(&aabb), Containment::Intersecting);
+        let aabb = Aabb3f::from_center(Vec3f::new(5.0, 5.0, -0.5), Vec3f::new(1.0, 1.0, 1.0));
+        assert_eq!(plane.classify_aabb(&aabb), Containment::Outside);
+    }
+
+    #[test]
+    fn test_ray() {
+        let ray = Rayf::new(Vec3f::new(0.0, 0.0, 10.0), Vec3f::new(0.0, 0.0, -5.0));
+        assert_eq!(ray.direction, -Vec3f::Z);
+        assert_eq!(ray.at(4.0), Vec3f::new(0.0, 0.0, 6.0));
+        assert_eq!(ray.closest_point(Vec3f::new(3.0, 0.0, 2.0)), Vec3f::new(0.0, 0.0, 2.0));
+        assert_eq!(ray.closest_point(Vec3f::new(3.0, 0.0, 12.0)), ray.origin);
+
+        let floor = Planef::from_point_normal(Vec3f::ZERO, Vec3f::Z);
+        assert_eq!(ray.intersect_plane(&floor), Some(10.0));
+        assert_eq!(Rayf::new(ray.origin, Vec3f::Z).intersect_plane(&floor), None);
+        assert_eq!(Rayf::new(ray.origin, Vec3f::X).intersect_plane(&floor), None);
+
+        let sphere = Spheref::new(Vec3f::new(0.0, 0.0, 2.0), 1.0);
+        assert_eq!(ray.intersect_sphere(&sphere), Some(7.0));
+        assert_eq!(Rayf::new(ray.origin, Vec3f::Z).intersect_sphere(&sphere), None);
+        assert_eq!(Rayf::new(Vec3f::new(0.0, 1.5, 10.0), -Vec3f::Z).intersect_sphere(&sphere), None);
+        // Starting inside hits straight away.
+        assert_eq!(Rayf::new(sphere.center, Vec3f::X).intersect_sphere(&sphere), Some(0.0));
+
+        let aabb = Aabb3f::new(Vec3f::new(-1.0, -1.0, 0.0), Vec3f::new(1.0, 1.0, 2.0));
+        assert_eq!(ray.intersect_aabb(&aabb), Some(8.0));
+        assert_eq!(Rayf::new(Vec3f::new(2.0, 0.0, 10.0), -Vec3f::Z).intersect_aabb(&aabb), None);
+        assert_eq!(Rayf::new(Vec3f::new(0.0, 0.0, 1.0), Vec3f::X).intersect_aabb(&aabb), Some(0.0));
+
+        let diagonal = Rayf::new(Vec3f::new(-3.0, -3.0, 1.0), Vec3f::new(1.0, 1.0, 0.0));
+        let distance = diagonal.intersect_aabb(&aabb).unwrap();
+        assert_abs_diff_eq!(distance, 8.0f32.sqrt(), EPSILON);
+        assert_eq!(Rayf::new(Vec3f::new(-3.0, -1.0, 1.0), Vec3f::new(1.0, -1.0, 0.0)).intersect_aabb(&aabb), None);
+    }
+
+    #[test]
+    fn test_segment() {
+        let segment = Segmentf::new(Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(4.0, 0.0, 0.0));
+        assert_eq!(segment.length(), 4.0);
+        assert_eq!(segment.at(0.25), Vec3f::new(1.0, 0.0, 0.0));
+        assert_eq!(segment.closest_point(Vec3f::new(2.0, 3.0, 0.0)), Vec3f::new(2.0, 0.0, 0.0));
+        assert_eq!(segment.closest_point(Vec3f::new(-2.0, 3.0, 0.0)), segment.start);
+        assert_eq!(segment.closest_point(Vec3f::new(9.0, 3.0, 0.0)), segment.end);
+
+        let crossing = Segmentf::new(Vec3f::new(1.0, -1.0, 2.0), Vec3f::new(1.0, 1.0, 2.0));
+        assert_eq!(segment.closest_points(&crossing), (Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 2.0)));
+        let parallel = Segmentf::new(Vec3f::new(5.0, 1.0, 0.0), Vec3f::new(8.0, 1.0, 0.0));
+        assert_eq!(segment.closest_points(&parallel), (segment.end, parallel.start));
+        let point = Segmentf::new(Vec3f::new(2.0, 2.0, 0.0), Vec3f::new(2.0, 2.0, 0.0));
+        assert_eq!(segment.closest_points(&point), (Vec3f::new(2.0, 0.0, 0.0), point.start));
+
+        let wall = Planef::from_point_normal(Vec3f::new(3.0, 0.0, 0.0), Vec3f::X);
+        assert_eq!(segment.intersect_plane(&wall), Some(Vec3f::new(3.0, 0.0, 0.0)));
+        let far_wall = Planef::from_point_normal(Vec3f::new(5.0, 0.0, 0.0), Vec3f::X);
+        assert_eq!(segment.intersect_plane(&far_wall), None);
+
+        assert!(segment.intersects_sphere(&Spheref::new(Vec3f::new(2.0, 1.0, 0.0), 1.0)));
+        assert!(!segment.intersects_sphere(&Spheref::new(Vec3f::new(6.0, 0.0, 0.0), 1.0)));
+
+        let aabb = Aabb3f::from_center(Vec3f::new(3.0, 0.0, 0.0), Vec3f::new(0.5, 0.5, 0.5));
+        assert_eq!(segment.intersect_aabb(&aabb), Some(Vec3f::new(2.5, 0.0, 0.0)));
+        let aabb = Aabb3f::from_center(Vec3f::new(5.0, 0.0, 0.0), Vec3f::new(0.5, 0.5, 0.5));
+        assert_eq!(segment.intersect_aabb(&aabb), None);
+    }
+
+    #[test]
+    fn test_obb() {
+        // A box rotated 45 degrees around z, so its corners point along the x and y axes.
+        let rotation = Quatf::from_axis_angle(Vec3f::Z, PI / 4.0);
+        let obb = Obbf::from_rotation(Vec3f::ZERO, Vec3f::new(1.0, 1.0, 1.0), rotation);
+        let corner = 2.0f32.sqrt();
+
+        assert!(obb.contains_point(Vec3f::new(corner - 0.01, 0.0, 0.0)));
+        assert!(!obb.contains_point(Vec3f::new(1.0, 1.0, 0.0)));
+        assert_abs_diff_eq!(obb.closest_point(Vec3f::new(5.0, 0.0, 0.0)), Vec3f::new(corner, 0.0, 0.0), EPSILON);
+        assert_abs_diff_eq!(obb.from_local(obb.to_local(Vec3f::new(1.0, 2.0, 3.0))), Vec3f::new(1.0, 2.0, 3.0), EPSILON);
+
+        let bounds = obb.to_aabb();
+        assert_abs_diff_eq!(bounds.max, Vec3f::new(corner, corner, 1.0), EPSILON);
+        assert_abs_diff_eq!(bounds.min, -bounds.max, EPSILON);
+
+        assert!(obb.overlaps_sphere(&Spheref::new(Vec3f::new(1.9, 0.0, 0.0), 0.5)));
+        assert!(!obb.overlaps_sphere(&Spheref::new(Vec3f::new(1.0, 1.0, 0.0), 0.35)));
+
+        // The aabbs of these boxes overlap, but the boxes themselves only do when moved closer.
+        let unrotated = Obbf::from_aabb(&Aabb3f::from_center(Vec3f::new(2.3, 2.3, 0.0), Vec3f::new(1.0, 1.0, 1.0)));
+        assert!(bounds.overlaps(&unrotated.to_aabb()));
+        assert!(!obb.overlaps(&unrotated));
+        assert!(!unrotated.overlaps(&obb));
+
+        let closer = Obbf::from_aabb(&Aabb3f::from_center(Vec3f::new(1.6, 1.6, 0.0), Vec3f::new(1.0, 1.0, 1.0)));
+        assert!(obb.overlaps(&closer));
+        assert!(obb.overlaps(&obb));
+
+        // Two boxes standing on edges which cross each other, only the cross product of the edges separates them.
+        let ridge = Obbf::from_rotation(Vec3f::ZERO, Vec3f::new(1.0, 1.0, 1.0), Quatf::from_axis_angle(Vec3f::X, PI / 4.0));
+        let above = Obbf::from_rotation(Vec3f::new(0.0, 0.0, 2.93), Vec3f::new(1.0, 1.0, 1.0), Quatf::from_axis_angle(Vec3f::Y, PI / 4.0));
+        assert!(!ridge.overlaps(&above));
+        assert!(ridge.overlaps(&Obbf { center: Vec3f::new(0.0, 0.0, 2.7), ..above }));
+
+        let ray = Rayf::new(Vec3f::new(-5.0, 0.0, 0.0), Vec3f::X);
+        let distance = ray.intersect_obb(&obb).unwrap();
+        assert_abs_diff_eq!(distance, 5.0 - corner, EPSILON);
+        assert_eq!(Rayf::new(Vec3f::new(-5.0, 1.5, 0.0), Vec3f::X).intersect_obb(&obb), None);
+
+        let segment = Segmentf::new(Vec3f::new(-5.0, 0.0, 0.0), Vec3f::new(-2.0, 0.0, 0.0));
+        assert_eq!(segment.intersect_obb(&obb), None);
+    }
+
+    #[test]
+    fn test_frustum() {
+        let proj = Mat44f::perspective(PI / 2.0, 1.0, 1.0, 100.0);
+        let view = Mat44f::look_at(Vec3f::new(0.0, 0.0, 10.0), Vec3f::ZERO, Vec3f::Y);
+        let frustum = Frustumf::from_matrix(&(proj * view));
+
+        // The camera looks down -z from z = 10, so the near plane is at 9 and the far plane at -90.
+        assert_abs_diff_eq!(frustum.planes[Frustumf::NEAR].normal, -Vec3f::Z, EPSILON);
+        assert!((frustum.planes[Frustumf::NEAR].signed_distance(Vec3f::ZERO) - 9.0).abs() < 0.001);
+        assert!((frustum.planes[Frustumf::FAR].signed_distance(Vec3f::ZERO) - 90.0).abs() < 0.001);
+
+        assert!(frustum.contains_point(Vec3f::ZERO));
+        assert!(frustum.contains_point(Vec3f::new(9.9, 0.0, 0.0)));
+        assert!(!frustum.contains_point(Vec3f::new(10.1, 0.0, 0.0)));
+        assert!(!frustum.contains_point(Vec3f::new(0.0, 0.0, 9.5)));
+        assert!(!frustum.contains_point(Vec3f::new(0.0, 0.0, -91.0)));
+
+        let inside = Spheref::new(Vec3f::ZERO, 1.0);
+        let crossing = Spheref::new(Vec3f::new(10.0, 0.0, 0.0), 1.0);
+        let behind = Spheref::new(Vec3f::new(0.0, 0.0, 12.0), 1.0);
+        assert_eq!(frustum.classify_sphere(&inside), Containment::Inside);
+        assert_eq!(frustum.classify_sphere(&crossing), Containment::Intersecting);
+        assert_eq!(frustum.classify_sphere(&behind), Containment::Outside);
+        assert!(frustum.intersects_sphere(&crossing));
+        assert!(!frustum.intersects_sphere(&behind));
+
+        let aabb = Aabb3f::from_center(Vec3f::new(0.0, -30.0, -10.0), Vec3f::new(1.0, 1.0, 1.0));
+        assert_eq!(frustum.classify_aabb(&aabb), Containment::Outside);
+        let aabb = Aabb3f::from_center(Vec3f::new(0.0, -20.0, -10.0), Vec3f::new(1.0, 1.0, 1.0));
+        assert_eq!(frustum.classify_aabb(&aabb), Containment::Intersecting);
+        let aabb = Aabb3f::from_center(Vec3f::new(0.0, -10.0, -10.0), Vec3f::new(1.0, 1.0, 1.0));
+        assert_eq!(frustum.classify_aabb(&aabb), Containment::Inside);
+        assert!(frustum.intersects_obb(&Obbf::from_aabb(&aabb)));
+
+        // Reversed-z and infinite projections give the same side planes.
+        let reversed = Frustumf::from_matrix_reversed_z(&(Mat44f::perspective_reversed_z(PI / 2.0, 1.0, 1.0, 100.0) * view));
+        for (a, b) in frustum.planes.iter().zip(reversed.planes.iter()) {
+            assert_abs_diff_eq!(a.normal, b.normal, EPSILON);
+            assert!((a.distance - b.distance).abs() < 0.001);
+        }
+
+        let infinite = Frustumf::from_matrix(&(Mat44f::infinite_perspective(PI / 2.0, 1.0, 1.0) * view));
+        assert!(infinite.contains_point(Vec3f::new(0.0, 0.0, -100000.0)));
+        assert!(!infinite.contains_point(Vec3f::new(0.0, 0.0, 9.5)));
+
+        let infinite = Frustumf::from_matrix_reversed_z(&(Mat44f::infinite_perspective_reversed_z(PI / 2.0, 1.0, 1.0) * view));
+        assert!(infinite.contains_point(Vec3f::new(0.0, 0.0, -100000.0)));
+        assert!(!infinite.contains_point(Vec3f::new(0.0, 0.0, 9.5)));
+    }
+
+    fn assert_transform_near(a: Transformf, b: Transformf) {
+        // q and -q are the same rotation.
+        let b = if a.rotation.abs_diff_eq(&-b.rotation, EPSILON) { Transformf { rotation: -b.rotation, ..b } } else { b };
+        assert_abs_diff_eq!(a, b, EPSILON);
+    }
+
+    #[test]
+    fn test_transform() {
+        let transform = Transformf::new(
+            Vec3f::new(1.0, 2.0, 3.0),
+            Quatf::from_axis_angle(Vec3f::Z, PI / 2.0),
+            Vec3f::new(2.0, 2.0, 2.0));
+
+        assert_eq!(Transformf::default(), Transformf::IDENTITY);
+        assert_eq!(Transformf::IDENTITY.transform_point(Vec3f::new(1.0, 2.0, 3.0)), Vec3f::new(1.0, 2.0, 3.0));
+
+        // Points are scaled, rotated and then translated, vectors aren't translated.
+        let rotated_x = transform.rotation * Vec3f::X;
+        assert_abs_diff_eq!(transform.transform_point(Vec3f::X), transform.translation + rotated_x * 2.0, EPSILON);
+        assert_abs_diff_eq!(transform.transform_vector(Vec3f::X), rotated_x * 2.0, EPSILON);
+
+        let point = Vec3f::new(-4.0, 0.5, 7.0);
+        assert_abs_diff_eq!(transform.inverse_transform_point(transform.transform_point(point)), point, EPSILON);
+        assert_abs_diff_eq!(transform.inverse_transform_vector(transform.transform_vector(point)), point, EPSILON);
+        assert_abs_diff_eq!(transform.inverse().transform_point(transform.transform_point(point)), point, EPSILON);
+        assert_transform_near(transform * transform.inverse(), Transformf::IDENTITY);
+        assert_transform_near(transform.inverse() * transform, Transformf::IDENTITY);
+
+        // The matrix transforms points the same way.
+        let matrix = transform.to_matrix();
+        let projected = matrix * Vec4f::new(point.x, point.y, point.z, 1.0);
+        assert_abs_diff_eq!(Vec3f::new(projected.x, projected.y, projected.z), transform.transform_point(point), EPSILON);
+        assert_eq!(Transformf::from_translation(Vec3f::new(1.0, 2.0, 3.0)).to_matrix(),
+            Mat44f::IDENTITY.get_translated(Vec3f::new(1.0, 2.0, 3.0)));
+        assert_eq!(Transformf::from_scale(Vec3f::new(1.0, 2.0, 3.0)).to_matrix(),
+            Mat44f::IDENTITY.get_scaled(Vec3f::new(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn test_transform_hierarchy() {
+        let parent = Transformf::new(
+            Vec3f::new(10.0, 0.0, -5.0),
+            Quatf::from_axis_angle(Vec3f::Y, 0.7),
+            Vec3f::new(3.0, 3.0, 3.0));
+        let child = Transformf::new(
+            Vec3f::new(0.0, 1.0, 2.0),
+            Quatf::from_axis_angle(Vec3f::new(1.0, 1.0, 0.0).get_normalized(), -1.2),
+            Vec3f::new(1.0, 0.5, 2.0));
+
+        // Composing is the same as transforming by the child and then the parent, or multiplying the matrices.
+        let world = parent * child;
+        let point = Vec3f::new(0.3, -2.0, 1.5);
+        assert_abs_diff_eq!(world.transform_point(point), parent.transform_point(child.transform_point(point)), EPSILON);
+
+        let matrix = parent.to_matrix() * child.to_matrix();
+        for (a, b) in matrix.data.iter().flatten().zip(world.to_matrix().data.iter().flatten()) {
+            assert!((a - b).abs() < 0.0001);
+        }
+
+        // Going from world space back to the parent's space gives the child.
+        assert_transform_near(world.relative_to(&parent), child);
+        assert_transform_near(child.relative_to(&Transformf::IDENTITY), child);
+    }
+
+    #[test]
+    fn test_transform_decompose() {
+        let transform = Transformf::new(
+            Vec3f::new(-3.0, 8.0, 0.5),
+            Quatf::from_axis_angle(Vec3f::new(1.0, -2.0, 0.5).get_normalized(), 2.5),
+            Vec3f::new(1.0, 4.0, 0.25));
+
+        assert_transform_near(Transformf::from_matrix(&transform.to_matrix()).unwrap(), transform);
+        assert_transform_near(Transformf::from_matrix(&Mat44f::IDENTITY).unwrap(), Transformf::IDENTITY);
+
+        // Mirroring ends up in the x scale.
+        let mirrored = Transformf::from_scale(Vec3f::new(1.0, 1.0, -1.0)).to_matrix();
+        let decomposed = Transformf::from_matrix(&mirrored).unwrap();
+        assert_eq!(decomposed.scale, Vec3f::new(-1.0, 1.0, 1.0));
+        let projected = mirrored * Vec4f::new(1.0, 2.0, 3.0, 1.0);
+        assert_abs_diff_eq!(decomposed.transform_point(Vec3f::new(1.0, 2.0, 3.0)), Vec3f::new(projected.x, projected.y, projected.z), EPSILON);
+
+        let flat = Transformf::from_scale(Vec3f::new(1.0, 0.0, 1.0)).to_matrix();
+        assert_eq!(Transformf::from_matrix(&flat), None);
+
+        // Interpolating blends every part.
+        let a = Transformf::from_translation(Vec3f::new(0.0, 0.0, 0.0));
+        let b = Transformf::new(Vec3f::new(10.0, 0.0, 0.0), Quatf::from_axis_angle(Vec3f::Z, PI / 2.0), Vec3f::new(3.0, 3.0, 3.0));
+        let halfway = Transformf::interpolate(&a, &b, 0.5);
+        assert_transform_near(halfway, Transformf::new(
+            Vec3f::new(5.0, 0.0, 0.0), Quatf::from_axis_angle(Vec3f::Z, PI / 4.0), Vec3f::new(2.0, 2.0, 2.0)));
+        assert_transform_near(Transformf::interpolate(&a, &b, 0.0), a);
+        assert_transform_near(Transformf::interpolate(&a, &b, 1.0), b);
+    }
+
+    #[test]
+    fn test_integer_vectors() {
+        // Snake tiles on a grid.
+        let head = Vec2i::new(3, -2);
+        let direction = -Vec2i::Y;
+        assert_eq!(head + direction * 2, Vec2i::new(3, -4));
+        assert_eq!(head - Vec2i::X, Vec2i::new(2, -2));
+        assert_eq!(head * Vec2i::new(2, 1), 4);
+        assert_eq!(Vec2i::new(7, -7) / 2, Vec2i::new(3, -3));
+
+        let mut tile = Vec3i::new(1, 2, 3);
+        tile += Vec3i::Z;
+        tile *= 3;
+        assert_eq!(tile, Vec3i::new(3, 6, 12));
+        assert_eq!(Vec3i::X % Vec3i::Y, Vec3i::Z);
+        assert_eq!(Vec4u::W + Vec4u::new(1, 2, 3, 4), Vec4u::new(1, 2, 3, 5));
+        assert_eq!(Vec2u::ZERO, Vec2u::default());
+
+        // Component-wise helpers.
+        let a = Vec3i::new(-5, 2, 9);
+        let b = Vec3i::new(1, -3, 9);
+        assert_eq!(a.min(b), Vec3i::new(-5, -3, 9));
+        assert_eq!(a.max(b), Vec3i::new(1, 2, 9));
+        assert_eq!(a.abs(), Vec3i::new(5, 2, 9));
+        assert_eq!(a.clamp(Vec3i::new(-1, -1, -1), Vec3i::new(1, 1, 1)), Vec3i::new(-1, 1, 1));
+        assert_eq!(Vec4f::new(-1.5, 0.0, 2.0, -0.0).abs(), Vec4f::new(1.5, 0.0, 2.0, 0.0));
+        assert_eq!(Vec2f::new(4.0, -4.0).clamp(Vec2f::ZERO, Vec2f::new(1.0, 1.0)), Vec2f::X);
+
+        // Lossless casts to floats.
+        assert_eq!(Vec2f::from(Vec2::<i16>::new(-300, 12)), Vec2f::new(-300.0, 12.0));
+        assert_eq!(Vec3::<f64>::from(Vec3i::new(i32::MAX, i32::MIN, 0)), Vec3::<f64>::new(2147483647.0, -2147483648.0, 0.0));
+        assert_eq!(Vec4::<f64>::from(Vec4u::new(u32::MAX, 0, 1, 2)), Vec4::<f64>::new(4294967295.0, 0.0, 1.0, 2.0));
+        let widened: Vec3::<f64> = Vec3f::new(0.1, 0.2, 0.3).into();
+        assert_eq!(widened.x, 0.1f32 as f64);
+    }
+
+    #[test]
+    fn test_vector_masks() {
+        let a = Vec4i::new(1, 5, -2, 0);
+        let b = Vec4i::new(1, 3, 4, -1);
+
+        assert_eq!(a.cmp_eq(b), Vec4b::new(true, false, false, false));
+        assert_eq!(a.cmp_ne(b), !a.cmp_eq(b));
+        assert_eq!(a.cmp_lt(b), Vec4b::new(false, false, true, false));
+        assert_eq!(a.cmp_le(b), Vec4b::new(true, false, true, false));
+        assert_eq!(a.cmp_gt(b), Vec4b::new(false, true, false, true));
+        assert_eq!(a.cmp_ge(b), a.cmp_gt(b) | a.cmp_eq(b));
+        assert_eq!(a.cmp_ge(b) & a.cmp_le(b), a.cmp_eq(b));
+
+        assert!(a.cmp_ne(b).any());
+        assert!(!a.cmp_ne(b).all());
+        assert!(Vec2b::new(true, true).all());
+        assert!(!Vec3b::default().any());
+
+        // Selecting with a mask, ex: the component-wise max.
+        assert_eq!(a.cmp_gt(b).select(a, b), a.max(b));
+
+        // Exact comparisons make sense for grid positions, unlike floats.
+        let bounds = Vec2i::new(10, 10);
+        assert!(Vec2i::new(3, 9).cmp_lt(bounds).all());
+        assert!(!Vec2i::new(3, 10).cmp_lt(bounds).all());
+    }
+
+    #[test]
+    fn test_generic_core() {
+        // The sized types are the same as the generic ones.
+        let v = Vec3f::new(1.0, -2.0, 0.5);
+        assert_eq!(Vector::from(v), Vector::new([1.0, -2.0, 0.5]));
+        assert_eq!(Vec3f::from(Vector::from(v) * 2.0), v * 2.0);
+        assert_eq!(Vector::from(v) * Vector::from(v), v * v);
+        assert_eq!(Vector::from(v).length(), v.length());
+        assert_eq!(format!("{}", v), "[1, -2, 0.5]");
+
+        let a = Mat22f::from_axes(Vec2f::new(1.0, 2.0), Vec2f::new(3.0, 4.0));
+        assert_eq!(format!("{}", a), "[1, 3]\n[2, 4]");
+        assert_eq!(format!("{}", Mat44f::IDENTITY).lines().count(), 4);
+        assert_eq!(a.row(0), Vector::new([1.0, 3.0]));
+        assert_eq!(a.column(1), Vector::new([3.0, 4.0]));
+
+        // Every element takes part in the element-wise operators.
+        let mut sum = a;
+        sum += a;
+        assert_eq!(sum, a * Mat22f::from_axes(Vec2f::new(2.0, 0.0), Vec2f::new(0.0, 2.0)));
+        assert_eq!(sum - a, a);
+        assert_eq!(-a + a, Mat22f::ZERO);
+
+        let mut b = Mat44f::IDENTITY.get_translated(Vec3f::new(1.0, 2.0, 3.0));
+        b.data[0][3] = 5.0;
+        assert_eq!((b + b).data[0][3], 10.0);
+        assert_eq!((b - b), Mat44f::ZERO);
+
+        // An affine 3x4 matrix transforms points the same as the full 4x4 one.
+        let transform = Transformf::new(Vec3f::new(4.0, 5.0, 6.0), Quatf::from_axis_angle(Vec3f::Y, 0.5), Vec3f::new(2.0, 2.0, 2.0));
+        let full = transform.to_matrix();
+        let affine = Mat34f { data: full.data.map(|column| [column[0], column[1], column[2]]) };
+        let point = Vec4f::new(1.0, -1.0, 2.0, 1.0);
+
+        assert_eq!(Vec3f::from(affine * Vector::from(point)), (full * point).xyz());
+        assert_eq!((affine * full).data.map(|column| column[2]), (full * full).data.map(|column| column[2]));
+        assert_eq!(affine.get_transposed().get_transposed(), affine);
+        assert_eq!(affine.get_transposed().row(3), Vector::new([4.0, 5.0, 6.0]));
+
+        // Sizes without a named type work the same way.
+        let identity = Matrix::<i32, 5, 5>::IDENTITY;
+        let column = Vector::new([1, 2, 3, 4, 5]);
+        assert_eq!(identity * column, column);
+        assert_eq!(Matrix::<i32, 1, 5>::from_columns(column.data.map(|value| Vector::new([value]))) * column, Vector::new([55]));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let one_ulp_up = f32::from_bits(1.0f32.to_bits() + 1);
+
+        assert_eq!(1.0f32.ulps_between(one_ulp_up), Some(1));
+        assert_eq!((-1.0f32).ulps_between(-one_ulp_up), Some(1));
+        assert_eq!(0.0f32.ulps_between(-0.0), Some(0));
+        assert_eq!(f32::MIN_POSITIVE.ulps_between(-f32::MIN_POSITIVE), None);
+        assert_eq!(f32::NAN.ulps_between(f32::NAN), None);
+
+        // Each mode, on the values it suits.
+        assert!(1.0f32.ulps_eq(&one_ulp_up, 1));
+        assert!(!1.0f32.ulps_eq(&one_ulp_up, 0));
+        assert!(1e6f32.relative_eq(&1.00001e6, 1e-5));
+        assert!(!1e6f32.abs_diff_eq(&1.00001e6, 1e-5));
+        assert!(1e-7f32.abs_diff_eq(&-1e-7, 1e-6));
+        assert!(!1e-7f32.relative_eq(&-1e-7, 1e-6));
+        assert!(0.0f32.relative_eq(&-0.0, 0.0));
+
+        // Infinities only equal themselves and NaN is never equal.
+        assert!(f32::INFINITY.ulps_eq(&f32::INFINITY, 0));
+        assert!(!f32::INFINITY.relative_eq(&f32::MAX, 1.0));
+        assert!(!f32::NAN.abs_diff_eq(&f32::NAN, f32::INFINITY));
+
+        // Composite types compare every component.
+        let q = Quatf::from_axis_angle(Vec3f::new(1.0, 2.0, 3.0).get_normalized(), 0.7);
+        assert_approx_eq!(q * q.get_conjugated(), Quatf::IDENTITY, Tolerance::Absolute(EPSILON));
+        assert!(!Vec4f::new(1.0, 2.0, 3.0, 4.0).abs_diff_eq(&Vec4f::new(1.0, 2.0, 3.0, 4.1), 0.01));
+        assert_relative_eq!(Mat44f::IDENTITY.get_scaled(Vec3f::new(1e4, 1e4, 1e4)).get_inverted(),
+            Mat44f::IDENTITY.get_scaled(Vec3f::new(1e-4, 1e-4, 1e-4)), 1e-6);
+        assert_ulps_eq!(Aabb3f::new(Vec3f::ZERO, Vec3f::X), Aabb3f::new(Vec3f::ZERO, Vec3f::X), 0);
+        assert_abs_diff_eq!(Vector::new([0.1f64; 5]) * 3.0, Vector::new([0.3; 5]), 1e-15);
+
+        // The failure lists only the components which differ, in memory order.
+        let message = approx_eq_failure(&Vec3f::new(1.0, 2.0, 3.0), &Vec3f::new(1.0, 2.5, 3.0), Tolerance::Absolute(0.1)).unwrap();
+        assert_eq!(message, "assertion `left ≈ right` failed with Absolute(0.1)\n  \
+            left: Vec3 { x: 1.0, y: 2.0, z: 3.0 }\n \
+            right: Vec3 { x: 1.0, y: 2.5, z: 3.0 }\n  \
+            component 1: 2.0 != 2.5 (2097152 ulps apart)");
+        assert_eq!(approx_eq_failure(&Mat22f::IDENTITY, &Mat22f::IDENTITY, Tolerance::Ulps(0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "component 13: 2.0 != 2.1")]
+    fn test_approx_eq_panics() {
+        let translation = Mat44f::IDENTITY.get_translated(Vec3f::new(1.0, 2.0, 3.0));
+        assert_approx_eq!(translation, Mat44f::IDENTITY.get_translated(Vec3f::new(1.0, 2.1, 3.0)));
+    }
+
+    /// Transforms with every kind of part, along with a projection.
+    fn simd_test_matrices() -> Vec<Mat44f> {
+        let mut matrices = vec![
+            Mat44f::IDENTITY,
+            Mat44f::perspective(PI / 3.0, 16.0 / 9.0, 0.1, 1000.0),
+            Mat44f::orthographic(-8.0, 8.0, -4.5, 4.5, 0.0, 50.0),
+            Mat44f::look_at(Vec3f::new(3.0, 4.0, -7.0), Vec3f::ZERO, Vec3f::Y)
+        ];
+
+        for i in 0..20 {
+            let f = i as f32;
+            let transform = Transformf::new(
+                Vec3f::new(f * 13.7 - 100.0, f * -3.1, 250.0 - f * f),
+                Quatf::from_axis_angle(Vec3f::new(f.sin(), 1.0, f.cos()).get_normalized(), f * 0.7),
+                Vec3f::new(0.01 + f * 0.5, 1.0 + f * 20.0, 2.0));
+            matrices.push(transform.to_matrix());
+        }
+
+        matrices
+    }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        let matrices = simd_test_matrices();
+        let vectors = [Vec4f::new(1.0, -2.0, 3.5, 1.0), Vec4f::new(1e-3, 4e4, -7.25, 0.0), Vec4f::W];
+
+        for a in &matrices {
+            assert_eq!(simd::mat44_transpose(a), a.get_transposed());
+
+            for b in &matrices {
+                assert_eq!(simd::mat44_mul(a, b), *a * *b);
+            }
+
+            for v in vectors {
+                assert_eq!(simd::mat44_mul_vec4(a, v), *a * v);
+            }
+
+            let mut transformed = vectors;
+            simd::transform_vec4s(a, &mut transformed);
+            assert_eq!(transformed.to_vec(), vectors.iter().map(|v| *a * *v).collect::<Vec<_>>());
+
+            // Within the documented tolerance of the scalar inverse, and actually an inverse.
+            let inverse = simd::mat44_inverse(a);
+            let expected = a.get_inverted();
+            let largest = |mat: &Mat44f| mat.data.iter().flatten().fold(0.0f32, |largest, value| largest.max(value.abs()));
+
+            assert_abs_diff_eq!(inverse, expected, 1e-5 * largest(&expected));
+            assert_abs_diff_eq!(*a * inverse, Mat44f::IDENTITY, 1e-5 * largest(a) * largest(&inverse));
+        }
+
+        // Singular matrices are returned unchanged, like the scalar inverse.
+        let flat = Mat44f::IDENTITY.get_scaled(Vec3f::new(1.0, 0.0, 1.0));
+        assert_eq!(simd::mat44_inverse(&flat), flat);
+        assert_eq!(flat.get_inverted(), flat);
+
+        let quats = [
+            Quatf::IDENTITY,
+            Quatf::from_axis_angle(Vec3f::X, 0.3),
+            Quatf::from_axis_angle(Vec3f::new(1.0, -2.0, 0.5).get_normalized(), 2.5),
+            Quatf::new(10.0, 13.0, 2.0, 1.0)
+        ];
+
+        for a in quats {
+            for b in quats {
+                let simd = simd::quat_mul(a, b);
+                let scalar = a * b;
+                assert_abs_diff_eq!(simd, scalar, 4.0 * f32::EPSILON * a.length() * b.length());
+            }
+        }
+    }
+
+    /// Compares the derivatives of a curve to finite differences of the curve.
+    fn assert_derivatives<C: Curve<f64, Point = Vec3<f64>>>(curve: &C) {
+        let h = 1e-5;
+
+        // Halfway between the samples, the second derivative of a spline can jump where its segments join.
+        for i in 0..20 {
+            let t = (i as f64 + 0.5) / 20.0;
+            let derivative = (curve.at(t + h) - curve.at(t - h)) / (2.0 * h);
+            let second_derivative = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+
+            assert_abs_diff_eq!(curve.derivative(t), derivative, 1e-4 * derivative.length().max(1.0));
+            assert_abs_diff_eq!(curve.second_derivative(t), second_derivative, 1e-4 * second_derivative.length().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_curves() {
+        let line = LinearBezier::new(Vec3f::ZERO, Vec3f::new(4.0, 0.0, 0.0));
+        assert_eq!(line.at(0.25), Vec3f::new(1.0, 0.0, 0.0));
+        assert_eq!(line.at(2.0), line.end);
+        assert_approx_eq!(line.length(), 4.0);
+
+        // The usual approximation of a quarter of a unit circle.
+        let k = 0.5522847498;
+        let arc = CubicBezier::new(Vec2f::X, Vec2f::new(1.0, k), Vec2f::new(k, 1.0), Vec2f::Y);
+        assert_eq!(arc.at(0.0), Vec2f::X);
+        assert_eq!(arc.at(1.0), Vec2f::Y);
+        assert_abs_diff_eq!(arc.at(0.5).length(), 1.0, 3e-4);
+        assert_relative_eq!(arc.length(), PI / 2.0, 1e-3);
+        assert_abs_diff_eq!(arc.tangent(0.0), Vec2f::Y, EPSILON);
+        assert_abs_diff_eq!(arc.closest_point(Vec2f::new(2.0, 2.0)), Vec2f::new(0.5f32.sqrt(), 0.5f32.sqrt()), 1e-3);
+        assert_eq!(arc.closest_point(Vec2f::new(-1.0, 3.0)), Vec2f::Y);
+
+        let quadratic = QuadraticBezier::new(Vector::new([0.0f32; 4]), Vector::new([1.0, 2.0, 0.0, 1.0]), Vector::new([2.0, 0.0, 0.0, 2.0]));
+        assert_approx_eq!(quadratic.at(0.5), Vector::new([1.0, 1.0, 0.0, 1.0]));
+
+        let points = [
+            Vec3::<f64>::new(0.0, 0.0, 0.0),
+            Vec3::<f64>::new(1.0, 2.0, 0.0),
+            Vec3::<f64>::new(3.0, 2.0, 1.0),
+            Vec3::<f64>::new(4.0, 0.0, -1.0),
+            Vec3::<f64>::new(6.0, 1.0, 0.0)];
+
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        let hermite = Hermite::new(points[0], (points[1] - points[0]) * 3.0, points[3], (points[3] - points[2]) * 3.0);
+        let catmull_rom = CatmullRom::through(&points).unwrap();
+        let b_spline = BSpline::clamped(&points).unwrap();
+
+        assert_derivatives(&LinearBezier::new(points[0], points[1]));
+        assert_derivatives(&QuadraticBezier::new(points[0], points[1], points[2]));
+        assert_derivatives(&cubic);
+        assert_derivatives(&hermite);
+        assert_derivatives(&catmull_rom);
+        assert_derivatives(&b_spline);
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_abs_diff_eq!(hermite.at(t), cubic.at(t), DELTA);
+        }
+
+        // A Catmull-Rom spline passes through its points, a B-spline only through the ends when clamped.
+        assert_eq!(catmull_rom.segment_count(), 4);
+
+        for (i, point) in points.iter().enumerate() {
+            assert_abs_diff_eq!(catmull_rom.at(i as f64 / 4.0), *point, DELTA);
+        }
+
+        assert_abs_diff_eq!(b_spline.at(0.0), points[0], DELTA);
+        assert_abs_diff_eq!(b_spline.at(1.0), points[4], DELTA);
+        assert_abs_diff_eq!(b_spline.tangent(0.0), (points[1] - points[0]).get_normalized(), DELTA);
+        assert!(BSpline::new(points[..3].to_vec()).is_none());
+        assert!(CatmullRom::new(points.to_vec()).is_some());
+        assert!(CatmullRom::through(&points[..1]).is_none());
+
+        // Moving by distance, the t of each distance should measure back to that distance.
+        for curve in [&cubic as &dyn Curve<f64, Point = Vec3<f64>>, &catmull_rom, &b_spline] {
+            let table = curve.arc_length();
+            assert_relative_eq!(table.length(), curve.length(), 1e-6);
+
+            for i in 0..=8 {
+                let distance = table.length() * i as f64 / 8.0;
+                let t = table.parameter_at(distance);
+
+                assert_abs_diff_eq!(curve.length_between(0.0, t), distance, 1e-3 * table.length() / curve.segment_count() as f64);
+                assert_abs_diff_eq!(table.distance_at(t), distance, 1e-9);
+            }
+
+            assert_eq!(table.parameter_at(-1.0), 0.0);
+            assert_eq!(table.parameter_at_fraction(2.0), 1.0);
+        }
+
+        // Closest points are on the curve, where the offset is perpendicular to it.
+        for target in [Vec3::<f64>::new(2.0, 3.0, 0.0), Vec3::<f64>::new(5.0, -1.0, 1.0), Vec3::<f64>::new(1.0, 1.0, 1.0)] {
+            let t = catmull_rom.closest_parameter(target);
+            assert_abs_diff_eq!((catmull_rom.at(t) - target) * catmull_rom.derivative(t), 0.0, 1e-6);
+
+            for i in 0..=100 {
+                assert!((catmull_rom.at(i as f64 / 100.0) - target).length() >= (catmull_rom.at(t) - target).length() - 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_squad() {
+        let q = Quat::<f64>::from_axis_angle(Vec3::<f64>::new(1.0, -2.0, 0.5).get_normalized(), 1.2);
+        assert_abs_diff_eq!(q.log().exp(), q, DELTA);
+        assert_abs_diff_eq!(Quat::<f64>::IDENTITY.log(), Quat::<f64>::ZERO, DELTA);
+
+        let keys = [
+            Quat::<f64>::IDENTITY,
+            Quat::<f64>::from_axis_angle(Vec3::<f64>::Z, 1.0),
+            -Quat::<f64>::from_axis_angle(Vec3::<f64>::new(0.0, 1.0, 1.0).get_normalized(), 2.0),
+            Quat::<f64>::from_axis_angle(Vec3::<f64>::X, 0.5)];
+
+        let squad = Squad::new(&keys).unwrap();
+        assert!(Squad::new(&keys[..1]).is_none());
+
+        // Passes through every key, the negated key is flipped back so the rotation is the same.
+        assert_abs_diff_eq!(squad.at(0.0), keys[0], DELTA);
+        assert_abs_diff_eq!(squad.at(1.0 / 3.0), keys[1], DELTA);
+        assert_abs_diff_eq!(squad.at(2.0 / 3.0), -keys[2], DELTA);
+        assert_abs_diff_eq!(squad.at(1.0), keys[3], DELTA);
+
+        // Smooth through the keys, stepping either side of a key changes the rotation by the same amount.
+        let h = 1e-4;
+
+        for t in [1.0 / 3.0, 2.0 / 3.0] {
+            let before = squad.at(t) - squad.at(t - h);
+            let after = squad.at(t + h) - squad.at(t);
+            assert_abs_diff_eq!(before, after, 1e-6);
+        }
+
+        for i in 0..=30 {
+            assert_abs_diff_eq!(squad.at(i as f64 / 30.0).length(), 1.0, DELTA);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use serializers::json::borrowed::parse_borrowed;
+use serializers::json::lexer::JsonLexer;
+use serializers::json::parser::parse_json;
+
+/// Builds a level file with the given number of entities, each with a few properties and a tag list.
+fn level_document(entities: usize) -> String {
+    let mut json = String::from("{ \"name\": \"benchmark level\", \"entities\": [");
+
+    for index in 0..entities {
+        if index > 0 {
+            json.push(',');
+        }
+
+        json.push_str(&format!(
+            "\n    {{ \"id\": {}, \"kind\": \"enemy_{}\", \"position\": [{}.5, -{}.25, 0.0], \"health\": {}, \
+             \"tags\": [\"hostile\", \"spawn\", \"wave_{}\"], \"script\": \"scripts/enemy_{}.lua\", \"active\": true }}",
+            index, index % 7, index, index, 100 + index % 50, index % 5, index % 7));
+    }
+
+    json.push_str("\n] }");
+    json
+}
+
+/// A document where every string holds escapes, so the borrowed parser has to decode them as well.
+fn escaped_document(strings: usize) -> String {
+    let strings: Vec<String> = (0..strings)
+        .map(|index| format!("\"line {}\\nwith \\\"quotes\\\" and \\u00e9\"", index))
+        .collect();
+
+    format!("[{}]", strings.join(", "))
+}
+
+fn bench_parsers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    let documents = [
+        ("level_100", level_document(100)),
+        ("level_10000", level_document(10_000)),
+        ("escaped_10000", escaped_document(10_000))
+    ];
+
+    for (name, json) in documents.iter() {
+        group.throughput(Throughput::Bytes(json.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("owned", name), json, |b, json| {
+            b.iter(|| parse_json(&mut JsonLexer::from_raw_json(black_box(json)).unwrap()).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("borrowed", name), json, |b, json| {
+            b.iter(|| parse_borrowed(black_box(json)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parsers);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serializers::json::lexer::JsonOptions;
+use serializers::json::reader::JsonReader;
+
+// The reader works on raw bytes, so invalid utf-8 is fed to it as well.
+// A tiny buffer makes every token cross a buffer boundary.
+fuzz_target!(|data: &[u8]| {
+    let (flags, json) = match data.split_first() {
+        Some(split) => split,
+        None => return
+    };
+
+    let mut reader = JsonReader::with_capacity(json, (*flags as usize >> 4) + 1);
+
+    reader.set_options(JsonOptions {
+        relaxed: flags & 1 != 0,
+        max_depth: if flags & 2 != 0 { 8 } else { JsonOptions::default().max_depth },
+        max_string_length: if flags & 4 != 0 { 16 } else { usize::MAX },
+        max_document_size: if flags & 8 != 0 { 64 } else { usize::MAX },
+        ..JsonOptions::default()
+    });
+
+    while let Ok(Some(_)) = reader.next_event() {
+        reader.current_pointer();
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serializers::msgpack::{decoder::decode, encoder::encode};
+
+fuzz_target!(|data: &[u8]| {
+    // Anything which decodes must encode and decode back to the same node.
+    if let Ok(node) = decode(data) {
+        assert_eq!(decode(&encode(&node)).unwrap(), node);
+    }
+});
@@ -0,0 +1,52 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serializers::json::borrowed::parse_borrowed_with_options;
+use serializers::json::lexer::{JsonLexer, JsonOptions};
+use serializers::json::parser::parse_json;
+use serializers::json::patch::values_equal;
+
+// The first byte picks the options so every mode of the lexer is covered.
+fuzz_target!(|data: &[u8]| {
+    let (flags, json) = match data.split_first() {
+        Some(split) => split,
+        None => return
+    };
+
+    let json = match std::str::from_utf8(json) {
+        Ok(json) => json,
+        Err(_) => return
+    };
+
+    let mut lexer = match JsonLexer::from_raw_json(json) {
+        Some(lexer) => lexer,
+        None => return
+    };
+
+    lexer.set_options(JsonOptions {
+        arbitrary_precision: flags & 1 != 0,
+        relaxed: flags & 2 != 0,
+        max_depth: if flags & 4 != 0 { 8 } else { JsonOptions::default().max_depth },
+        max_string_length: if flags & 8 != 0 { 16 } else { usize::MAX },
+        ..JsonOptions::default()
+    });
+
+    let result = parse_json(&mut lexer);
+
+    // The borrowed parser must accept and reject exactly the same documents.
+    let borrowed = parse_borrowed_with_options(json, lexer.get_options()).map(|node| node.to_node());
+    assert_eq!(borrowed, result);
+
+    match result {
+        // Anything which parses must write out json which parses back to the same document.
+        Ok(node) => {
+            let mut written = JsonLexer::from_raw_json(&node.to_string()).unwrap();
+            written.set_options(lexer.get_options().clone());
+
+            assert!(values_equal(&parse_json(&mut written).unwrap(), &node));
+        },
+        Err(error) => {
+            error.render_snippet(json);
+        }
+    }
+});
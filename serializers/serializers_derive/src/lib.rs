@@ -0,0 +1,233 @@
+//! Derive macros for the `ToJson` and `FromJson` traits in `serializers::json::typed`.
+//!
+//! Supported attributes:
+//! - `#[json(default)]` on a struct: missing fields are taken from the struct's `Default` impl.
+//! - `#[json(rename = "name")]` on a field: the key used in the json object.
+//! - `#[json(default)]` on a field: a missing field uses `Default::default()`.
+//! - `#[json(default = "expr")]` on a field: a missing field uses the given expression.
+//! - `#[json(required)]` on a field: the field must be present, even if a default exists.
+//!
+//! Fields without a default are required, except for `Option` fields which load as `None`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Expr, Fields, Generics, LitStr, Path};
+
+#[proc_macro_derive(ToJson, attributes(json))]
+pub fn derive_to_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_to_json(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+#[proc_macro_derive(FromJson, attributes(json))]
+pub fn derive_from_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_from_json(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+/// How a missing field gets its value.
+enum FieldDefault {
+    /// The field must be in the object, unless the type supplies a missing value (ex: Option).
+    None,
+    /// Uses Default::default() for the field type.
+    Default,
+    /// Uses the given expression.
+    Expr(Expr)
+}
+
+struct JsonField {
+    ident: syn::Ident,
+    ty: syn::Type,
+    key: String,
+    default: FieldDefault,
+    required: bool
+}
+
+/// The attributes which apply to the whole struct.
+struct ContainerAttributes {
+    default: bool
+}
+
+fn parse_container_attributes(input: &DeriveInput) -> syn::Result<ContainerAttributes> {
+    let mut attributes = ContainerAttributes { default: false };
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("json")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                attributes.default = true;
+                Ok(())
+            }
+            else {
+                Err(meta.error("unsupported json attribute, expected `default`"))
+            }
+        })?;
+    }
+
+    Ok(attributes)
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<JsonField>> {
+    let named_fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "json derives only support structs with named fields"))
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "json derives only support structs with named fields"))
+    };
+
+    let mut fields = Vec::new();
+
+    for field in named_fields {
+        // Named fields always have an identifier.
+        let ident = field.ident.clone().unwrap();
+        let mut json_field = JsonField {
+            key: ident.to_string().trim_start_matches("r#").to_string(),
+            ident,
+            ty: field.ty.clone(),
+            default: FieldDefault::None,
+            required: false
+        };
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    json_field.key = meta.value()?.parse::<LitStr>()?.value();
+                }
+                else if meta.path.is_ident("default") {
+                    json_field.default = if meta.input.peek(syn::Token![=]) {
+                        FieldDefault::Expr(meta.value()?.parse::<LitStr>()?.parse::<Expr>()?)
+                    }
+                    else {
+                        FieldDefault::Default
+                    };
+                }
+                else if meta.path.is_ident("required") {
+                    json_field.required = true;
+                }
+                else {
+                    return Err(meta.error("unsupported json attribute, expected `rename`, `default` or `required`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        fields.push(json_field);
+    }
+
+    Ok(fields)
+}
+
+/// Clones the generics and bounds every type parameter by the derived trait.
+fn add_trait_bounds(generics: &Generics, bound: Path) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<_> = generics.type_params().map(|param| param.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+
+    for ident in params {
+        where_clause.predicates.push(parse_quote! { #ident: #bound });
+    }
+
+    generics
+}
+
+fn expand_to_json(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    parse_container_attributes(input)?;
+    let fields = parse_fields(input)?;
+
+    let name = &input.ident;
+    let generics = add_trait_bounds(&input.generics, parse_quote! { ::serializers::json::typed::ToJson });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let add_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let key = &field.key;
+
+        quote! {
+            object.add(#key, ::serializers::json::typed::ToJson::to_json(&self.#ident));
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::serializers::json::typed::ToJson for #name #ty_generics #where_clause {
+            fn to_json(&self) -> ::serializers::json::parser::JsonNode {
+                let mut object = ::serializers::json::parser::JsonObject::new();
+                #(#add_fields)*
+                ::serializers::json::parser::JsonNode::Object(object)
+            }
+        }
+    })
+}
+
+fn expand_from_json(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let container = parse_container_attributes(input)?;
+    let fields = parse_fields(input)?;
+
+    let name = &input.ident;
+    let generics = add_trait_bounds(&input.generics, parse_quote! { ::serializers::json::typed::FromJson });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let load_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let key = &field.key;
+
+        let missing = if field.required {
+            quote! { return Err(::serializers::json::typed::FromJsonError::missing_field(#key)) }
+        }
+        else {
+            match &field.default {
+                FieldDefault::Expr(expr) => quote! { #expr },
+                FieldDefault::Default => quote! { ::std::default::Default::default() },
+                FieldDefault::None if container.default => quote! { container_default.#ident },
+                FieldDefault::None => quote! {
+                    match <#ty as ::serializers::json::typed::FromJson>::from_missing() {
+                        Some(value) => value,
+                        None => return Err(::serializers::json::typed::FromJsonError::missing_field(#key))
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #ident: match object.get(#key) {
+                Some(node) => <#ty as ::serializers::json::typed::FromJson>::from_json(node)
+                    .map_err(|e| e.with_key(#key))?,
+                None => #missing
+            },
+        }
+    });
+
+    let container_default = if container.default {
+        quote! { let container_default: Self = ::std::default::Default::default(); }
+    }
+    else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::serializers::json::typed::FromJson for #name #ty_generics #where_clause {
+            fn from_json(node: &::serializers::json::parser::JsonNode) -> ::std::result::Result<Self, ::serializers::json::typed::FromJsonError> {
+                let object = match node {
+                    ::serializers::json::parser::JsonNode::Object(object) => object,
+                    _ => return Err(::serializers::json::typed::FromJsonError::unexpected_type("object", node))
+                };
+
+                #container_default
+
+                Ok(#name {
+                    #(#load_fields)*
+                })
+            }
+        }
+    })
+}
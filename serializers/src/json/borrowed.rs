@@ -0,0 +1,363 @@
+use std::borrow::Cow;
+
+use crate::json::error::{JsonError, SourceLocation};
+use crate::json::lexer::{self, JsonOptions, ReserveCode, Token, TokenType};
+use crate::json::parser::{JsonNode, JsonObject, JsonArray, JsonValue};
+use crate::json::pointer::{parse_index, parse_pointer};
+
+/// A json node which borrows from the text it was parsed from.
+/// Keys and strings without escapes, along with raw numbers, are slices of the source,
+/// so large documents can be read without copying them. `to_node` makes an owned copy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonNodeRef<'a> {
+    /// The entries in the order they were written. A repeated key keeps every entry,
+    /// and `get` finds the last one, which is the value the owned parser keeps.
+    Object(Vec<(Cow<'a, str>, JsonNodeRef<'a>)>),
+    Array(Vec<JsonNodeRef<'a>>),
+    Number(i64),
+    /// Holds integers above `i64::MAX`, smaller integers are always loaded as a `Number`.
+    UnsignedNumber(u64),
+    Float(f64),
+    /// A number kept as the text it was written with, when arbitrary precision is enabled.
+    RawNumber(&'a str),
+    Bool(bool),
+    String(Cow<'a, str>),
+    Null
+}
+
+impl<'a> JsonNodeRef<'a> {
+    /// Finds the value of a key in an object.
+    /// Objects are not indexed, so this is a linear search through the entries.
+    pub fn get(&self, key: &str) -> Option<&JsonNodeRef<'a>> {
+        match self {
+            JsonNodeRef::Object(entries) => entries.iter().rev().find(|(name, _)| name == key).map(|(_, node)| node),
+            _ => None
+        }
+    }
+
+    /// Finds an element of an array.
+    pub fn get_index(&self, index: usize) -> Option<&JsonNodeRef<'a>> {
+        match self {
+            JsonNodeRef::Array(elements) => elements.get(index),
+            _ => None
+        }
+    }
+
+    /// Finds a node by its json pointer, the same as `JsonNode::pointer`.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonNodeRef<'a>> {
+        let mut node = self;
+
+        for token in parse_pointer(pointer)? {
+            node = match node {
+                JsonNodeRef::Object(_) => node.get(&token)?,
+                JsonNodeRef::Array(_) => node.get_index(parse_index(&token)?)?,
+                _ => return None
+            };
+        }
+
+        Some(node)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonNodeRef::String(value) => Some(value),
+            _ => None
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonNodeRef::Bool(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Returns the value as an integer, with the same rules as `JsonNode::as_i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.number_node()?.as_i64()
+    }
+
+    /// Returns the value as an unsigned integer, with the same rules as `JsonNode::as_u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.number_node()?.as_u64()
+    }
+
+    /// Returns the value as a float, with the same rules as `JsonNode::as_f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.number_node()?.as_f64()
+    }
+
+    /// Copies the node and everything inside of it into an owned node.
+    pub fn to_node(&self) -> JsonNode {
+        match self {
+            JsonNodeRef::Object(entries) => {
+                let mut obj = JsonObject::new();
+
+                for (key, node) in entries {
+                    obj.add(key, node.to_node());
+                }
+
+                JsonNode::Object(obj)
+            },
+            JsonNodeRef::Array(elements) => {
+                let mut array = JsonArray::new();
+
+                for node in elements {
+                    array.add(node.to_node());
+                }
+
+                JsonNode::Array(array)
+            },
+            JsonNodeRef::Bool(value) => JsonNode::Bool(JsonValue::new(*value)),
+            JsonNodeRef::String(value) => JsonNode::String(JsonValue::new(value.to_string())),
+            JsonNodeRef::Null => JsonNode::Null,
+            _ => self.number_node().unwrap_or_default()
+        }
+    }
+
+    fn number_node(&self) -> Option<JsonNode> {
+        match self {
+            JsonNodeRef::Number(value) => Some(JsonNode::Number(JsonValue::new(*value))),
+            JsonNodeRef::UnsignedNumber(value) => Some(JsonNode::UnsignedNumber(JsonValue::new(*value))),
+            JsonNodeRef::Float(value) => Some(JsonNode::Float(JsonValue::new(*value))),
+            JsonNodeRef::RawNumber(lexeme) => Some(JsonNode::RawNumber(JsonValue::new(lexeme.to_string()))),
+            _ => None
+        }
+    }
+}
+
+/// Parses a whole document without copying its strings, see `JsonNodeRef`.
+pub fn parse_borrowed(json: &str) -> Result<JsonNodeRef<'_>, JsonError> {
+    parse_borrowed_with_options(json, &JsonOptions::default())
+}
+
+/// Parses a whole document with the given options.
+/// Accepts and rejects exactly the same documents as `parse_json`, with the same errors.
+pub fn parse_borrowed_with_options<'a>(json: &'a str, options: &JsonOptions) -> Result<JsonNodeRef<'a>, JsonError> {
+    if json.len() > options.max_document_size {
+        return Err(JsonError::DocumentTooLarge {
+            max_size: options.max_document_size,
+            location: SourceLocation::from_offset(json.as_bytes(), options.max_document_size)
+        });
+    }
+
+    let mut parser = BorrowedParser { json, index: 0, options };
+
+    let (token, offset) = parser.next_token()?;
+    let node = parser.parse_node(token, offset, 0)?;
+
+    // Nothing but whitespace may follow the document.
+    match parser.next_token()? {
+        (RefToken::End, _) => Ok(node),
+        (found, offset) => Err(JsonError::TrailingData {
+            found: found.token_type().to_string(),
+            location: parser.location(offset)
+        })
+    }
+}
+
+/// A token which borrows from the source where it can.
+enum RefToken<'a> {
+    Reserve(ReserveCode),
+    String(Cow<'a, str>),
+    /// An unquoted object key, only produced in relaxed mode.
+    Identifier(&'a str),
+    /// A number, bool or null.
+    Scalar(JsonNodeRef<'a>),
+    End
+}
+
+impl RefToken<'_> {
+    /// The matching token of the owned lexer, used so error messages are the same.
+    fn token_type(&self) -> TokenType {
+        match self {
+            RefToken::Reserve(reserve_id) => TokenType::Reserve { reserve_id: *reserve_id },
+            RefToken::String(value) => TokenType::String { value: value.to_string() },
+            RefToken::Identifier(name) => TokenType::Identifier { name: name.to_string() },
+            RefToken::Scalar(JsonNodeRef::Number(value)) => TokenType::Number { value: *value },
+            RefToken::Scalar(JsonNodeRef::UnsignedNumber(value)) => TokenType::UnsignedNumber { value: *value },
+            RefToken::Scalar(JsonNodeRef::Float(value)) => TokenType::Float { value: *value },
+            RefToken::Scalar(JsonNodeRef::RawNumber(lexeme)) => TokenType::RawNumber { lexeme: lexeme.to_string() },
+            RefToken::Scalar(JsonNodeRef::Bool(value)) => TokenType::Boolean { value: *value },
+            RefToken::Scalar(_) => TokenType::Null,
+            RefToken::End => TokenType::Undefined
+        }
+    }
+}
+
+struct BorrowedParser<'a, 'o> {
+    json: &'a str,
+    index: usize,
+    options: &'o JsonOptions
+}
+
+impl<'a> BorrowedParser<'a, '_> {
+    /// Reads the next token along with the byte offset it starts at.
+    fn next_token(&mut self) -> Result<(RefToken<'a>, usize), JsonError> {
+        let json = self.json.as_bytes();
+        lexer::skip_whitespace(json, &mut self.index, json.len(), self.options)?;
+
+        let start = self.index;
+
+        if start >= json.len() {
+            return Ok((RefToken::End, start));
+        }
+
+        // Strings without escapes are sliced straight out of the source. Anything else,
+        // including every malformed string, goes through the lexer so the errors are the same.
+        if lexer::is_str_start(json[start], self.options) {
+            if let Some(end) = find_plain_string_end(json, start) {
+                if end - start - 1 <= self.options.max_string_length {
+                    self.index = end + 1;
+                    return Ok((RefToken::String(Cow::Borrowed(&self.json[start + 1..end])), start));
+                }
+            }
+        }
+
+        let mut token = Token::default();
+        lexer::lex_token(json, &mut self.index, self.options, &mut token)?;
+
+        let token = match token.into_type() {
+            TokenType::Reserve { reserve_id } => RefToken::Reserve(reserve_id),
+            TokenType::String { value } => RefToken::String(Cow::Owned(value)),
+            TokenType::Identifier { .. } => RefToken::Identifier(&self.json[start..self.index]),
+            TokenType::Number { value } => RefToken::Scalar(JsonNodeRef::Number(value)),
+            TokenType::UnsignedNumber { value } => RefToken::Scalar(JsonNodeRef::UnsignedNumber(value)),
+            TokenType::Float { value } => RefToken::Scalar(JsonNodeRef::Float(value)),
+            TokenType::RawNumber { .. } => RefToken::Scalar(JsonNodeRef::RawNumber(&self.json[start..self.index])),
+            TokenType::Boolean { value } => RefToken::Scalar(JsonNodeRef::Bool(value)),
+            TokenType::Null => RefToken::Scalar(JsonNodeRef::Null),
+            TokenType::Undefined => RefToken::End
+        };
+
+        Ok((token, start))
+    }
+
+    fn location(&self, offset: usize) -> SourceLocation {
+        SourceLocation::from_offset(self.json.as_bytes(), offset)
+    }
+
+    fn unexpected(&self, token: RefToken, offset: usize, expected: &'static str) -> JsonError {
+        match token {
+            RefToken::End => JsonError::UnexpectedEof { expected, location: self.location(offset) },
+            found => JsonError::UnexpectedToken {
+                expected,
+                found: found.token_type().to_string(),
+                location: self.location(offset)
+            }
+        }
+    }
+
+    /// Parses the node starting at the token. The depth is the number of objects and arrays the node is inside of.
+    fn parse_node(&mut self, token: RefToken<'a>, offset: usize, depth: usize) -> Result<JsonNodeRef<'a>, JsonError> {
+        match token {
+            RefToken::Reserve(ReserveCode::OpenBrace) => {
+                self.check_depth(offset, depth)?;
+                self.parse_object(depth + 1)
+            },
+            RefToken::Reserve(ReserveCode::OpenBracket) => {
+                self.check_depth(offset, depth)?;
+                self.parse_array(depth + 1)
+            },
+            RefToken::String(value) => Ok(JsonNodeRef::String(value)),
+            RefToken::Scalar(node) => Ok(node),
+            found => Err(self.unexpected(found, offset, "a json node"))
+        }
+    }
+
+    fn check_depth(&self, offset: usize, depth: usize) -> Result<(), JsonError> {
+        if depth >= self.options.max_depth {
+            return Err(JsonError::NestingTooDeep { max_depth: self.options.max_depth, location: self.location(offset) });
+        }
+
+        Ok(())
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonNodeRef<'a>, JsonError> {
+        let mut entries = Vec::new();
+        let (mut token, mut offset) = self.next_token()?;
+
+        if let RefToken::Reserve(ReserveCode::CloseBrace) = token {
+            return Ok(JsonNodeRef::Object(entries));
+        }
+
+        loop {
+            let key = match token {
+                RefToken::String(key) => key,
+                RefToken::Identifier(name) => Cow::Borrowed(name),
+                found => return Err(self.unexpected(found, offset, "an object key"))
+            };
+
+            match self.next_token()? {
+                (RefToken::Reserve(ReserveCode::Colon), _) => {},
+                (found, offset) => return Err(self.unexpected(found, offset, "':'"))
+            }
+
+            let (value_token, value_offset) = self.next_token()?;
+            entries.push((key, self.parse_node(value_token, value_offset, depth)?));
+
+            (token, offset) = self.next_token()?;
+
+            match token {
+                RefToken::Reserve(ReserveCode::Comma) => {
+                    (token, offset) = self.next_token()?;
+
+                    // Relaxed json allows a comma after the last entry.
+                    if self.options.relaxed && matches!(token, RefToken::Reserve(ReserveCode::CloseBrace)) {
+                        break;
+                    }
+                },
+                RefToken::Reserve(ReserveCode::CloseBrace) => break,
+                found => return Err(self.unexpected(found, offset, "',' or '}'"))
+            }
+        }
+
+        Ok(JsonNodeRef::Object(entries))
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonNodeRef<'a>, JsonError> {
+        let mut elements = Vec::new();
+        let (mut token, mut offset) = self.next_token()?;
+
+        if let RefToken::Reserve(ReserveCode::CloseBracket) = token {
+            return Ok(JsonNodeRef::Array(elements));
+        }
+
+        loop {
+            elements.push(self.parse_node(token, offset, depth)?);
+
+            (token, offset) = self.next_token()?;
+
+            match token {
+                RefToken::Reserve(ReserveCode::Comma) => {
+                    (token, offset) = self.next_token()?;
+
+                    if self.options.relaxed && matches!(token, RefToken::Reserve(ReserveCode::CloseBracket)) {
+                        break;
+                    }
+                },
+                RefToken::Reserve(ReserveCode::CloseBracket) => break,
+                found => return Err(self.unexpected(found, offset, "',' or ']'"))
+            }
+        }
+
+        Ok(JsonNodeRef::Array(elements))
+    }
+}
+
+/// Returns the index of the closing quote of a string with no escapes or control characters,
+/// or None when the string needs to be decoded by the lexer.
+fn find_plain_string_end(json: &[u8], start: usize) -> Option<usize> {
+    let quote = json[start];
+
+    for (index, ch) in json.iter().enumerate().skip(start + 1) {
+        if *ch == quote {
+            return Some(index);
+        }
+        else if *ch == b'\\' || *ch < 0x20 {
+            return None;
+        }
+    }
+
+    None
+}
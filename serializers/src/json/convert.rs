@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::json::parser::{JsonNode, JsonObject, JsonArray};
+use crate::json::typed::{ToJson, FromJson, FromJsonError};
+
+/// Builds a `JsonNode` from json written inline, ex:
+///
+/// ```
+/// use serializers::json;
+///
+/// let xres = 1920;
+/// let config = json!({
+///     "window_config": { "xres": xres, "yres": 1080, "title": "Snake" },
+///     "spawn_points": [[0, 0], [4.5, -2]],
+///     "icon": null
+/// });
+///
+/// assert_eq!(config.get_i64("/window_config/xres"), Some(1920));
+/// ```
+///
+/// Values can be any expression which converts into a `JsonNode`.
+/// Keys are string literals, or any expression which converts into a `String` when wrapped in parentheses.
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::json::parser::JsonNode::Null
+    };
+    ([ $($elements:tt)* ]) => {{
+        #[allow(unused_mut)]
+        let mut array = $crate::json::parser::JsonArray::new();
+        $crate::json!(@array array $($elements)*);
+        $crate::json::parser::JsonNode::Array(array)
+    }};
+    ({ $($entries:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object = $crate::json::parser::JsonObject::new();
+        $crate::json!(@object object $($entries)*);
+        $crate::json::parser::JsonNode::Object(object)
+    }};
+
+    // Each element is added and the rest of the array is passed on, nested values are expanded first.
+    (@array $array:ident) => {};
+    (@array $array:ident null $(, $($rest:tt)*)?) => {
+        $array.add($crate::json!(null));
+        $crate::json!(@array $array $($($rest)*)?);
+    };
+    (@array $array:ident [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $array.add($crate::json!([ $($inner)* ]));
+        $crate::json!(@array $array $($($rest)*)?);
+    };
+    (@array $array:ident { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $array.add($crate::json!({ $($inner)* }));
+        $crate::json!(@array $array $($($rest)*)?);
+    };
+    (@array $array:ident $value:expr $(, $($rest:tt)*)?) => {
+        $array.add($crate::json::parser::JsonNode::from($value));
+        $crate::json!(@array $array $($($rest)*)?);
+    };
+
+    (@object $object:ident) => {};
+    (@object $object:ident $key:tt : null $(, $($rest:tt)*)?) => {
+        $object.add(&::std::string::String::from($key), $crate::json!(null));
+        $crate::json!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:tt : [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $object.add(&::std::string::String::from($key), $crate::json!([ $($inner)* ]));
+        $crate::json!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:tt : { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $object.add(&::std::string::String::from($key), $crate::json!({ $($inner)* }));
+        $crate::json!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:tt : $value:expr $(, $($rest:tt)*)?) => {
+        $object.add(&::std::string::String::from($key), $crate::json::parser::JsonNode::from($value));
+        $crate::json!(@object $object $($($rest)*)?);
+    };
+
+    ($value:expr) => {
+        $crate::json::parser::JsonNode::from($value)
+    };
+}
+
+/// Primitives convert the same way they are written by `ToJson`.
+macro_rules! impl_from_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl From<$primitive> for JsonNode {
+                fn from(value: $primitive) -> Self {
+                    value.to_json()
+                }
+            }
+
+            impl TryFrom<&JsonNode> for $primitive {
+                type Error = FromJsonError;
+
+                fn try_from(node: &JsonNode) -> Result<Self, Self::Error> {
+                    <$primitive>::from_json(node)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool, String);
+
+impl From<&str> for JsonNode {
+    fn from(value: &str) -> Self {
+        value.to_json()
+    }
+}
+
+impl From<&String> for JsonNode {
+    fn from(value: &String) -> Self {
+        value.to_json()
+    }
+}
+
+impl From<()> for JsonNode {
+    fn from(_: ()) -> Self {
+        JsonNode::Null
+    }
+}
+
+impl From<JsonObject> for JsonNode {
+    fn from(value: JsonObject) -> Self {
+        JsonNode::Object(value)
+    }
+}
+
+impl From<JsonArray> for JsonNode {
+    fn from(value: JsonArray) -> Self {
+        JsonNode::Array(value)
+    }
+}
+
+/// None converts to null.
+impl<T: Into<JsonNode>> From<Option<T>> for JsonNode {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonNode::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonNode>> From<Vec<T>> for JsonNode {
+    fn from(values: Vec<T>) -> Self {
+        let mut array = JsonArray::new();
+
+        for value in values {
+            array.add(value.into());
+        }
+
+        JsonNode::Array(array)
+    }
+}
+
+impl<T: Into<JsonNode> + Clone> From<&[T]> for JsonNode {
+    fn from(values: &[T]) -> Self {
+        values.to_vec().into()
+    }
+}
+
+/// Keys are added in sorted order so the output is stable, the same as `ToJson` for maps.
+impl<K: AsRef<str> + Ord, V: Into<JsonNode>> From<HashMap<K, V>> for JsonNode {
+    fn from(values: HashMap<K, V>) -> Self {
+        let mut entries: Vec<(K, V)> = values.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut object = JsonObject::new();
+
+        for (key, value) in entries {
+            object.add(key.as_ref(), value.into());
+        }
+
+        JsonNode::Object(object)
+    }
+}
+
+impl<'a> TryFrom<&'a JsonNode> for &'a str {
+    type Error = FromJsonError;
+
+    fn try_from(node: &'a JsonNode) -> Result<Self, Self::Error> {
+        node.as_str().ok_or_else(|| FromJsonError::unexpected_type("string", node))
+    }
+}
+
+impl<'a> TryFrom<&'a JsonNode> for &'a JsonObject {
+    type Error = FromJsonError;
+
+    fn try_from(node: &'a JsonNode) -> Result<Self, Self::Error> {
+        match node {
+            JsonNode::Object(obj) => Ok(obj),
+            _ => Err(FromJsonError::unexpected_type("object", node))
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a JsonNode> for &'a JsonArray {
+    type Error = FromJsonError;
+
+    fn try_from(node: &'a JsonNode) -> Result<Self, Self::Error> {
+        match node {
+            JsonNode::Array(array) => Ok(array),
+            _ => Err(FromJsonError::unexpected_type("array", node))
+        }
+    }
+}
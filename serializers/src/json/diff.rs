@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use crate::json::parser::{JsonNode, JsonArray};
+use crate::json::patch::values_equal;
+use crate::json::pointer::{escape_pointer_token, parse_pointer};
+use crate::json::typed::json_type_name;
+use crate::json::writer::escape_json_string;
+
+/// How the elements of two arrays are paired up before they are compared.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ArrayDiffMode {
+    /// Elements at the same index are compared, so inserting an element changes every element after it.
+    #[default]
+    ByIndex,
+    /// Object elements are paired by the value of this key, ex: "id", so inserting or reordering
+    /// elements only reports the elements which really changed. Arrays where an element is missing
+    /// the key or shares its key with another element are compared by index instead.
+    ByKey(String)
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DiffOptions {
+    /// The mode used for every array without an override.
+    pub arrays: ArrayDiffMode,
+    /// The mode for the arrays at specific json pointers, where a `*` token matches any key or index,
+    /// ex: ("/levels/*/enemies", ArrayDiffMode::ByKey("name")).
+    pub array_overrides: Vec<(String, ArrayDiffMode)>
+}
+
+impl DiffOptions {
+    fn array_mode(&self, path: &str) -> &ArrayDiffMode {
+        let tokens = match parse_pointer(path) {
+            Some(tokens) => tokens,
+            None => return &self.arrays
+        };
+
+        self.array_overrides.iter()
+            .find(|(pattern, _)| pattern_matches(pattern, &tokens))
+            .map_or(&self.arrays, |(_, mode)| mode)
+    }
+}
+
+/// What happened to a single value between the old and new document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffKind {
+    Added {
+        value: JsonNode
+    },
+    Removed {
+        value: JsonNode
+    },
+    /// The value was replaced. Objects and arrays are only reported as changed when their type changed,
+    /// otherwise the differences inside of them are reported.
+    Changed {
+        old: JsonNode,
+        new: JsonNode
+    }
+}
+
+/// A single difference between two documents, at a json pointer.
+/// Added and changed values use their path in the new document, removed values their path in the old one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub path: String,
+    pub kind: DiffKind
+}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() { "/" } else { &self.path };
+
+        match &self.kind {
+            DiffKind::Added { value } => write!(f, "+ {}: {}", path, value.to_string()),
+            DiffKind::Removed { value } => write!(f, "- {}: {}", path, value.to_string()),
+            DiffKind::Changed { old, new } => write!(f, "~ {}: {} -> {}", path, old.to_string(), new.to_string())
+        }
+    }
+}
+
+/// Compares two documents, comparing arrays index by index.
+/// Numbers are equal when they hold the same number, no matter how they were written.
+pub fn diff(old: &JsonNode, new: &JsonNode) -> Vec<DiffEntry> {
+    diff_with_options(old, new, &DiffOptions::default())
+}
+
+pub fn diff_with_options(old: &JsonNode, new: &JsonNode, options: &DiffOptions) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    diff_nodes(old, new, String::new(), options, &mut entries);
+    entries
+}
+
+/// Formats the entries as a readable report with one line per entry and a summary at the end, ex:
+///
+/// ```text
+/// ~ /window_config/xres: 1280 -> 1920
+/// + /graphics/vsync: true
+/// 1 added, 0 removed, 1 changed
+/// ```
+pub fn format_report(entries: &[DiffEntry]) -> String {
+    if entries.is_empty() {
+        return "no changes".to_string();
+    }
+
+    let mut report = String::new();
+    let mut counts = [0; 3];
+
+    for entry in entries {
+        report.push_str(&entry.to_string());
+        report.push('\n');
+
+        match entry.kind {
+            DiffKind::Added { .. } => counts[0] += 1,
+            DiffKind::Removed { .. } => counts[1] += 1,
+            DiffKind::Changed { .. } => counts[2] += 1
+        }
+    }
+
+    report.push_str(&format!("{} added, {} removed, {} changed", counts[0], counts[1], counts[2]));
+    report
+}
+
+fn diff_nodes(old: &JsonNode, new: &JsonNode, path: String, options: &DiffOptions, entries: &mut Vec<DiffEntry>) {
+    if values_equal(old, new) {
+        return;
+    }
+
+    match (old, new) {
+        (JsonNode::Object(old), JsonNode::Object(new)) => {
+            for (key, value) in old.iter() {
+                let child_path = format!("{}/{}", path, escape_pointer_token(key));
+
+                match new.get(key) {
+                    Some(new_value) => diff_nodes(value, new_value, child_path, options, entries),
+                    None => entries.push(DiffEntry { path: child_path, kind: DiffKind::Removed { value: value.clone() } })
+                }
+            }
+
+            for (key, value) in new.iter() {
+                if !old.contains_key(key) {
+                    entries.push(DiffEntry {
+                        path: format!("{}/{}", path, escape_pointer_token(key)),
+                        kind: DiffKind::Added { value: value.clone() }
+                    });
+                }
+            }
+        },
+        (JsonNode::Array(old), JsonNode::Array(new)) => {
+            let keyed = match options.array_mode(&path) {
+                ArrayDiffMode::ByKey(key) => key_identities(old, key).zip(key_identities(new, key)),
+                ArrayDiffMode::ByIndex => None
+            };
+
+            match keyed {
+                Some((old_keys, new_keys)) => diff_keyed_arrays(old, new, &old_keys, &new_keys, &path, options, entries),
+                None => diff_indexed_arrays(old, new, &path, options, entries)
+            }
+        },
+        _ => entries.push(DiffEntry { path, kind: DiffKind::Changed { old: old.clone(), new: new.clone() } })
+    }
+}
+
+fn diff_indexed_arrays(old: &JsonArray, new: &JsonArray, path: &str, options: &DiffOptions, entries: &mut Vec<DiffEntry>) {
+    for (index, (value, new_value)) in old.iter().zip(new.iter()).enumerate() {
+        diff_nodes(value, new_value, format!("{}/{}", path, index), options, entries);
+    }
+
+    for (index, value) in old.iter().enumerate().skip(new.size()) {
+        entries.push(DiffEntry { path: format!("{}/{}", path, index), kind: DiffKind::Removed { value: value.clone() } });
+    }
+
+    for (index, value) in new.iter().enumerate().skip(old.size()) {
+        entries.push(DiffEntry { path: format!("{}/{}", path, index), kind: DiffKind::Added { value: value.clone() } });
+    }
+}
+
+fn diff_keyed_arrays(old: &JsonArray, new: &JsonArray, old_keys: &[String], new_keys: &[String],
+    path: &str, options: &DiffOptions, entries: &mut Vec<DiffEntry>) {
+    let old_set: HashSet<&str> = old_keys.iter().map(|key| key.as_str()).collect();
+    let new_indices: HashMap<&str, usize> = new_keys.iter().enumerate().map(|(index, key)| (key.as_str(), index)).collect();
+
+    for (index, (key, value)) in old_keys.iter().zip(old.iter()).enumerate() {
+        match new_indices.get(key.as_str()) {
+            Some(&new_index) => diff_nodes(value, new.get(new_index).unwrap(), format!("{}/{}", path, new_index), options, entries),
+            None => entries.push(DiffEntry { path: format!("{}/{}", path, index), kind: DiffKind::Removed { value: value.clone() } })
+        }
+    }
+
+    for (index, (key, value)) in new_keys.iter().zip(new.iter()).enumerate() {
+        if !old_set.contains(key.as_str()) {
+            entries.push(DiffEntry { path: format!("{}/{}", path, index), kind: DiffKind::Added { value: value.clone() } });
+        }
+    }
+}
+
+/// Returns the identity of each element, or None when an element has no identity or two
+/// elements share one. Identities are the canonical json of the key's value, so that keys pair up
+/// whenever `values_equal` would call them equal.
+fn key_identities(array: &JsonArray, key: &str) -> Option<Vec<String>> {
+    let mut identities = Vec::with_capacity(array.size());
+    let mut seen = HashSet::with_capacity(array.size());
+
+    for element in array.iter() {
+        let identity = match element {
+            JsonNode::Object(obj) => canonical_json(obj.get(key)?),
+            _ => return None
+        };
+
+        if !seen.insert(identity.clone()) {
+            return None;
+        }
+
+        identities.push(identity);
+    }
+
+    Some(identities)
+}
+
+/// Writes a node so that values which are equal by `values_equal` are written the same, numbers are
+/// written as integers whenever they hold one, ex: 1.0 as 1, and object keys are sorted.
+fn canonical_json(node: &JsonNode) -> String {
+    match node {
+        JsonNode::Object(obj) => {
+            let mut members: Vec<String> = obj.iter()
+                .map(|(key, value)| format!("\"{}\":{}", escape_json_string(key), canonical_json(value)))
+                .collect();
+
+            members.sort();
+            format!("{{{}}}", members.join(","))
+        },
+        JsonNode::Array(arr) => format!("[{}]", arr.iter().map(canonical_json).collect::<Vec<String>>().join(",")),
+        _ if json_type_name(node) == "number" => {
+            match (node.as_i64(), node.as_u64(), node.as_f64()) {
+                (Some(integer), _, _) => integer.to_string(),
+                (None, Some(integer), _) => integer.to_string(),
+                (None, None, Some(float)) => format!("{:?}", float),
+                // Raw numbers which don't parse are only ever equal to the same text.
+                (None, None, None) => node.to_string()
+            }
+        },
+        _ => node.to_string()
+    }
+}
+
+fn pattern_matches(pattern: &str, tokens: &[String]) -> bool {
+    match parse_pointer(pattern) {
+        Some(pattern) => pattern.len() == tokens.len() && pattern.iter().zip(tokens).all(|(part, token)| part == "*" || part == token),
+        None => false
+    }
+}
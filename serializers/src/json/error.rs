@@ -0,0 +1,223 @@
+use std::fmt::{Display, Formatter};
+
+/// A position in the json source text.
+/// The offset is in bytes, the line and column are 1 based and the column counts characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceLocation {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize
+}
+
+impl SourceLocation {
+    /// Computes the line and column of a byte offset into the source text.
+    pub fn from_offset(json: &[u8], offset: usize) -> SourceLocation {
+        let offset = offset.min(json.len());
+        let line_start = find_line_start(json, offset);
+
+        let line = json[..offset].iter().filter(|ch| **ch == b'\n').count() + 1;
+        let column = String::from_utf8_lossy(&json[line_start..offset]).chars().count() + 1;
+
+        SourceLocation { offset, line, column }
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Every error which can be produced while lexing or parsing a json document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    /// A token was found which is not valid at this point in the document.
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+        location: SourceLocation
+    },
+    /// The document ended while more input was expected.
+    UnexpectedEof {
+        expected: &'static str,
+        location: SourceLocation
+    },
+    /// A character which cannot start any json token.
+    UnexpectedCharacter {
+        found: char,
+        location: SourceLocation
+    },
+    /// A string was not closed before the end of the line or file.
+    UnterminatedString {
+        location: SourceLocation
+    },
+    /// A `/* */` comment was not closed before the end of the file.
+    UnterminatedComment {
+        location: SourceLocation
+    },
+    /// A backslash escape which is not defined by json, or a \u escape which is not a valid character.
+    InvalidEscape {
+        sequence: String,
+        location: SourceLocation
+    },
+    /// Control characters must be escaped inside of strings.
+    ControlCharacterInString {
+        found: char,
+        location: SourceLocation
+    },
+    /// A number literal which does not follow the json number grammar.
+    InvalidNumber {
+        lexeme: String,
+        location: SourceLocation
+    },
+    /// A complete json node was parsed, but more tokens followed it.
+    TrailingData {
+        found: String,
+        location: SourceLocation
+    },
+    /// Objects and arrays were nested deeper than `JsonOptions::max_depth`.
+    NestingTooDeep {
+        max_depth: usize,
+        location: SourceLocation
+    },
+    /// A string was longer than `JsonOptions::max_string_length`.
+    StringTooLong {
+        max_length: usize,
+        location: SourceLocation
+    },
+    /// A number was longer than `JsonOptions::max_string_length`.
+    NumberTooLong {
+        max_length: usize,
+        location: SourceLocation
+    },
+    /// The document was larger than `JsonOptions::max_document_size`.
+    DocumentTooLarge {
+        max_size: usize,
+        location: SourceLocation
+    },
+    /// Reading from the input stream failed.
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+        location: SourceLocation
+    }
+}
+
+impl JsonError {
+    /// Returns where in the source text the error occurred.
+    pub fn location(&self) -> SourceLocation {
+        match self {
+            JsonError::UnexpectedToken { location, .. } => *location,
+            JsonError::UnexpectedEof { location, .. } => *location,
+            JsonError::UnexpectedCharacter { location, .. } => *location,
+            JsonError::UnterminatedString { location } => *location,
+            JsonError::UnterminatedComment { location } => *location,
+            JsonError::InvalidEscape { location, .. } => *location,
+            JsonError::ControlCharacterInString { location, .. } => *location,
+            JsonError::InvalidNumber { location, .. } => *location,
+            JsonError::TrailingData { location, .. } => *location,
+            JsonError::NestingTooDeep { location, .. } => *location,
+            JsonError::StringTooLong { location, .. } => *location,
+            JsonError::NumberTooLong { location, .. } => *location,
+            JsonError::DocumentTooLarge { location, .. } => *location,
+            JsonError::Io { location, .. } => *location
+        }
+    }
+
+    /// Describes the error without the location.
+    pub fn message(&self) -> String {
+        match self {
+            JsonError::UnexpectedToken { expected, found, .. } => format!("expected {} but found {}", expected, found),
+            JsonError::UnexpectedEof { expected, .. } => format!("expected {} but reached the end of the file", expected),
+            JsonError::UnexpectedCharacter { found, .. } => format!("unexpected character '{}'", found.escape_debug()),
+            JsonError::UnterminatedString { .. } => "unterminated string".to_string(),
+            JsonError::UnterminatedComment { .. } => "unterminated comment".to_string(),
+            JsonError::InvalidEscape { sequence, .. } => format!("invalid escape sequence '{}'", sequence),
+            JsonError::ControlCharacterInString { found, .. } => format!("control character '{}' must be escaped in strings", found.escape_debug()),
+            JsonError::InvalidNumber { lexeme, .. } => format!("invalid number '{}'", lexeme),
+            JsonError::TrailingData { found, .. } => format!("unexpected {} after the end of the document", found),
+            JsonError::NestingTooDeep { max_depth, .. } => format!("objects and arrays are nested deeper than the limit of {}", max_depth),
+            JsonError::StringTooLong { max_length, .. } => format!("string is longer than the limit of {} bytes", max_length),
+            JsonError::NumberTooLong { max_length, .. } => format!("number is longer than the limit of {} bytes", max_length),
+            JsonError::DocumentTooLarge { max_size, .. } => format!("document is larger than the limit of {} bytes", max_size),
+            JsonError::Io { message, .. } => format!("unable to read the input: {}", message)
+        }
+    }
+
+    /// Moves an error found in a piece of the document so it is relative to the whole document.
+    /// The base is the location the piece starts at.
+    pub(crate) fn relative_to(mut self, base: SourceLocation) -> JsonError {
+        let location = self.location_mut();
+
+        if location.line == 1 {
+            location.column += base.column - 1;
+        }
+
+        location.offset += base.offset;
+        location.line += base.line - 1;
+        self
+    }
+
+    fn location_mut(&mut self) -> &mut SourceLocation {
+        match self {
+            JsonError::UnexpectedToken { location, .. } => location,
+            JsonError::UnexpectedEof { location, .. } => location,
+            JsonError::UnexpectedCharacter { location, .. } => location,
+            JsonError::UnterminatedString { location } => location,
+            JsonError::UnterminatedComment { location } => location,
+            JsonError::InvalidEscape { location, .. } => location,
+            JsonError::ControlCharacterInString { location, .. } => location,
+            JsonError::InvalidNumber { location, .. } => location,
+            JsonError::TrailingData { location, .. } => location,
+            JsonError::NestingTooDeep { location, .. } => location,
+            JsonError::StringTooLong { location, .. } => location,
+            JsonError::NumberTooLong { location, .. } => location,
+            JsonError::DocumentTooLarge { location, .. } => location,
+            JsonError::Io { location, .. } => location
+        }
+    }
+
+    /// Renders the error along with the offending line of the source and a marker under the error column.
+    /// The source must be the same text the error was produced from.
+    pub fn render_snippet(&self, source: &str) -> String {
+        let location = self.location();
+        let json = source.as_bytes();
+        let offset = location.offset.min(json.len());
+
+        let line_start = find_line_start(json, offset);
+        let line_end = json[offset..].iter()
+            .position(|ch| *ch == b'\n')
+            .map_or(json.len(), |end| offset + end);
+
+        let line_text = String::from_utf8_lossy(&json[line_start..line_end]);
+        let line_text = line_text.trim_end_matches('\r');
+        let line_number = location.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        // Tabs are kept in the marker line so the caret lines up with the source.
+        let marker: String = line_text.chars()
+            .take(location.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!("error: {} at {}\n{} |\n{} | {}\n{} | {}^\n",
+            self.message(), location,
+            gutter,
+            line_number, line_text,
+            gutter, marker)
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.message(), self.location())
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+fn find_line_start(json: &[u8], offset: usize) -> usize {
+    json[..offset].iter()
+        .rposition(|ch| *ch == b'\n')
+        .map_or(0, |newline| newline + 1)
+}
@@ -1,5 +1,7 @@
 use std::{io::Error, str::FromStr};
 
+use super::error::{JsonError, SourceLocation};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ReserveCode {
     OpenBrace,
@@ -25,15 +27,27 @@ pub enum TokenType {
     Number { 
         value: i64
     },
+    /// Integers which are too large for an i64 but still fit in a u64.
+    UnsignedNumber {
+        value: u64
+    },
     Float { 
         value: f64
     },
+    /// The untouched text of a number, produced when arbitrary precision is enabled.
+    RawNumber {
+        lexeme: String
+    },
     Boolean {
         value: bool
     },
     String {
         value: String
     },
+    /// An unquoted object key, only produced in relaxed mode.
+    Identifier {
+        name: String
+    },
     Null,
     Undefined
 }
@@ -44,14 +58,99 @@ impl Default for TokenType {
     }
 }
 
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenType::Reserve { reserve_id } => match reserve_id {
+                ReserveCode::OpenBrace => write!(f, "'{{'"),
+                ReserveCode::CloseBrace => write!(f, "'}}'"),
+                ReserveCode::OpenBracket => write!(f, "'['"),
+                ReserveCode::CloseBracket => write!(f, "']'"),
+                ReserveCode::Colon => write!(f, "':'"),
+                ReserveCode::Comma => write!(f, "','"),
+                ReserveCode::Undefined => write!(f, "an undefined token")
+            },
+            TokenType::Number { value } => write!(f, "number {}", value),
+            TokenType::UnsignedNumber { value } => write!(f, "number {}", value),
+            TokenType::Float { value } => write!(f, "number {}", value),
+            TokenType::RawNumber { lexeme } => write!(f, "number {}", lexeme),
+            TokenType::Boolean { value } => write!(f, "{}", value),
+            TokenType::String { value } => write!(f, "string \"{}\"", value),
+            TokenType::Identifier { name } => write!(f, "identifier {}", name),
+            TokenType::Null => write!(f, "null"),
+            TokenType::Undefined => write!(f, "the end of the file")
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Token {
-    token_type: TokenType
+    token_type: TokenType,
+    /// The byte offset of the first character of the token.
+    offset: usize
 }
 
 impl Token {
-    pub fn get_type(&self) -> TokenType {
-        self.token_type.clone()
+    pub fn get_type(&self) -> &TokenType {
+        &self.token_type
+    }
+
+    /// Lets the parser move a string out of the token instead of copying it.
+    pub(crate) fn get_type_mut(&mut self) -> &mut TokenType {
+        &mut self.token_type
+    }
+
+    /// Returns the byte offset in the source where the token starts.
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Takes the type out of the token without cloning it.
+    pub(crate) fn into_type(self) -> TokenType {
+        self.token_type
+    }
+}
+
+/// The default limit on how deeply objects and arrays may be nested.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Settings which change how a json document is lexed and parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonOptions {
+    /// Keeps every number as the text it was written with instead of converting it.
+    /// Numbers are then loaded as `JsonNode::RawNumber` and written back exactly as they were read.
+    pub arbitrary_precision: bool,
+    /// Accepts hand written json: `//` and `/* */` comments, trailing commas,
+    /// unquoted object keys and single quoted strings.
+    pub relaxed: bool,
+    /// The number of objects and arrays which may be nested inside of each other.
+    /// Deeper documents are rejected instead of overflowing the stack.
+    pub max_depth: usize,
+    /// The longest string, object key or number allowed, in bytes. Strings are measured after escapes are decoded.
+    pub max_string_length: usize,
+    /// The largest document allowed, in bytes.
+    pub max_document_size: usize
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        JsonOptions {
+            arbitrary_precision: false,
+            relaxed: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_string_length: usize::MAX,
+            max_document_size: usize::MAX
+        }
+    }
+}
+
+impl JsonOptions {
+    /// Options for hand edited files, see `JsonOptions::relaxed`.
+    pub fn relaxed() -> JsonOptions {
+        JsonOptions {
+            relaxed: true,
+            ..JsonOptions::default()
+        }
     }
 }
 
@@ -59,7 +158,8 @@ pub struct JsonLexer {
     /// All the text in the json file.
     json_text: String,
     /// the current location we are lexing in the file.
-    index: usize
+    index: usize,
+    options: JsonOptions
 }
 
 /// A lexer for a json file which returns
@@ -72,7 +172,8 @@ impl JsonLexer {
                 Ok(file_text) => file_text,
                 Err(e) => return Err(e)
             },
-            index: 0
+            index: 0,
+            options: JsonOptions::default()
         })
     }
 
@@ -88,237 +189,445 @@ impl JsonLexer {
                 Ok(json_text) => json_text,
                 Err(_) => return None
             },
-            index: 0 
+            index: 0,
+            options: JsonOptions::default()
         })
     }
 
-    pub fn next_token<'a>(&mut self, token: &'a mut Token) {
+    /// Replaces the options used when lexing and parsing the document.
+    pub fn set_options(&mut self, options: JsonOptions) {
+        self.options = options;
+    }
+
+    pub fn get_options(&self) -> &JsonOptions {
+        &self.options
+    }
+
+    /// Returns the source text being lexed.
+    pub fn source(&self) -> &str {
+        &self.json_text
+    }
+
+    /// Computes the line and column of a byte offset into the source.
+    pub fn location(&self, offset: usize) -> SourceLocation {
+        SourceLocation::from_offset(self.json_text.as_bytes(), offset)
+    }
+
+    /// Loads the next token into the given token.
+    /// When the end of the file is reached, the token type is set to undefined.
+    pub fn next_token(&mut self, token: &mut Token) -> Result<(), JsonError> {
         let size = self.json_text.len();
         let json_text = self.json_text.as_bytes();
 
-        skip_whitespace(json_text, &mut self.index, size);
+        if let Err(error) = skip_whitespace(json_text, &mut self.index, size, &self.options) {
+            token.token_type = TokenType::Undefined;
+            return Err(error);
+        }
+
+        token.offset = self.index;
 
         if is_eof(self.index, size) {
             token.token_type = TokenType::Undefined;
-            return;
+            return Ok(());
         }
 
-        let ch = json_text[self.index] as char;
+        let result = lex_token(json_text, &mut self.index, &self.options, token);
 
-        if ch.is_numeric() || ch == '-' {
-            // Load number
-            if !load_number(json_text, &mut self.index, size, token) {
-                token.token_type = TokenType::Undefined;
-                return;
-            }
+        if result.is_err() {
+            token.token_type = TokenType::Undefined;
         }
-        else if is_str_start(ch as u8) {
-            // Load string
-            if !load_string(json_text, &mut self.index, size, token) {
-                token.token_type = TokenType::Undefined;
-                return;
-            }
+
+        result
+    }
+}
+
+/// Lexes the token starting at the index, which must not be whitespace or the end of the input.
+pub(crate) fn lex_token(json: &[u8], index: &mut usize, options: &JsonOptions, token: &mut Token) -> Result<(), JsonError> {
+    let size = json.len();
+    let ch = json[*index] as char;
+
+    if ch.is_ascii_digit() || ch == '-' {
+        load_number(json, index, size, options, token)
+    }
+    else if is_str_start(ch as u8, options) {
+        load_string(json, index, size, options, token)
+    }
+    else if options.relaxed && is_identifier_char(ch as u8) {
+        load_identifier(json, index, size, options, token)
+    }
+    else if load_boolean_or_null(json, index, size, token) {
+        // load_boolean_or_null returns true if it successfully loaded a bool or null.
+        Ok(())
+    }
+    else {
+        // If it's a reserve, add it, otherwise there is an error :D
+        load_reserve(json, index, token)
+    }
+}
+
+fn is_eof(index: usize, size: usize) -> bool {
+    index >= size
+}
+
+/// Skips whitespace, along with comments in relaxed mode.
+pub(crate) fn skip_whitespace(json: &[u8], index: &mut usize, size: usize, options: &JsonOptions) -> Result<(), JsonError> {
+    while !is_eof(*index, size) {
+        if json[*index].is_ascii_whitespace() {
+            *index += 1;
         }
-        else if load_boolean_or_null(json_text, &mut self.index, size, token) {
-            // load_boolean_or_null returns true if it successfully loaded a bool or null.
+        else if options.relaxed && json[*index] == b'/' && !is_eof(*index + 1, size) && b"/*".contains(&json[*index + 1]) {
+            skip_comment(json, index, size)?;
         }
         else {
-            // If it's a reserve, add it, otherwise there is an error :D
-            if !load_reserve(json_text, &mut self.index, token) {
-                token.token_type = TokenType::Undefined;
-            }
+            break;
         }
     }
-}
 
-fn is_eof(index: usize, size: usize) -> bool {
-    index >= size
+    Ok(())
 }
 
-fn skip_whitespace(json: &[u8], index: &mut usize, size: usize) {
-    while !is_eof(*index, size) && (json[*index]).is_ascii_whitespace() {
+/// Skips a `//` comment up to the end of the line, or a `/* */` comment up to its closing marker.
+fn skip_comment(json: &[u8], index: &mut usize, size: usize) -> Result<(), JsonError> {
+    let comment_start = *index;
+    *index += 2;
+
+    if json[comment_start + 1] == b'/' {
+        while !is_eof(*index, size) && json[*index] != b'\n' {
+            *index += 1;
+        }
+
+        return Ok(());
+    }
+
+    while !is_eof(*index + 1, size) {
+        if json[*index] == b'*' && json[*index + 1] == b'/' {
+            *index += 2;
+            return Ok(());
+        }
+
         *index += 1;
     }
+
+    Err(JsonError::UnterminatedComment {
+        location: SourceLocation::from_offset(json, comment_start)
+    })
 }
 
-fn is_str_start(c: u8) -> bool {
-    c == '"' as u8
+pub(crate) fn is_str_start(c: u8, options: &JsonOptions) -> bool {
+    c == b'"' || (options.relaxed && c == b'\'')
 }
 
-fn get_integer_num(json: &[u8], index: &mut usize, size: usize) {
-    let mut ch = json[*index];
+/// Characters which can make up an unquoted key in relaxed mode.
+pub(crate) fn is_identifier_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
 
-    while (ch as char).is_numeric() && !is_eof(*index, size) {
+fn get_integer_num(json: &[u8], index: &mut usize, size: usize) {
+    while !is_eof(*index, size) && json[*index].is_ascii_digit() {
         *index += 1;
+    }
+}
 
-        if is_eof(*index, size) {
-            return;
-        }
+/// Creates an error holding the malformed number, including any characters which were
+/// likely meant to be part of it.
+fn invalid_number(json: &[u8], token_start: usize) -> JsonError {
+    let mut token_end = token_start + 1;
+
+    while !is_eof(token_end, json.len()) && (json[token_end].is_ascii_alphanumeric() || b".+-".contains(&json[token_end])) {
+        token_end += 1;
+    }
 
-        ch = json[*index];
+    JsonError::InvalidNumber {
+        lexeme: String::from_utf8_lossy(&json[token_start..token_end.min(json.len())]).to_string(),
+        location: SourceLocation::from_offset(json, token_start)
     }
 }
 
-fn load_number<'a>(json: &'a [u8], index: &mut usize, size: usize, new_token: &'a mut Token) -> bool {
-    let mut ch;
+/// Loads a number following the RFC 8259 grammar:
+/// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+fn load_number(json: &[u8], index: &mut usize, size: usize, options: &JsonOptions, new_token: &mut Token) -> Result<(), JsonError> {
+    let is_digit = |index: usize| !is_eof(index, size) && json[index].is_ascii_digit();
+    let token_start = *index;
     let mut flt = false;
 
-    let token_start = *index;
-    *index += 1;
+    if json[*index] == b'-' {
+        *index += 1;
+    }
 
-    get_integer_num(json, index, size);
+    // The integer part must have at least one digit, and only zero itself may start with a zero.
+    if !is_digit(*index) {
+        return Err(invalid_number(json, token_start));
+    }
+    else if json[*index] == b'0' {
+        *index += 1;
 
-    if !is_eof(*index, size) {
-        ch = json[*index];
+        if is_digit(*index) {
+            return Err(invalid_number(json, token_start));
+        }
+    }
+    else {
+        get_integer_num(json, index, size);
+    }
 
-        // If it has floating point notation, continue with parsing as a float.
-        if ch == '.' as u8 {
-            *index += 1;
-            flt = true;
-
-            get_integer_num(json, index, size);
-
-            if !is_eof(*index, size) {
-                ch = json[*index];
-
-                if ch == 'e' as u8 || ch == 'E' as u8 && !is_eof(*index + 1, size) {
-                    *index += 1;
-                    ch = json[*index];
-
-                    if ch == '-' as u8 || ch == '+' as u8 && !is_eof(*index + 1, size) {
-                        *index += 1;
-                        ch = json[*index];
-                    }
-                    
-                    if !(ch as char).is_numeric() {
-                        println!("ERROR: Exponential notation must have at least one digit");
-                        return false;
-                    }
-                    else {
-                        get_integer_num(json, index, size);
-                    }
-                }
-            }
+    // If it has floating point notation, continue with parsing as a float.
+    if !is_eof(*index, size) && json[*index] == b'.' {
+        *index += 1;
+        flt = true;
+
+        if !is_digit(*index) {
+            return Err(invalid_number(json, token_start));
         }
+
+        get_integer_num(json, index, size);
     }
 
-    if !flt {
-        let as_str = String::from_utf8_lossy(&json[token_start..*index]);
-        let value = i64::from_str_radix(&as_str, 10);
+    if !is_eof(*index, size) && (json[*index] == b'e' || json[*index] == b'E') {
+        *index += 1;
+        flt = true;
 
-        if let Ok(value) = value {
-            new_token.token_type = TokenType::Number { value };
-            return true;
+        if !is_eof(*index, size) && (json[*index] == b'-' || json[*index] == b'+') {
+            *index += 1;
         }
-        else {
-            println!("Error parsing integer: {}", as_str);
-            return false;
+
+        // Exponential notation must have at least one digit.
+        if !is_digit(*index) {
+            return Err(invalid_number(json, token_start));
         }
+
+        get_integer_num(json, index, size);
+    }
+
+    if *index - token_start > options.max_string_length {
+        return Err(JsonError::NumberTooLong {
+            max_length: options.max_string_length,
+            location: SourceLocation::from_offset(json, token_start)
+        });
+    }
+
+    let as_str = String::from_utf8_lossy(&json[token_start..*index]);
+
+    if options.arbitrary_precision {
+        new_token.token_type = TokenType::RawNumber { lexeme: as_str.to_string() };
+        return Ok(());
     }
-    else { 
-        let as_str = String::from_utf8_lossy(&json[token_start..*index]);
-        let value = as_str.parse::<f64>();
 
-        if let Ok(value) = value {
-            new_token.token_type = TokenType::Float { value };
-            return true;
+    if !flt {
+        // Integers are kept exact whenever they fit, otherwise they fall back to a float.
+        if let Ok(value) = as_str.parse::<i64>() {
+            new_token.token_type = TokenType::Number { value };
+            return Ok(());
         }
-        else {
-            println!("Error parsing float: {}", as_str);
-            return false;
+        else if let Ok(value) = as_str.parse::<u64>() {
+            new_token.token_type = TokenType::UnsignedNumber { value };
+            return Ok(());
         }
     }
+
+    // Json has no representation for infinity, so numbers too large for a float are rejected.
+    match as_str.parse::<f64>() {
+        Ok(value) if value.is_finite() => new_token.token_type = TokenType::Float { value },
+        _ => return Err(invalid_number(json, token_start))
+    }
+
+    Ok(())
 }
 
-fn load_string<'a>(json: &'a [u8], index: &mut usize, size: usize, new_token: &'a mut Token) -> bool {
-    let mut ch;
+fn load_string(json: &[u8], index: &mut usize, size: usize, options: &JsonOptions, new_token: &mut Token) -> Result<(), JsonError> {
+    let token_start = *index;
     let ending_quote = json[*index];
 
     *index += 1;
 
-    let token_start = *index;
-    ch = json[*index];
+    let mut value = String::new();
+    let mut segment_start = *index;
 
-    'str_contents: while ch != ending_quote && !is_eof(*index, size) {
-        if ch == '\\' as u8 {
-            *index += 1;
+    loop {
+        // Strings cannot appear on multiple lines.
+        if is_eof(*index, size) || json[*index] == b'\n' {
+            return Err(JsonError::UnterminatedString {
+                location: SourceLocation::from_offset(json, token_start)
+            });
         }
-        if ch != '\n' as u8 {
-            *index += 1;
-            ch = json[*index];
+
+        // Checked as the string is read so a huge string is rejected before it is copied.
+        if value.len() + (*index - segment_start) > options.max_string_length {
+            return Err(JsonError::StringTooLong {
+                max_length: options.max_string_length,
+                location: SourceLocation::from_offset(json, token_start)
+            });
+        }
+
+        let ch = json[*index];
+
+        if ch == ending_quote {
+            break;
+        }
+        else if ch < 0x20 {
+            return Err(JsonError::ControlCharacterInString {
+                found: ch as char,
+                location: SourceLocation::from_offset(json, *index)
+            });
+        }
+        else if ch == b'\\' {
+            // Copy everything up until the escape, then decode the escape itself.
+            value.push_str(&String::from_utf8_lossy(&json[segment_start..*index]));
+            value.push(load_escape(json, index, size, options, token_start)?);
+            segment_start = *index;
         }
         else {
-            break 'str_contents;
+            *index += 1;
         }
     }
 
-    if ch != '\n' as u8 {
-        *index += 1;
+    value.push_str(&String::from_utf8_lossy(&json[segment_start..*index]));
+    new_token.token_type = TokenType::String { value };
 
-        new_token.token_type = TokenType::String { 
-            value: match String::from_utf8_lossy(&json[token_start..*index - 1]) {
-                std::borrow::Cow::Borrowed(lex) => String::from_str(lex).unwrap(),
-                std::borrow::Cow::Owned(lex) => lex
-            }
-        };
-        true
-    }
-    else {
-        println!("Error: Strings cannot appear on multiple lines");
-        false
+    *index += 1;
+    Ok(())
+}
+
+/// Decodes the escape sequence starting at the backslash under the index.
+/// The index is left on the first character after the escape.
+fn load_escape(json: &[u8], index: &mut usize, size: usize, options: &JsonOptions, token_start: usize) -> Result<char, JsonError> {
+    let escape_start = *index;
+    *index += 1;
+
+    if is_eof(*index, size) {
+        return Err(JsonError::UnterminatedString {
+            location: SourceLocation::from_offset(json, token_start)
+        });
     }
+
+    let escaped = match json[*index] {
+        b'"' => '"',
+        b'\'' if options.relaxed => '\'',
+        b'\\' => '\\',
+        b'/' => '/',
+        b'b' => '\u{8}',
+        b'f' => '\u{c}',
+        b'n' => '\n',
+        b'r' => '\r',
+        b't' => '\t',
+        b'u' => return load_unicode_escape(json, index, size, escape_start),
+        _ => return Err(invalid_escape(json, escape_start, *index + 1))
+    };
+
+    *index += 1;
+    Ok(escaped)
 }
 
-fn load_reserve<'a>(json: &'a [u8], index: &mut usize, 
-    new_token: &'a mut Token) -> bool 
-{
-    let ch = json[*index] as char;
+/// Decodes a \uXXXX escape, combining utf-16 surrogate pairs into a single character.
+/// The index starts on the 'u' of the escape.
+fn load_unicode_escape(json: &[u8], index: &mut usize, size: usize, escape_start: usize) -> Result<char, JsonError> {
+    let high = match load_hex_code_unit(json, index, size) {
+        Some(code_unit) => code_unit,
+        None => return Err(invalid_escape(json, escape_start, *index))
+    };
+
+    if !(0xD800..=0xDFFF).contains(&high) {
+        return char::from_u32(high).ok_or_else(|| invalid_escape(json, escape_start, *index));
+    }
+
+    // A high surrogate must be immediately followed by an escaped low surrogate.
+    if high >= 0xDC00 || is_eof(*index + 1, size) || json[*index] != b'\\' || json[*index + 1] != b'u' {
+        return Err(invalid_escape(json, escape_start, *index));
+    }
+
     *index += 1;
-    
-    let mut err = false;
-    match ch {
-        '{' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::OpenBrace },
-        '}' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::CloseBrace },
-        '[' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::OpenBracket },
-        ']' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::CloseBracket },
-        ':' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::Colon },
-        ',' => new_token.token_type = TokenType::Reserve { reserve_id: ReserveCode::Comma },
-        _ => {
-            err = true;
-        }
+    let low = match load_hex_code_unit(json, index, size) {
+        Some(code_unit) if (0xDC00..=0xDFFF).contains(&code_unit) => code_unit,
+        _ => return Err(invalid_escape(json, escape_start, *index))
+    };
+
+    let code_point = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+    char::from_u32(code_point).ok_or_else(|| invalid_escape(json, escape_start, *index))
+}
+
+/// Reads the four hex digits following the 'u' under the index.
+fn load_hex_code_unit(json: &[u8], index: &mut usize, size: usize) -> Option<u32> {
+    let digits_start = *index + 1;
+    let digits_end = digits_start + 4;
+
+    if digits_end > size || !json[digits_start..digits_end].iter().all(|ch| ch.is_ascii_hexdigit()) {
+        *index = digits_start.min(size);
+        return None;
     }
 
-    return !err;
+    *index = digits_end;
+    u32::from_str_radix(std::str::from_utf8(&json[digits_start..digits_end]).ok()?, 16).ok()
 }
 
-fn load_boolean_or_null<'a>(json: &'a [u8], index: &mut usize, size: usize, new_token: &'a mut Token) -> bool {
-    let true_token = "true".as_bytes();
-    let false_token = "false".as_bytes();
-    let null_token = "null".as_bytes();
+fn invalid_escape(json: &[u8], escape_start: usize, escape_end: usize) -> JsonError {
+    let escape_end = escape_end.min(json.len());
 
-    if !is_eof(*index + true_token.len(), size) {
-        if json[*index..(*index + true_token.len())].eq(true_token) {
-            *index += true_token.len();
-            new_token.token_type = TokenType::Boolean { value: true };
-            return true
-        }
+    JsonError::InvalidEscape {
+        sequence: String::from_utf8_lossy(&json[escape_start..escape_end]).to_string(),
+        location: SourceLocation::from_offset(json, escape_start)
     }
+}
 
-    if !is_eof(*index + false_token.len(), size) {
-        if json[*index..(*index + false_token.len())].eq(false_token) {
-            *index += false_token.len();
-            new_token.token_type = TokenType::Boolean { value: false };
-            return true
+fn load_reserve(json: &[u8], index: &mut usize, new_token: &mut Token) -> Result<(), JsonError> {
+    let reserve_id = match json[*index] {
+        b'{' => ReserveCode::OpenBrace,
+        b'}' => ReserveCode::CloseBrace,
+        b'[' => ReserveCode::OpenBracket,
+        b']' => ReserveCode::CloseBracket,
+        b':' => ReserveCode::Colon,
+        b',' => ReserveCode::Comma,
+        _ => {
+            let end = (*index + 4).min(json.len());
+            return Err(JsonError::UnexpectedCharacter {
+                found: String::from_utf8_lossy(&json[*index..end]).chars().next().unwrap_or('\u{FFFD}'),
+                location: SourceLocation::from_offset(json, *index)
+            });
         }
-    }
+    };
 
-    if !is_eof(*index + null_token.len(), size) {
-        if json[*index..(*index + null_token.len())].eq(null_token) {
-            *index += null_token.len();
-            new_token.token_type = TokenType::Null;
+    *index += 1;
+    new_token.token_type = TokenType::Reserve { reserve_id };
+    Ok(())
+}
+
+fn load_boolean_or_null(json: &[u8], index: &mut usize, size: usize, new_token: &mut Token) -> bool {
+    let literals = [
+        ("true".as_bytes(), TokenType::Boolean { value: true }),
+        ("false".as_bytes(), TokenType::Boolean { value: false }),
+        ("null".as_bytes(), TokenType::Null)
+    ];
+
+    for (literal, token_type) in literals {
+        if *index + literal.len() <= size && json[*index..(*index + literal.len())].eq(literal) {
+            *index += literal.len();
+            new_token.token_type = token_type;
             return true
         }
     }
 
     false
-}
\ No newline at end of file
+}
+
+/// Loads an unquoted key, or a bool or null when the word is one of those literals.
+fn load_identifier(json: &[u8], index: &mut usize, size: usize, options: &JsonOptions, new_token: &mut Token) -> Result<(), JsonError> {
+    let token_start = *index;
+
+    while !is_eof(*index, size) && is_identifier_char(json[*index]) {
+        *index += 1;
+    }
+
+    // Unquoted keys are limited the same as quoted ones.
+    if *index - token_start > options.max_string_length {
+        return Err(JsonError::StringTooLong {
+            max_length: options.max_string_length,
+            location: SourceLocation::from_offset(json, token_start)
+        });
+    }
+
+    new_token.token_type = match &json[token_start..*index] {
+        b"true" => TokenType::Boolean { value: true },
+        b"false" => TokenType::Boolean { value: false },
+        b"null" => TokenType::Null,
+        name => TokenType::Identifier { name: String::from_utf8_lossy(name).to_string() }
+    };
+
+    Ok(())
+}
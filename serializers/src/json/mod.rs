@@ -1,3 +1,13 @@
 pub mod parser;
 pub mod lexer;
+pub mod error;
+pub mod writer;
+pub mod typed;
+pub mod reader;
+pub mod pointer;
+pub mod patch;
+pub mod schema;
+pub mod diff;
+pub mod convert;
+pub mod borrowed;
 mod tests;
\ No newline at end of file
@@ -1,33 +1,59 @@
-use std::{collections::{HashMap, hash_map::Keys}, str::FromStr};
+use std::{collections::HashMap, str::FromStr};
 
 use crate::json::lexer::*;
+use crate::json::error::JsonError;
+use crate::json::writer::{write_to_string, WriterConfig};
 
 use super::lexer;
 
-#[derive(Debug, Clone, PartialEq, Default)]
+/// A json object which remembers the order its keys were added in,
+/// so documents can be loaded and saved without reordering them.
+#[derive(Debug, Clone, Default)]
 pub struct JsonObject {
-    sub_nodes: HashMap<String, JsonNode>
+    /// The entries in insertion order.
+    sub_nodes: Vec<(String, JsonNode)>,
+    /// Maps each key to its position in sub_nodes for constant time lookups.
+    key_indices: HashMap<String, usize>
 }
 
 impl JsonObject {
     pub fn new() -> JsonObject {
-        JsonObject { sub_nodes: HashMap::new() }
+        JsonObject { sub_nodes: Vec::new(), key_indices: HashMap::new() }
     }
 
+    /// Adds a node to the end of the object.
+    /// If the key already exists, its value is replaced and it keeps its position.
     pub fn add(&mut self, name: &str, node: JsonNode) {
-        self.sub_nodes.insert(String::from_str(name).unwrap(), node);
+        match self.key_indices.get(name) {
+            Some(index) => self.sub_nodes[*index].1 = node,
+            None => {
+                self.key_indices.insert(String::from_str(name).unwrap(), self.sub_nodes.len());
+                self.sub_nodes.push((String::from_str(name).unwrap(), node));
+            }
+        }
     }
 
+    /// Removes a key from the object, keeping the order of the remaining keys.
     pub fn remove(&mut self, name: &str) -> Option<JsonNode> {
-        self.sub_nodes.remove(name)
+        let index = self.key_indices.remove(name)?;
+        let (_, node) = self.sub_nodes.remove(index);
+
+        // Everything after the removed entry moved back by one.
+        for (key, _) in &self.sub_nodes[index..] {
+            if let Some(key_index) = self.key_indices.get_mut(key) {
+                *key_index -= 1;
+            }
+        }
+
+        Some(node)
     }
 
     pub fn get(&self, name: &str) -> Option<&JsonNode> {
-        self.sub_nodes.get(name)
+        self.key_indices.get(name).map(|index| &self.sub_nodes[*index].1)
     }
 
     pub fn get_mut(&mut self, name: &str) -> Option<&mut JsonNode> {
-        self.sub_nodes.get_mut(name)
+        self.key_indices.get(name).map(|index| &mut self.sub_nodes[*index].1)
     }
 
     /// Returns the total number of elements.
@@ -37,53 +63,34 @@ impl JsonObject {
     
     /// Returns if the key exists in the object.
     pub fn contains_key(&self, name: &str) -> bool {
-        self.sub_nodes.contains_key(name)
+        self.key_indices.contains_key(name)
     }
 
-    /// Returns an iterator through all keys in the object.
-    pub fn keys(&self) -> Keys<'_, String, JsonNode> {
-        self.sub_nodes.keys()
+    /// Returns an iterator through all keys in the object in insertion order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator {
+        self.sub_nodes.iter().map(|(key, _)| key)
     }
 
-    pub fn iter(&self) -> std::collections::hash_map::Iter<String, JsonNode> {
-        self.sub_nodes.iter()
+    /// Iterates through the entries in insertion order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&String, &JsonNode)> + ExactSizeIterator {
+        self.sub_nodes.iter().map(|(key, node)| (key, node))
     }
 
-    pub fn iter_mut(&mut self) -> std::collections::hash_map::IterMut<String, JsonNode> {
-        self.sub_nodes.iter_mut()
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (&String, &mut JsonNode)> + ExactSizeIterator {
+        self.sub_nodes.iter_mut().map(|(key, node)| (&*key, node))
+    }
+}
+
+/// Objects are equal when they hold the same keys with equal values, regardless of order.
+impl PartialEq for JsonObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.size() == other.size() && self.iter().all(|(key, node)| other.get(key) == Some(node))
     }
 }
 
 impl ToString for JsonObject {
     fn to_string(&self) -> String {
-        if self.sub_nodes.len() == 0 { return "{}". to_string() }
-
-        let mut returned_string = "{".to_string();
-
-        let mut node_ittr = self.sub_nodes.iter();
-        let mut current_itt = node_ittr.next().unwrap();
-
-        loop {
-            returned_string.push_str("\"");
-            returned_string.push_str(current_itt.0);
-            returned_string.push_str("\"");
-            returned_string.push_str(":");
-            returned_string.push_str(current_itt.1.to_string().as_str());
-
-            let next_itt = node_ittr.next();
-
-            match next_itt {
-                None => break,
-                Some(next) => {
-                    returned_string.push_str(",");
-                    current_itt = next;
-                }
-            }
-        }
-
-        returned_string.push_str("}");
-
-        returned_string
+        write_to_string(WriterConfig::default(), |writer| writer.write_object(self))
     }
 }
 
@@ -109,6 +116,18 @@ impl JsonArray {
         self.sub_nodes.get_mut(index)
     }
 
+    /// Inserts an item before the given index, shifting everything after it back.
+    /// Returns false when the index is past the end of the array.
+    pub fn insert(&mut self, index: usize, node: JsonNode) -> bool {
+        if index <= self.size() {
+            self.sub_nodes.insert(index, node);
+            true
+        }
+        else {
+            false
+        }
+    }
+
     /// Removes an item from the array and returns it if it exists.
     pub fn remove(&mut self, index: usize) -> Option<JsonNode> { 
         return if index < self.size() {
@@ -134,30 +153,7 @@ impl JsonArray {
 
 impl ToString for JsonArray {
     fn to_string(&self) -> String {
-        if self.sub_nodes.len() == 0 { return "[]". to_string() }
-
-        let mut returned_string = "[".to_string();
-
-        let mut node_ittr = self.sub_nodes.iter();
-        let mut current_itt = node_ittr.next().unwrap();
-
-        loop {
-            returned_string.push_str(current_itt.to_string().as_str());
-
-            let next_itt = node_ittr.next();
-
-            match next_itt {
-                None => break,
-                Some(next) => {
-                    returned_string.push_str(",");
-                    current_itt = next;
-                }
-            }
-        }
-
-        returned_string.push_str("]");
-
-        returned_string
+        write_to_string(WriterConfig::default(), |writer| writer.write_array(self))
     }
 }
 
@@ -206,7 +202,11 @@ pub enum JsonNode {
     Object(JsonObject),
     Array(JsonArray),
     Number(JsonValue<i64>),
+    /// Holds integers above `i64::MAX`, smaller integers are always loaded as a `Number`.
+    UnsignedNumber(JsonValue<u64>),
     Float(JsonValue<f64>),
+    /// A number kept as the text it was written with.
+    RawNumber(JsonValue<String>),
     Bool(JsonValue<bool>),
     String(JsonValue<String>),
     #[default]
@@ -215,53 +215,101 @@ pub enum JsonNode {
 
 impl ToString for JsonNode {
     fn to_string(&self) -> String {
-        match self {
-            JsonNode::Object(obj) => obj.to_string(),
-            JsonNode::Array(arr) => arr.to_string(),
-            JsonNode::Number(num) => num.to_string(),
-            JsonNode::Float(num) => num.to_string(),
-            JsonNode::Bool(val) => val.to_string(),
-            JsonNode::String(str) => {
-                let mut as_string = "\"".to_string();
-                as_string.push_str(str.to_string().as_str());
-                as_string.push_str("\"");
-                as_string
-            },
-            JsonNode::Null => "null".to_string(),
-        }
+        write_to_string(WriterConfig::default(), |writer| writer.write_node(self))
     }
 }
 
-pub fn parse_json(lexer: &mut JsonLexer) -> Option<JsonNode> {
+/// Parses an entire json document from the lexer.
+/// The document must contain exactly one json node.
+pub fn parse_json(lexer: &mut JsonLexer) -> Result<JsonNode, JsonError> {
+    let max_size = lexer.get_options().max_document_size;
+
+    if lexer.source().len() > max_size {
+        return Err(JsonError::DocumentTooLarge { max_size, location: lexer.location(max_size) });
+    }
+
     let mut token = Token::default();
     lexer.reset();
-    lexer.next_token(&mut token);
+    lexer.next_token(&mut token)?;
 
     // Execute the CFG.
-    parse_node(lexer, &mut token)
+    let node = parse_node(lexer, &mut token, 0)?;
+
+    // Nothing but whitespace may follow the document.
+    lexer.next_token(&mut token)?;
+
+    match token.get_type() {
+        TokenType::Undefined => Ok(node),
+        found => Err(JsonError::TrailingData {
+            found: found.to_string(),
+            location: lexer.location(token.get_offset())
+        })
+    }
+}
+
+/// Creates the error for a token which cannot appear at the current location in the document.
+fn unexpected_token(lexer: &JsonLexer, token: &Token, expected: &'static str) -> JsonError {
+    match token.get_type() {
+        TokenType::Undefined => JsonError::UnexpectedEof {
+            expected,
+            location: lexer.location(token.get_offset())
+        },
+        found => JsonError::UnexpectedToken {
+            expected,
+            found: found.to_string(),
+            location: lexer.location(token.get_offset())
+        }
+    }
 }
 
-fn parse_node(lexer: &mut JsonLexer, token: &mut Token) -> Option<JsonNode> {
+/// Parses the node starting at the token. The depth is the number of objects and arrays the node is inside of.
+fn parse_node(lexer: &mut JsonLexer, token: &mut Token, depth: usize) -> Result<JsonNode, JsonError> {
     // At this context, we are expecting anything except for syntax tokens.
-    return match token.get_type() {
-        TokenType::Reserve { reserve_id: lexer::ReserveCode::OpenBrace } => parse_object(lexer, token),
-        TokenType::Reserve { reserve_id: lexer::ReserveCode::OpenBracket } => parse_array(lexer, token),
-        TokenType::Number { value } => Some(JsonNode::Number(JsonValue { value })),
-        TokenType::Float { value } => Some(JsonNode::Float(JsonValue { value })),
-        TokenType::Boolean { value } => Some(JsonNode::Bool(JsonValue { value })),
-        TokenType::String { value } => Some(JsonNode::String(JsonValue { value })),
-        TokenType::Null => Some(JsonNode::Null),
-        TokenType::Undefined => None,
-        _ => {
-            println!("Expected a json node");
-            None
+    match token.get_type() {
+        TokenType::Reserve { reserve_id: lexer::ReserveCode::OpenBrace } => {
+            check_depth(lexer, token, depth)?;
+            parse_object(lexer, token, depth + 1)
+        },
+        TokenType::Reserve { reserve_id: lexer::ReserveCode::OpenBracket } => {
+            check_depth(lexer, token, depth)?;
+            parse_array(lexer, token, depth + 1)
+        },
+        _ => match scalar_node(token.get_type_mut()) {
+            Some(node) => Ok(node),
+            None => Err(unexpected_token(lexer, token, "a json node"))
         }
     }
 }
 
-fn parse_object(lexer: &mut JsonLexer, token: &mut Token) -> Option<JsonNode> {
+/// Fails when an object or array starting at the token would be nested deeper than the limit.
+fn check_depth(lexer: &JsonLexer, token: &Token, depth: usize) -> Result<(), JsonError> {
+    let max_depth = lexer.get_options().max_depth;
+
+    if depth >= max_depth {
+        return Err(JsonError::NestingTooDeep { max_depth, location: lexer.location(token.get_offset()) });
+    }
+
+    Ok(())
+}
+
+/// Converts a token holding a single value into a node, moving any text out of the token.
+/// Returns None for syntax tokens and the end of the file, which are left as they were.
+pub(crate) fn scalar_node(token_type: &mut TokenType) -> Option<JsonNode> {
+    match token_type {
+        TokenType::Number { value } => Some(JsonNode::Number(JsonValue { value: *value })),
+        TokenType::UnsignedNumber { value } => Some(JsonNode::UnsignedNumber(JsonValue { value: *value })),
+        TokenType::Float { value } => Some(JsonNode::Float(JsonValue { value: *value })),
+        TokenType::RawNumber { lexeme } => Some(JsonNode::RawNumber(JsonValue { value: std::mem::take(lexeme) })),
+        TokenType::Boolean { value } => Some(JsonNode::Bool(JsonValue { value: *value })),
+        TokenType::String { value } => Some(JsonNode::String(JsonValue { value: std::mem::take(value) })),
+        TokenType::Null => Some(JsonNode::Null),
+        _ => None
+    }
+}
+
+fn parse_object(lexer: &mut JsonLexer, token: &mut Token, depth: usize) -> Result<JsonNode, JsonError> {
     // We already know we are starting with an open brace, so just get the next token.
-    lexer.next_token(token);
+    lexer.next_token(token)?;
 
     // Objects are formatted as follows: key: string: node: "json_node" 
     // Then they can have a comma and another string: node, so on.
@@ -269,90 +317,85 @@ fn parse_object(lexer: &mut JsonLexer, token: &mut Token) -> Option<JsonNode> {
 
     // Handle empty object case.
     if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBrace } = token.get_type() {
-        return Some(JsonNode::Object(obj));
+        return Ok(JsonNode::Object(obj));
     }
 
     'find_object: loop {
-        match token.get_type() {
-            TokenType::String { value } => {
-                lexer.next_token(token);
+        match token.get_type_mut() {
+            // Unquoted keys are only produced by the lexer in relaxed mode.
+            TokenType::String { value } | TokenType::Identifier { name: value } => {
+                let value = std::mem::take(value);
+                lexer.next_token(token)?;
 
                 if let TokenType::Reserve { reserve_id: lexer::ReserveCode::Colon } = token.get_type() {
-                    lexer.next_token(token);
+                    lexer.next_token(token)?;
 
                     // Load the next node.
-                    match parse_node(lexer, token) {
-                        Some(loaded_node) => {
-                            obj.add(value.as_str(), loaded_node);
-                            lexer.next_token(token);
-                        },
-                        None => return None
-                    }
+                    let loaded_node = parse_node(lexer, token, depth)?;
+                    obj.add(value.as_str(), loaded_node);
+                    lexer.next_token(token)?;
                 }
                 else {
-                    println!("Expected ':'");
-                    return None;
+                    return Err(unexpected_token(lexer, token, "':'"));
                 }
             },
-            _ => {
-                println!("Expected an object key");
-                return None;
-            }
+            _ => return Err(unexpected_token(lexer, token, "an object key"))
         }
 
         // If there's no comma, we are done finding objects.
         if let TokenType::Reserve { reserve_id: lexer::ReserveCode::Comma } = token.get_type() {
-            lexer.next_token(token);
+            lexer.next_token(token)?;
+
+            // Relaxed json allows a comma after the last entry.
+            if lexer.get_options().relaxed && *token.get_type() == (TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBrace }) {
+                break 'find_object;
+            }
         }
         else {
             break 'find_object;
         }
     }
 
-    return if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBrace } = token.get_type() {
-        Some(JsonNode::Object(obj))
+    if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBrace } = token.get_type() {
+        Ok(JsonNode::Object(obj))
     }
     else {
-        println!("Expected '}}'");
-        None
+        Err(unexpected_token(lexer, token, "',' or '}'"))
     }
 }
 
-fn parse_array(lexer: &mut JsonLexer, token: &mut Token) -> Option<JsonNode> {
+fn parse_array(lexer: &mut JsonLexer, token: &mut Token, depth: usize) -> Result<JsonNode, JsonError> {
     // We already know we are starting with an open bracket, so just get the next token.
-    lexer.next_token(token);
+    lexer.next_token(token)?;
 
     // Expect there to be a JsonNode and if there's a comma following it, expect another json node...
     let mut array = JsonArray::default();
 
     // Handle empty array case.
     if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBracket } = token.get_type() {
-        return Some(JsonNode::Array(array))
+        return Ok(JsonNode::Array(array))
     }
 
     'load_array_values: loop {
-        match parse_node(lexer, token) {
-            Some(node) => {
-                array.add(node);
-                lexer.next_token(token);
-            },
-            // If an error has been found, just break out.
-            None => return None
-        }
+        array.add(parse_node(lexer, token, depth)?);
+        lexer.next_token(token)?;
 
         if let TokenType::Reserve { reserve_id: lexer::ReserveCode::Comma } = token.get_type() {
-            lexer.next_token(token);
+            lexer.next_token(token)?;
+
+            if lexer.get_options().relaxed && *token.get_type() == (TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBracket }) {
+                break 'load_array_values;
+            }
         }
         else {
             break 'load_array_values;
         }
     }
 
-    return if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBracket } = token.get_type() {
-        Some(JsonNode::Array(array))
+    if let TokenType::Reserve { reserve_id: lexer::ReserveCode::CloseBracket } = token.get_type() {
+        Ok(JsonNode::Array(array))
     }
     else {
-        println!("Expected ']'");
-        None
+        Err(unexpected_token(lexer, token, "',' or ']'"))
     }
 }
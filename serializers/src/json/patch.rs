@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::json::parser::{JsonNode, JsonObject, JsonValue};
+use crate::json::pointer::{escape_pointer_token, parse_index, parse_pointer, resolve_tokens, resolve_tokens_mut};
+use crate::json::typed::{json_type_name, FromJson, FromJsonError, FromJsonErrorKind, ToJson};
+
+/// A single RFC 6902 json patch operation. Paths are json pointers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    /// Adds a key to an object, inserts an element into an array, or replaces the whole document.
+    /// The path "/-" appends to an array.
+    Add {
+        path: String,
+        value: JsonNode
+    },
+    Remove {
+        path: String
+    },
+    Replace {
+        path: String,
+        value: JsonNode
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        from: String,
+        path: String
+    },
+    Copy {
+        from: String,
+        path: String
+    },
+    /// Fails the patch unless the value at the path equals the given value.
+    Test {
+        path: String,
+        value: JsonNode
+    }
+}
+
+impl PatchOperation {
+    /// The name of the operation in a json patch document.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatchOperation::Add { .. } => "add",
+            PatchOperation::Remove { .. } => "remove",
+            PatchOperation::Replace { .. } => "replace",
+            PatchOperation::Move { .. } => "move",
+            PatchOperation::Copy { .. } => "copy",
+            PatchOperation::Test { .. } => "test"
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. } => path,
+            PatchOperation::Remove { path } => path,
+            PatchOperation::Replace { path, .. } => path,
+            PatchOperation::Move { path, .. } => path,
+            PatchOperation::Copy { path, .. } => path,
+            PatchOperation::Test { path, .. } => path
+        }
+    }
+}
+
+/// The reason a json patch operation could not be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchErrorKind {
+    /// The path is not a valid json pointer.
+    InvalidPointer {
+        path: String
+    },
+    /// Nothing exists at the path, or the parent of the path does not exist when adding.
+    PathNotFound {
+        path: String
+    },
+    /// A value cannot be moved into one of its own children.
+    MoveIntoChild {
+        from: String,
+        path: String
+    },
+    TestFailed {
+        path: String
+    }
+}
+
+/// An error applying a json patch, along with the index of the operation which failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchError {
+    pub operation: usize,
+    pub kind: PatchErrorKind
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "operation {}: ", self.operation)?;
+
+        match &self.kind {
+            PatchErrorKind::InvalidPointer { path } => write!(f, "invalid json pointer \"{}\"", path),
+            PatchErrorKind::PathNotFound { path } => write!(f, "no value at \"{}\"", path),
+            PatchErrorKind::MoveIntoChild { from, path } => write!(f, "cannot move \"{}\" into its own child \"{}\"", from, path),
+            PatchErrorKind::TestFailed { path } => write!(f, "test failed at \"{}\"", path)
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl JsonNode {
+    /// Applies an RFC 7396 merge patch. Objects in the patch are merged key by key,
+    /// a null removes the key and any other value replaces what was there.
+    pub fn merge_patch(&mut self, patch: &JsonNode) {
+        let patch_obj = match patch {
+            JsonNode::Object(obj) => obj,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+
+        if !matches!(self, JsonNode::Object(_)) {
+            *self = JsonNode::Object(JsonObject::new());
+        }
+
+        if let JsonNode::Object(target) = self {
+            for (key, value) in patch_obj.iter() {
+                if *value == JsonNode::Null {
+                    target.remove(key);
+                    continue;
+                }
+
+                match target.get_mut(key) {
+                    Some(existing) => existing.merge_patch(value),
+                    None => {
+                        let mut node = JsonNode::Null;
+                        node.merge_patch(value);
+                        target.add(key, node);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates the merge patch which turns this node into the target.
+    /// Merge patches cannot set an object key to null, so those keys are left out.
+    pub fn merge_diff(&self, target: &JsonNode) -> JsonNode {
+        let (source, target_obj) = match (self, target) {
+            (JsonNode::Object(source), JsonNode::Object(target_obj)) => (source, target_obj),
+            // Diffing against an empty object drops the nulls from a new object.
+            (_, JsonNode::Object(_)) => return JsonNode::Object(JsonObject::new()).merge_diff(target),
+            _ => return target.clone()
+        };
+
+        let mut patch = JsonObject::new();
+
+        for (key, _) in source.iter() {
+            if !target_obj.contains_key(key) {
+                patch.add(key, JsonNode::Null);
+            }
+        }
+
+        for (key, value) in target_obj.iter() {
+            match source.get(key) {
+                Some(existing) if values_equal(existing, value) => {},
+                Some(existing) => patch.add(key, existing.merge_diff(value)),
+                None if *value == JsonNode::Null => {},
+                None => patch.add(key, JsonNode::Null.merge_diff(value))
+            }
+        }
+
+        JsonNode::Object(patch)
+    }
+
+    /// Applies RFC 6902 json patch operations in order.
+    /// The patch is all or nothing, when an operation fails the node is left unchanged.
+    pub fn apply_patch(&mut self, operations: &[PatchOperation]) -> Result<(), PatchError> {
+        let mut patched = self.clone();
+
+        for (index, operation) in operations.iter().enumerate() {
+            apply_operation(&mut patched, operation).map_err(|kind| PatchError { operation: index, kind })?;
+        }
+
+        *self = patched;
+        Ok(())
+    }
+
+    /// Creates the json patch operations which turn this node into the target.
+    /// Arrays are compared index by index.
+    pub fn patch_diff(&self, target: &JsonNode) -> Vec<PatchOperation> {
+        let mut operations = Vec::new();
+        diff_nodes(self, target, String::new(), &mut operations);
+        operations
+    }
+}
+
+/// Compares two nodes by value. Numbers are equal when they hold the same number,
+/// no matter how they were written, ex: 1 and 1.0.
+pub fn values_equal(a: &JsonNode, b: &JsonNode) -> bool {
+    match (a, b) {
+        (JsonNode::Object(a), JsonNode::Object(b)) => {
+            a.size() == b.size() && a.iter().all(|(key, value)| b.get(key).is_some_and(|other| values_equal(value, other)))
+        },
+        (JsonNode::Array(a), JsonNode::Array(b)) => {
+            a.size() == b.size() && a.iter().zip(b.iter()).all(|(value, other)| values_equal(value, other))
+        },
+        _ if json_type_name(a) == "number" && json_type_name(b) == "number" => {
+            if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+                a == b
+            }
+            else if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+                a == b
+            }
+            else {
+                a.as_f64() == b.as_f64()
+            }
+        },
+        _ => a == b
+    }
+}
+
+fn apply_operation(node: &mut JsonNode, operation: &PatchOperation) -> Result<(), PatchErrorKind> {
+    match operation {
+        PatchOperation::Add { path, value } => add_value(node, path, value.clone()),
+        PatchOperation::Remove { path } => remove_value(node, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let target = resolve_tokens_mut(node, &pointer_tokens(path)?).ok_or_else(|| path_not_found(path))?;
+            *target = value.clone();
+            Ok(())
+        },
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(PatchErrorKind::MoveIntoChild { from: from.clone(), path: path.clone() });
+            }
+
+            let value = remove_value(node, from)?;
+            add_value(node, path, value)
+        },
+        PatchOperation::Copy { from, path } => {
+            let value = resolve_tokens(node, &pointer_tokens(from)?).ok_or_else(|| path_not_found(from))?.clone();
+            add_value(node, path, value)
+        },
+        PatchOperation::Test { path, value } => {
+            let existing = resolve_tokens(node, &pointer_tokens(path)?).ok_or_else(|| path_not_found(path))?;
+
+            if values_equal(existing, value) {
+                Ok(())
+            }
+            else {
+                Err(PatchErrorKind::TestFailed { path: path.clone() })
+            }
+        }
+    }
+}
+
+fn add_value(node: &mut JsonNode, path: &str, value: JsonNode) -> Result<(), PatchErrorKind> {
+    let mut tokens = pointer_tokens(path)?;
+
+    let last = match tokens.pop() {
+        Some(last) => last,
+        None => {
+            *node = value;
+            return Ok(());
+        }
+    };
+
+    match resolve_tokens_mut(node, &tokens) {
+        Some(JsonNode::Object(obj)) => {
+            obj.add(&last, value);
+            Ok(())
+        },
+        Some(JsonNode::Array(array)) => {
+            let index = if last == "-" { Some(array.size()) } else { parse_index(&last) };
+
+            match index {
+                Some(index) if array.insert(index, value) => Ok(()),
+                _ => Err(path_not_found(path))
+            }
+        },
+        _ => Err(path_not_found(path))
+    }
+}
+
+fn remove_value(node: &mut JsonNode, path: &str) -> Result<JsonNode, PatchErrorKind> {
+    let mut tokens = pointer_tokens(path)?;
+
+    // The whole document cannot be removed.
+    let last = tokens.pop().ok_or_else(|| path_not_found(path))?;
+
+    let removed = match resolve_tokens_mut(node, &tokens) {
+        Some(JsonNode::Object(obj)) => obj.remove(&last),
+        Some(JsonNode::Array(array)) => parse_index(&last).and_then(|index| array.remove(index)),
+        _ => None
+    };
+
+    removed.ok_or_else(|| path_not_found(path))
+}
+
+fn pointer_tokens(path: &str) -> Result<Vec<String>, PatchErrorKind> {
+    parse_pointer(path).ok_or_else(|| PatchErrorKind::InvalidPointer { path: path.to_string() })
+}
+
+fn path_not_found(path: &str) -> PatchErrorKind {
+    PatchErrorKind::PathNotFound { path: path.to_string() }
+}
+
+fn diff_nodes(source: &JsonNode, target: &JsonNode, path: String, operations: &mut Vec<PatchOperation>) {
+    if values_equal(source, target) {
+        return;
+    }
+
+    match (source, target) {
+        (JsonNode::Object(source), JsonNode::Object(target)) => {
+            for (key, value) in source.iter() {
+                let child_path = format!("{}/{}", path, escape_pointer_token(key));
+
+                match target.get(key) {
+                    Some(target_value) => diff_nodes(value, target_value, child_path, operations),
+                    None => operations.push(PatchOperation::Remove { path: child_path })
+                }
+            }
+
+            for (key, value) in target.iter() {
+                if !source.contains_key(key) {
+                    operations.push(PatchOperation::Add {
+                        path: format!("{}/{}", path, escape_pointer_token(key)),
+                        value: value.clone()
+                    });
+                }
+            }
+        },
+        (JsonNode::Array(source), JsonNode::Array(target)) => {
+            for (index, (value, target_value)) in source.iter().zip(target.iter()).enumerate() {
+                diff_nodes(value, target_value, format!("{}/{}", path, index), operations);
+            }
+
+            // Elements are removed from the back so the indices of the others do not change.
+            for index in (target.size()..source.size()).rev() {
+                operations.push(PatchOperation::Remove { path: format!("{}/{}", path, index) });
+            }
+
+            for (index, value) in target.iter().enumerate().skip(source.size()) {
+                operations.push(PatchOperation::Add { path: format!("{}/{}", path, index), value: value.clone() });
+            }
+        },
+        _ => operations.push(PatchOperation::Replace { path, value: target.clone() })
+    }
+}
+
+impl ToJson for PatchOperation {
+    fn to_json(&self) -> JsonNode {
+        let mut object = JsonObject::new();
+        object.add("op", JsonNode::String(JsonValue::new(self.name().to_string())));
+
+        if let PatchOperation::Move { from, .. } | PatchOperation::Copy { from, .. } = self {
+            object.add("from", from.to_json());
+        }
+
+        object.add("path", self.path().to_json());
+
+        if let PatchOperation::Add { value, .. } | PatchOperation::Replace { value, .. } | PatchOperation::Test { value, .. } = self {
+            object.add("value", value.clone());
+        }
+
+        JsonNode::Object(object)
+    }
+}
+
+impl FromJson for PatchOperation {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        let object = match node {
+            JsonNode::Object(object) => object,
+            _ => return Err(FromJsonError::unexpected_type("object", node))
+        };
+
+        let load_string = |key: &str| match object.get(key) {
+            Some(value) => String::from_json(value).map_err(|e| e.with_key(key)),
+            None => Err(FromJsonError::missing_field(key))
+        };
+
+        // Any json value is allowed, including null, but the key must be present.
+        let load_value = || object.get("value").cloned().ok_or_else(|| FromJsonError::missing_field("value"));
+
+        let op = load_string("op")?;
+
+        match op.as_str() {
+            "add" => Ok(PatchOperation::Add { path: load_string("path")?, value: load_value()? }),
+            "remove" => Ok(PatchOperation::Remove { path: load_string("path")? }),
+            "replace" => Ok(PatchOperation::Replace { path: load_string("path")?, value: load_value()? }),
+            "move" => Ok(PatchOperation::Move { from: load_string("from")?, path: load_string("path")? }),
+            "copy" => Ok(PatchOperation::Copy { from: load_string("from")?, path: load_string("path")? }),
+            "test" => Ok(PatchOperation::Test { path: load_string("path")?, value: load_value()? }),
+            _ => Err(FromJsonError::new(FromJsonErrorKind::UnknownVariant { found: op }).with_key("op"))
+        }
+    }
+}
+
+/// A document built by merge patching layers over each other, which remembers the layer
+/// that set each value, ex: a base config with per platform and per developer overrides.
+#[derive(Debug, Clone, Default)]
+pub struct LayeredDocument {
+    node: JsonNode,
+    layer_names: Vec<String>,
+    /// The index of the layer which set each value, by json pointer.
+    sources: HashMap<String, usize>
+}
+
+impl LayeredDocument {
+    pub fn new() -> LayeredDocument {
+        LayeredDocument::default()
+    }
+
+    /// Merge patches the layer over all of the layers added before it.
+    pub fn add_layer(&mut self, name: &str, layer: &JsonNode) {
+        let layer_index = self.layer_names.len();
+        self.layer_names.push(name.to_string());
+
+        merge_traced(&mut self.node, layer, String::new(), layer_index, &mut self.sources);
+    }
+
+    /// The merged document.
+    pub fn node(&self) -> &JsonNode {
+        &self.node
+    }
+
+    pub fn into_node(self) -> JsonNode {
+        self.node
+    }
+
+    pub fn layer_names(&self) -> &[String] {
+        &self.layer_names
+    }
+
+    /// Returns the name of the layer which set the value at the pointer.
+    /// Arrays are replaced as a whole, so their elements report the layer which set the array.
+    pub fn source_of(&self, pointer: &str) -> Option<&str> {
+        let mut pointer = pointer;
+
+        loop {
+            if let Some(layer) = self.sources.get(pointer) {
+                return Some(&self.layer_names[*layer]);
+            }
+
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+
+    /// Lists every value in the document which is not an object, along with the layer which set it.
+    pub fn sources(&self) -> Vec<(String, &str)> {
+        let mut sources = Vec::new();
+        self.collect_sources(&self.node, String::new(), &mut sources);
+        sources
+    }
+
+    fn collect_sources<'a>(&'a self, node: &JsonNode, path: String, sources: &mut Vec<(String, &'a str)>) {
+        if let JsonNode::Object(obj) = node {
+            for (key, value) in obj.iter() {
+                self.collect_sources(value, format!("{}/{}", path, escape_pointer_token(key)), sources);
+            }
+        }
+        else if let Some(layer) = self.source_of(&path) {
+            sources.push((path, layer));
+        }
+    }
+}
+
+/// Merge patches the node while recording the layer which set each value.
+fn merge_traced(target: &mut JsonNode, patch: &JsonNode, path: String, layer: usize, sources: &mut HashMap<String, usize>) {
+    let patch_obj = match patch {
+        JsonNode::Object(obj) => obj,
+        _ => {
+            *target = patch.clone();
+            forget_sources(sources, &path);
+            sources.insert(path, layer);
+            return;
+        }
+    };
+
+    if !matches!(target, JsonNode::Object(_)) {
+        *target = JsonNode::Object(JsonObject::new());
+        forget_sources(sources, &path);
+    }
+
+    if let JsonNode::Object(target) = target {
+        for (key, value) in patch_obj.iter() {
+            let child_path = format!("{}/{}", path, escape_pointer_token(key));
+
+            if *value == JsonNode::Null {
+                target.remove(key);
+                forget_sources(sources, &child_path);
+                continue;
+            }
+
+            if !target.contains_key(key) {
+                target.add(key, JsonNode::Null);
+            }
+
+            if let Some(child) = target.get_mut(key) {
+                merge_traced(child, value, child_path, layer, sources);
+            }
+        }
+    }
+}
+
+/// Removes the records for a value and everything inside of it.
+fn forget_sources(sources: &mut HashMap<String, usize>, path: &str) {
+    let child_prefix = format!("{}/", path);
+    sources.retain(|pointer, _| pointer != path && !pointer.starts_with(&child_prefix));
+}
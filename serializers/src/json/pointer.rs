@@ -0,0 +1,234 @@
+use crate::json::parser::{JsonNode, JsonValueOps};
+
+/// A node found by `JsonNode::query` along with the json pointer it was found at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerMatch<'a> {
+    pub path: String,
+    pub node: &'a JsonNode
+}
+
+impl JsonNode {
+    /// Finds a node by its RFC 6901 json pointer, ex: "/graphics/clear_color/r".
+    /// The empty pointer is the node itself. Returns None when any part of the path does not exist.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonNode> {
+        resolve_tokens(self, &parse_pointer(pointer)?)
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonNode> {
+        resolve_tokens_mut(self, &parse_pointer(pointer)?)
+    }
+
+    /// Finds every node matching a json pointer where a `*` token matches all keys or elements,
+    /// ex: "/resources/textures/*". Matches are returned in document order.
+    pub fn query(&self, path: &str) -> Vec<PointerMatch<'_>> {
+        let mut matches = Vec::new();
+
+        if let Some(tokens) = parse_pointer(path) {
+            query_tokens(self, &tokens, String::new(), &mut matches);
+        }
+
+        matches
+    }
+
+    /// Returns the value as a float. Integers are converted, which may round very large values.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonNode::Number(num) => Some(*num.get() as f64),
+            JsonNode::UnsignedNumber(num) => Some(*num.get() as f64),
+            JsonNode::Float(num) => Some(*num.get()),
+            JsonNode::RawNumber(num) => num.get().parse::<f64>().ok(),
+            _ => None
+        }
+    }
+
+    /// Returns the value as an integer.
+    /// Floats are only converted when they have no fractional part and fit in an i64.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonNode::Number(num) => Some(*num.get()),
+            JsonNode::UnsignedNumber(num) => i64::try_from(*num.get()).ok(),
+            JsonNode::Float(num) => float_to_i64(*num.get()),
+            JsonNode::RawNumber(num) => num.get().parse::<i64>().ok()
+                .or_else(|| float_to_i64(num.get().parse::<f64>().ok()?)),
+            _ => None
+        }
+    }
+
+    /// Returns the value as an unsigned integer, with the same rules as `as_i64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonNode::Number(num) => u64::try_from(*num.get()).ok(),
+            JsonNode::UnsignedNumber(num) => Some(*num.get()),
+            JsonNode::Float(num) => float_to_u64(*num.get()),
+            JsonNode::RawNumber(num) => num.get().parse::<u64>().ok()
+                .or_else(|| float_to_u64(num.get().parse::<f64>().ok()?)),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonNode::String(value) => Some(value.get()),
+            _ => None
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonNode::Bool(value) => Some(*value.get()),
+            _ => None
+        }
+    }
+
+    /// Finds a number by json pointer, see `as_f64`.
+    pub fn get_f64(&self, pointer: &str) -> Option<f64> {
+        self.pointer(pointer)?.as_f64()
+    }
+
+    /// Finds an integer by json pointer, see `as_i64`.
+    pub fn get_i64(&self, pointer: &str) -> Option<i64> {
+        self.pointer(pointer)?.as_i64()
+    }
+
+    /// Finds an unsigned integer by json pointer, see `as_u64`.
+    pub fn get_u64(&self, pointer: &str) -> Option<u64> {
+        self.pointer(pointer)?.as_u64()
+    }
+
+    pub fn get_str(&self, pointer: &str) -> Option<&str> {
+        self.pointer(pointer)?.as_str()
+    }
+
+    pub fn get_bool(&self, pointer: &str) -> Option<bool> {
+        self.pointer(pointer)?.as_bool()
+    }
+}
+
+/// Escapes a key so it can be used as a single token of a json pointer.
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Splits a json pointer into its unescaped tokens.
+/// Returns None when the pointer does not start with a slash or has an invalid escape.
+pub fn parse_pointer(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+
+    pointer.strip_prefix('/')?
+        .split('/')
+        .map(unescape_pointer_token)
+        .collect()
+}
+
+fn unescape_pointer_token(token: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '~' {
+            unescaped.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('0') => unescaped.push('~'),
+            Some('1') => unescaped.push('/'),
+            _ => return None
+        }
+    }
+
+    Some(unescaped)
+}
+
+/// Finds the node at the end of a list of unescaped pointer tokens.
+pub(crate) fn resolve_tokens<'a>(node: &'a JsonNode, tokens: &[String]) -> Option<&'a JsonNode> {
+    let mut node = node;
+
+    for token in tokens {
+        node = match node {
+            JsonNode::Object(obj) => obj.get(token)?,
+            JsonNode::Array(array) => array.get(parse_index(token)?)?,
+            _ => return None
+        };
+    }
+
+    Some(node)
+}
+
+pub(crate) fn resolve_tokens_mut<'a>(node: &'a mut JsonNode, tokens: &[String]) -> Option<&'a mut JsonNode> {
+    let mut node = node;
+
+    for token in tokens {
+        node = match node {
+            JsonNode::Object(obj) => obj.get_mut(token)?,
+            JsonNode::Array(array) => array.get_mut(parse_index(token)?)?,
+            _ => return None
+        };
+    }
+
+    Some(node)
+}
+
+/// Array indices are written in decimal without leading zeros.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || !token.bytes().all(|ch| ch.is_ascii_digit()) || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    token.parse::<usize>().ok()
+}
+
+// The max bounds are exclusive since the integer max rounds up when converted to a float.
+fn float_to_i64(value: f64) -> Option<i64> {
+    if value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+        Some(value as i64)
+    }
+    else {
+        None
+    }
+}
+
+fn float_to_u64(value: f64) -> Option<u64> {
+    if value.fract() == 0.0 && value >= 0.0 && value < u64::MAX as f64 {
+        Some(value as u64)
+    }
+    else {
+        None
+    }
+}
+
+fn query_tokens<'a>(node: &'a JsonNode, tokens: &[String], path: String, matches: &mut Vec<PointerMatch<'a>>) {
+    let (token, rest) = match tokens.split_first() {
+        Some(split) => split,
+        None => {
+            matches.push(PointerMatch { path, node });
+            return;
+        }
+    };
+
+    match node {
+        JsonNode::Object(obj) if token == "*" => {
+            for (key, child) in obj.iter() {
+                query_tokens(child, rest, format!("{}/{}", path, escape_pointer_token(key)), matches);
+            }
+        },
+        JsonNode::Array(array) if token == "*" => {
+            for (index, child) in array.iter().enumerate() {
+                query_tokens(child, rest, format!("{}/{}", path, index), matches);
+            }
+        },
+        JsonNode::Object(obj) => {
+            if let Some(child) = obj.get(token) {
+                query_tokens(child, rest, format!("{}/{}", path, escape_pointer_token(token)), matches);
+            }
+        },
+        JsonNode::Array(array) => {
+            if let Some(child) = parse_index(token).and_then(|index| array.get(index)) {
+                query_tokens(child, rest, format!("{}/{}", path, token), matches);
+            }
+        },
+        _ => {}
+    }
+}
@@ -0,0 +1,514 @@
+use std::io::{ErrorKind, Read};
+
+use crate::json::error::{JsonError, SourceLocation};
+use crate::json::lexer::{self, JsonOptions, ReserveCode, Token, TokenType};
+use crate::json::parser::{self, JsonArray, JsonNode, JsonObject};
+use crate::json::pointer::escape_pointer_token;
+
+/// The default number of bytes read from the input at once.
+pub const DEFAULT_BUFFER_SIZE: usize = 8192;
+
+/// A single step through a json document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    /// The key of the next value in an object.
+    Key(String),
+    /// A string, number, bool or null.
+    Value(JsonNode)
+}
+
+/// What is expected next inside of an object or array.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expect {
+    /// Right after the opening brace or bracket.
+    FirstOrEnd,
+    /// After a comma.
+    Next,
+    /// An object key was read, the value comes next.
+    Value,
+    /// After a complete value.
+    CommaOrEnd
+}
+
+#[derive(Debug)]
+struct Frame {
+    is_object: bool,
+    expect: Expect,
+    /// The most recent key of an object.
+    key: Option<String>,
+    /// The number of values started in an array.
+    count: usize
+}
+
+/// Reads a json document from a stream one event at a time.
+/// Only a fixed size buffer of the input is held in memory, plus the token currently being read,
+/// so documents of any size can be walked. Selected parts can be loaded as nodes with `read_node`.
+pub struct JsonReader<R: Read> {
+    input: R,
+    buffer: Vec<u8>,
+    /// The next unread byte in the buffer.
+    pos: usize,
+    /// The number of valid bytes in the buffer.
+    filled: usize,
+    /// The location of the next unread byte in the document.
+    location: SourceLocation,
+    stack: Vec<Frame>,
+    root_done: bool,
+    options: JsonOptions,
+    /// The bytes of the token being lexed.
+    token_bytes: Vec<u8>
+}
+
+impl<R: Read> JsonReader<R> {
+    pub fn new(input: R) -> JsonReader<R> {
+        JsonReader::with_capacity(input, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Creates a reader which reads at most the given number of bytes from the input at once.
+    pub fn with_capacity(input: R, capacity: usize) -> JsonReader<R> {
+        JsonReader {
+            input,
+            buffer: vec![0; capacity.max(1)],
+            pos: 0,
+            filled: 0,
+            location: SourceLocation { offset: 0, line: 1, column: 1 },
+            stack: Vec::new(),
+            root_done: false,
+            options: JsonOptions::default(),
+            token_bytes: Vec::new()
+        }
+    }
+
+    pub fn set_options(&mut self, options: JsonOptions) {
+        self.options = options;
+    }
+
+    pub fn get_options(&self) -> &JsonOptions {
+        &self.options
+    }
+
+    /// Returns the location of the next unread character.
+    pub fn location(&self) -> SourceLocation {
+        self.location
+    }
+
+    /// The number of objects and arrays the reader is currently inside of.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Returns the json pointer of the value the last event belongs to, ex: "/resources/textures/0".
+    pub fn current_pointer(&self) -> String {
+        let mut pointer = String::new();
+
+        for frame in self.stack.iter() {
+            if frame.is_object {
+                if let Some(key) = &frame.key {
+                    pointer.push('/');
+                    pointer.push_str(&escape_pointer_token(key));
+                }
+            }
+            else if frame.count > 0 {
+                pointer.push('/');
+                pointer.push_str(&(frame.count - 1).to_string());
+            }
+        }
+
+        pointer
+    }
+
+    /// Reads the next event in the document.
+    /// Returns None once the document is complete and nothing but whitespace follows it.
+    pub fn next_event(&mut self) -> Result<Option<JsonEvent>, JsonError> {
+        let relaxed = self.options.relaxed;
+
+        loop {
+            let (token, start) = self.read_token()?;
+
+            let frame = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None if self.root_done => return match token.get_type() {
+                    TokenType::Undefined => Ok(None),
+                    found => Err(JsonError::TrailingData { found: found.to_string(), location: start })
+                },
+                None => return self.start_value(token, start).map(Some)
+            };
+
+            // Relaxed json allows a comma after the last entry.
+            let can_end = match frame.expect {
+                Expect::FirstOrEnd | Expect::CommaOrEnd => true,
+                Expect::Next => relaxed,
+                Expect::Value => false
+            };
+
+            let token_type = token.get_type();
+
+            return match (frame.is_object, frame.expect, token_type) {
+                (true, _, TokenType::Reserve { reserve_id: ReserveCode::CloseBrace }) if can_end => {
+                    self.end_container();
+                    Ok(Some(JsonEvent::EndObject))
+                },
+                (false, _, TokenType::Reserve { reserve_id: ReserveCode::CloseBracket }) if can_end => {
+                    self.end_container();
+                    Ok(Some(JsonEvent::EndArray))
+                },
+                (_, Expect::CommaOrEnd, TokenType::Reserve { reserve_id: ReserveCode::Comma }) => {
+                    frame.expect = Expect::Next;
+                    continue;
+                },
+                // Unquoted keys are only produced by the lexer in relaxed mode.
+                (true, Expect::FirstOrEnd | Expect::Next, TokenType::String { value } | TokenType::Identifier { name: value }) => {
+                    frame.key = Some(value.clone());
+                    self.read_colon()?;
+                    Ok(Some(JsonEvent::Key(value.clone())))
+                },
+                (true, Expect::FirstOrEnd | Expect::Next, _) => Err(unexpected_token(token_type, start, "an object key")),
+                (true, Expect::CommaOrEnd, _) => Err(unexpected_token(token_type, start, "',' or '}'")),
+                (false, Expect::CommaOrEnd, _) => Err(unexpected_token(token_type, start, "',' or ']'")),
+                _ => self.start_value(token, start).map(Some)
+            };
+        }
+    }
+
+    /// Reads the next value and everything inside of it as a node.
+    /// Returns None when the enclosing object or array ends, or the document is complete.
+    /// A key before the value is passed over, use `next_event` to see keys.
+    pub fn read_node(&mut self) -> Result<Option<JsonNode>, JsonError> {
+        let mut event = self.next_event()?;
+
+        if let Some(JsonEvent::Key(_)) = event {
+            event = self.next_event()?;
+        }
+
+        match event {
+            None | Some(JsonEvent::EndObject) | Some(JsonEvent::EndArray) => Ok(None),
+            Some(event) => self.materialize(event).map(Some)
+        }
+    }
+
+    /// Reads past the next value without loading it.
+    /// Returns false when there was no value to skip, in the same cases `read_node` returns None.
+    pub fn skip_value(&mut self) -> Result<bool, JsonError> {
+        let mut event = self.next_event()?;
+
+        if let Some(JsonEvent::Key(_)) = event {
+            event = self.next_event()?;
+        }
+
+        match event {
+            None | Some(JsonEvent::EndObject) | Some(JsonEvent::EndArray) => Ok(false),
+            Some(JsonEvent::StartObject) | Some(JsonEvent::StartArray) => {
+                let depth = self.stack.len();
+
+                while self.stack.len() >= depth {
+                    self.next_event()?;
+                }
+
+                Ok(true)
+            },
+            Some(_) => Ok(true)
+        }
+    }
+
+    /// Builds the node which starts with the given event.
+    fn materialize(&mut self, first: JsonEvent) -> Result<JsonNode, JsonError> {
+        // The containers being built along with the key of the value they are waiting for.
+        let mut containers: Vec<(JsonNode, Option<String>)> = Vec::new();
+        let mut event = first;
+
+        loop {
+            let complete = match event {
+                JsonEvent::StartObject => {
+                    containers.push((JsonNode::Object(JsonObject::new()), None));
+                    None
+                },
+                JsonEvent::StartArray => {
+                    containers.push((JsonNode::Array(JsonArray::default()), None));
+                    None
+                },
+                JsonEvent::Key(key) => {
+                    if let Some((_, pending_key)) = containers.last_mut() {
+                        *pending_key = Some(key);
+                    }
+                    None
+                },
+                JsonEvent::EndObject | JsonEvent::EndArray => containers.pop().map(|(node, _)| node),
+                JsonEvent::Value(node) => Some(node)
+            };
+
+            if let Some(node) = complete {
+                match containers.last_mut() {
+                    None => return Ok(node),
+                    Some((JsonNode::Object(obj), key)) => obj.add(&key.take().unwrap_or_default(), node),
+                    Some((JsonNode::Array(array), _)) => array.add(node),
+                    Some(_) => {}
+                }
+            }
+
+            // The reader only finishes the document once every container is closed.
+            event = match self.next_event()? {
+                Some(event) => event,
+                None => return Ok(JsonNode::Null)
+            };
+        }
+    }
+
+    /// Starts the value held by the token. Objects and arrays are entered.
+    fn start_value(&mut self, token: Token, start: SourceLocation) -> Result<JsonEvent, JsonError> {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.expect = Expect::CommaOrEnd;
+            frame.count += 1;
+        }
+
+        let mut token_type = token.into_type();
+
+        if matches!(token_type, TokenType::Reserve { reserve_id: ReserveCode::OpenBrace | ReserveCode::OpenBracket })
+            && self.stack.len() >= self.options.max_depth {
+            return Err(JsonError::NestingTooDeep { max_depth: self.options.max_depth, location: start });
+        }
+
+        match token_type {
+            TokenType::Reserve { reserve_id: ReserveCode::OpenBrace } => {
+                self.stack.push(Frame { is_object: true, expect: Expect::FirstOrEnd, key: None, count: 0 });
+                Ok(JsonEvent::StartObject)
+            },
+            TokenType::Reserve { reserve_id: ReserveCode::OpenBracket } => {
+                self.stack.push(Frame { is_object: false, expect: Expect::FirstOrEnd, key: None, count: 0 });
+                Ok(JsonEvent::StartArray)
+            },
+            _ => match parser::scalar_node(&mut token_type) {
+                Some(node) => {
+                    self.root_done = self.stack.is_empty();
+                    Ok(JsonEvent::Value(node))
+                },
+                None => Err(unexpected_token(&token_type, start, "a json node"))
+            }
+        }
+    }
+
+    fn end_container(&mut self) {
+        self.stack.pop();
+        self.root_done = self.stack.is_empty();
+    }
+
+    fn read_colon(&mut self) -> Result<(), JsonError> {
+        let (token, start) = self.read_token()?;
+
+        match token.get_type() {
+            TokenType::Reserve { reserve_id: ReserveCode::Colon } => {
+                if let Some(frame) = self.stack.last_mut() {
+                    frame.expect = Expect::Value;
+                }
+                Ok(())
+            },
+            token_type => Err(unexpected_token(token_type, start, "':'"))
+        }
+    }
+
+    /// Reads the next token from the input along with where it starts.
+    /// The bytes of the token are gathered first so the lexer can work on them as a whole.
+    fn read_token(&mut self) -> Result<(Token, SourceLocation), JsonError> {
+        loop {
+            match self.peek_byte()? {
+                Some(ch) if ch.is_ascii_whitespace() => self.advance(),
+                Some(b'/') if self.options.relaxed => self.skip_comment()?,
+                _ => break
+            }
+        }
+
+        let start = self.location;
+        let mut token = Token::default();
+
+        let first = match self.peek_byte()? {
+            Some(ch) => ch,
+            None => return Ok((token, start))
+        };
+
+        self.token_bytes.clear();
+        self.take_byte(first);
+
+        if lexer::is_str_start(first, &self.options) {
+            self.gather_string(first, start)?;
+        }
+        else {
+            // Numbers and words are limited like strings. One byte past the limit is enough for the lexer
+            // to report the token as too long, so a huge token is not buffered.
+            let max_length = self.options.max_string_length.saturating_add(1);
+
+            if first.is_ascii_digit() || first == b'-' {
+                self.gather_while(|ch| ch.is_ascii_alphanumeric() || b".+-".contains(&ch), max_length)?;
+            }
+            else if lexer::is_identifier_char(first) {
+                self.gather_while(lexer::is_identifier_char, max_length)?;
+            }
+            else {
+                // Keep the whole character so errors can show it, which is at most four bytes.
+                self.gather_while(|ch| (ch & 0xC0) == 0x80, 4)?;
+            }
+        }
+
+        let mut index = 0;
+        lexer::lex_token(&self.token_bytes, &mut index, &self.options, &mut token)
+            .map_err(|error| error.relative_to(start))?;
+
+        // Anything the lexer did not use, ex: "truex", is not a valid token.
+        if index < self.token_bytes.len() {
+            let rest = String::from_utf8_lossy(&self.token_bytes[index..]);
+
+            let error = JsonError::UnexpectedCharacter {
+                found: rest.chars().next().unwrap_or('\u{FFFD}'),
+                location: SourceLocation::from_offset(&self.token_bytes, index)
+            };
+
+            return Err(error.relative_to(start));
+        }
+
+        Ok((token, start))
+    }
+
+    /// Skips a `//` or `/* */` comment starting at the slash under the reader.
+    fn skip_comment(&mut self) -> Result<(), JsonError> {
+        let start = self.location;
+        self.advance();
+
+        match self.peek_byte()? {
+            Some(b'/') => {
+                while let Some(ch) = self.peek_byte()? {
+                    if ch == b'\n' {
+                        break;
+                    }
+                    self.advance();
+                }
+            },
+            Some(b'*') => {
+                self.advance();
+                let mut star = false;
+
+                loop {
+                    match self.peek_byte()? {
+                        Some(b'/') if star => break,
+                        Some(ch) => star = ch == b'*',
+                        None => return Err(JsonError::UnterminatedComment { location: start })
+                    }
+                    self.advance();
+                }
+
+                self.advance();
+            },
+            // A lone slash is not valid json in any mode.
+            _ => return Err(JsonError::UnexpectedCharacter { found: '/', location: start })
+        }
+
+        Ok(())
+    }
+
+    /// Gathers a string up to and including its closing quote.
+    /// Strings end early at a new line or the end of the input, which the lexer reports.
+    fn gather_string(&mut self, quote: u8, start: SourceLocation) -> Result<(), JsonError> {
+        // The lexer checks the decoded length, but the raw text is capped here so a huge string is not buffered.
+        // An escape is at most six bytes for each decoded byte, plus the quotes.
+        let max_raw_length = self.options.max_string_length.saturating_mul(6).saturating_add(2);
+
+        while let Some(ch) = self.peek_byte()? {
+            if ch == b'\n' {
+                break;
+            }
+
+            if self.token_bytes.len() >= max_raw_length {
+                return Err(JsonError::StringTooLong { max_length: self.options.max_string_length, location: start });
+            }
+
+            self.take_byte(ch);
+
+            if ch == quote {
+                break;
+            }
+
+            if ch == b'\\' {
+                match self.peek_byte()? {
+                    Some(escaped) if escaped != b'\n' => self.take_byte(escaped),
+                    _ => break
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gathers bytes while they match, stopping once the token holds the maximum length.
+    fn gather_while<F: Fn(u8) -> bool>(&mut self, matches: F, max_length: usize) -> Result<(), JsonError> {
+        while let Some(ch) = self.peek_byte()? {
+            if !matches(ch) || self.token_bytes.len() >= max_length {
+                break;
+            }
+            self.take_byte(ch);
+        }
+
+        Ok(())
+    }
+
+    fn take_byte(&mut self, ch: u8) {
+        self.token_bytes.push(ch);
+        self.advance();
+    }
+
+    /// Returns the next unread byte, reading more of the input when the buffer is used up.
+    fn peek_byte(&mut self) -> Result<Option<u8>, JsonError> {
+        if self.pos == self.filled {
+            self.pos = 0;
+            self.filled = 0;
+
+            loop {
+                match self.input.read(&mut self.buffer) {
+                    Ok(read) => {
+                        self.filled = read;
+                        break;
+                    },
+                    Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(JsonError::Io {
+                        kind: error.kind(),
+                        message: error.to_string(),
+                        location: self.location
+                    })
+                }
+            }
+        }
+
+        if self.pos == self.filled {
+            return Ok(None);
+        }
+
+        if self.location.offset >= self.options.max_document_size {
+            return Err(JsonError::DocumentTooLarge { max_size: self.options.max_document_size, location: self.location });
+        }
+
+        Ok(Some(self.buffer[self.pos]))
+    }
+
+    /// Moves past the byte returned by `peek_byte`.
+    fn advance(&mut self) {
+        let ch = self.buffer[self.pos];
+        self.pos += 1;
+        self.location.offset += 1;
+
+        if ch == b'\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        }
+        else if (ch & 0xC0) != 0x80 {
+            // Utf-8 continuation bytes are part of the previous character.
+            self.location.column += 1;
+        }
+    }
+}
+
+fn unexpected_token(found: &TokenType, location: SourceLocation, expected: &'static str) -> JsonError {
+    match found {
+        TokenType::Undefined => JsonError::UnexpectedEof { expected, location },
+        found => JsonError::UnexpectedToken { expected, found: found.to_string(), location }
+    }
+}
@@ -0,0 +1,326 @@
+use std::fmt::{Display, Formatter};
+
+use crate::json::parser::{JsonNode, JsonObject, JsonValueOps};
+use crate::json::patch::values_equal;
+use crate::json::pointer::escape_pointer_token;
+use crate::json::typed::{json_type_name, FromJson, FromJsonError, FromJsonErrorKind};
+
+/// The json types a schema can require. Integers are numbers without a fractional part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaType {
+    Null,
+    Boolean,
+    Object,
+    Array,
+    Number,
+    Integer,
+    String
+}
+
+impl SchemaType {
+    pub fn from_name(name: &str) -> Option<SchemaType> {
+        match name {
+            "null" => Some(SchemaType::Null),
+            "boolean" => Some(SchemaType::Boolean),
+            "object" => Some(SchemaType::Object),
+            "array" => Some(SchemaType::Array),
+            "number" => Some(SchemaType::Number),
+            "integer" => Some(SchemaType::Integer),
+            "string" => Some(SchemaType::String),
+            _ => None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaType::Null => "null",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Object => "object",
+            SchemaType::Array => "array",
+            SchemaType::Number => "number",
+            SchemaType::Integer => "integer",
+            SchemaType::String => "string"
+        }
+    }
+
+    pub fn matches(&self, node: &JsonNode) -> bool {
+        match (self, node) {
+            (SchemaType::Null, JsonNode::Null) => true,
+            (SchemaType::Boolean, JsonNode::Bool(_)) => true,
+            (SchemaType::Object, JsonNode::Object(_)) => true,
+            (SchemaType::Array, JsonNode::Array(_)) => true,
+            (SchemaType::String, JsonNode::String(_)) => true,
+            (SchemaType::Number, _) => json_type_name(node) == "number",
+            (SchemaType::Integer, _) => node.as_f64().is_some_and(|value| value.fract() == 0.0),
+            _ => false
+        }
+    }
+}
+
+/// A json schema supporting a subset of draft 2020-12: `type`, `properties`, `required`,
+/// `additionalProperties`, `items`, `enum`, `minimum`, `maximum`, `exclusiveMinimum`,
+/// `exclusiveMaximum`, `minLength`, `maxLength`, `minItems` and `maxItems`.
+/// Other keywords, like `title` or `description`, are ignored.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JsonSchema {
+    /// Set by the `false` schema, which no value matches. The `true` schema is the default.
+    pub reject_all: bool,
+    /// The value must be one of these types. Empty allows any type.
+    pub types: Vec<SchemaType>,
+    pub properties: Vec<(String, JsonSchema)>,
+    pub required: Vec<String>,
+    /// The schema for object keys which are not in `properties`.
+    pub additional_properties: Option<Box<JsonSchema>>,
+    /// The schema for every element of an array.
+    pub items: Option<Box<JsonSchema>>,
+    pub enum_values: Option<Vec<JsonNode>>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub exclusive_minimum: Option<f64>,
+    pub exclusive_maximum: Option<f64>,
+    /// String lengths are counted in characters.
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>
+}
+
+/// The way a value breaks a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaViolationKind {
+    /// The value matched a `false` schema.
+    Rejected,
+    WrongType {
+        expected: String,
+        found: &'static str
+    },
+    MissingProperty,
+    /// The key is not in `properties` and `additionalProperties` is false.
+    AdditionalProperty,
+    NotInEnum {
+        allowed: String
+    },
+    TooSmall {
+        value: f64,
+        limit: f64,
+        exclusive: bool
+    },
+    TooLarge {
+        value: f64,
+        limit: f64,
+        exclusive: bool
+    },
+    /// A string has too few characters, or an array too few items.
+    TooShort {
+        length: usize,
+        min: usize
+    },
+    TooLong {
+        length: usize,
+        max: usize
+    }
+}
+
+/// A single schema violation, along with the json pointer of the value which caused it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub kind: SchemaViolationKind
+}
+
+impl Display for SchemaViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", if self.path.is_empty() { "/" } else { &self.path })?;
+
+        match &self.kind {
+            SchemaViolationKind::Rejected => write!(f, "value is not allowed"),
+            SchemaViolationKind::WrongType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+            SchemaViolationKind::MissingProperty => write!(f, "missing required property"),
+            SchemaViolationKind::AdditionalProperty => write!(f, "unknown property"),
+            SchemaViolationKind::NotInEnum { allowed } => write!(f, "expected one of {}", allowed),
+            SchemaViolationKind::TooSmall { value, limit, exclusive: false } => write!(f, "{} is less than the minimum of {}", value, limit),
+            SchemaViolationKind::TooSmall { value, limit, exclusive: true } => write!(f, "{} must be greater than {}", value, limit),
+            SchemaViolationKind::TooLarge { value, limit, exclusive: false } => write!(f, "{} is greater than the maximum of {}", value, limit),
+            SchemaViolationKind::TooLarge { value, limit, exclusive: true } => write!(f, "{} must be less than {}", value, limit),
+            SchemaViolationKind::TooShort { length, min } => write!(f, "length {} is shorter than the minimum of {}", length, min),
+            SchemaViolationKind::TooLong { length, max } => write!(f, "length {} is longer than the maximum of {}", length, max)
+        }
+    }
+}
+
+impl JsonSchema {
+    /// Checks the value against the schema and returns every violation, or nothing when it is valid.
+    pub fn validate(&self, node: &JsonNode) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        self.validate_at(node, String::new(), &mut violations);
+        violations
+    }
+
+    pub fn is_valid(&self, node: &JsonNode) -> bool {
+        self.validate(node).is_empty()
+    }
+
+    fn validate_at(&self, node: &JsonNode, path: String, violations: &mut Vec<SchemaViolation>) {
+        if self.reject_all {
+            return violations.push(SchemaViolation { path, kind: SchemaViolationKind::Rejected });
+        }
+
+        // None of the other keywords are meaningful for a value of the wrong type.
+        if !self.types.is_empty() && !self.types.iter().any(|schema_type| schema_type.matches(node)) {
+            let expected: Vec<&str> = self.types.iter().map(|schema_type| schema_type.name()).collect();
+            let kind = SchemaViolationKind::WrongType { expected: expected.join(" or "), found: json_type_name(node) };
+            return violations.push(SchemaViolation { path, kind });
+        }
+
+        let mut kinds = Vec::new();
+
+        if let Some(enum_values) = &self.enum_values {
+            if !enum_values.iter().any(|value| values_equal(value, node)) {
+                let allowed: Vec<String> = enum_values.iter().map(|value| value.to_string()).collect();
+                kinds.push(SchemaViolationKind::NotInEnum { allowed: allowed.join(", ") });
+            }
+        }
+
+        match node {
+            JsonNode::String(value) => {
+                check_length(value.get().chars().count(), self.min_length, self.max_length, &mut kinds);
+            },
+            JsonNode::Array(array) => {
+                check_length(array.size(), self.min_items, self.max_items, &mut kinds);
+            },
+            _ => {
+                if let Some(value) = node.as_f64() {
+                    self.check_range(value, &mut kinds);
+                }
+            }
+        }
+
+        violations.extend(kinds.into_iter().map(|kind| SchemaViolation { path: path.clone(), kind }));
+
+        match node {
+            JsonNode::Array(array) => {
+                if let Some(items) = &self.items {
+                    for (index, item) in array.iter().enumerate() {
+                        items.validate_at(item, format!("{}/{}", path, index), violations);
+                    }
+                }
+            },
+            JsonNode::Object(obj) => self.validate_object(obj, &path, violations),
+            _ => {}
+        }
+    }
+
+    fn check_range(&self, value: f64, kinds: &mut Vec<SchemaViolationKind>) {
+        if let Some(limit) = self.minimum.filter(|limit| value < *limit) {
+            kinds.push(SchemaViolationKind::TooSmall { value, limit, exclusive: false });
+        }
+
+        if let Some(limit) = self.exclusive_minimum.filter(|limit| value <= *limit) {
+            kinds.push(SchemaViolationKind::TooSmall { value, limit, exclusive: true });
+        }
+
+        if let Some(limit) = self.maximum.filter(|limit| value > *limit) {
+            kinds.push(SchemaViolationKind::TooLarge { value, limit, exclusive: false });
+        }
+
+        if let Some(limit) = self.exclusive_maximum.filter(|limit| value >= *limit) {
+            kinds.push(SchemaViolationKind::TooLarge { value, limit, exclusive: true });
+        }
+    }
+
+    fn validate_object(&self, obj: &JsonObject, path: &str, violations: &mut Vec<SchemaViolation>) {
+        for name in self.required.iter() {
+            if !obj.contains_key(name) {
+                violations.push(SchemaViolation {
+                    path: format!("{}/{}", path, escape_pointer_token(name)),
+                    kind: SchemaViolationKind::MissingProperty
+                });
+            }
+        }
+
+        for (key, value) in obj.iter() {
+            let child_path = format!("{}/{}", path, escape_pointer_token(key));
+
+            match self.properties.iter().find(|(name, _)| name == key) {
+                Some((_, schema)) => schema.validate_at(value, child_path, violations),
+                None => match &self.additional_properties {
+                    Some(schema) if schema.reject_all => violations.push(SchemaViolation {
+                        path: child_path,
+                        kind: SchemaViolationKind::AdditionalProperty
+                    }),
+                    Some(schema) => schema.validate_at(value, child_path, violations),
+                    None => {}
+                }
+            }
+        }
+    }
+}
+
+impl FromJson for JsonSchema {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        let obj = match node {
+            JsonNode::Bool(value) => return Ok(JsonSchema { reject_all: !*value.get(), ..JsonSchema::default() }),
+            JsonNode::Object(obj) => obj,
+            _ => return Err(FromJsonError::unexpected_type("schema", node))
+        };
+
+        let types = match obj.get("type") {
+            None => Vec::new(),
+            Some(JsonNode::Array(names)) => names.iter().enumerate()
+                .map(|(index, name)| load_type(name).map_err(|e| e.with_index(index).with_key("type")))
+                .collect::<Result<Vec<SchemaType>, FromJsonError>>()?,
+            Some(name) => vec![load_type(name).map_err(|e| e.with_key("type"))?]
+        };
+
+        let properties = match obj.get("properties") {
+            None => Vec::new(),
+            Some(JsonNode::Object(properties)) => properties.iter()
+                .map(|(name, schema)| match JsonSchema::from_json(schema) {
+                    Ok(schema) => Ok((name.clone(), schema)),
+                    Err(error) => Err(error.with_key(name).with_key("properties"))
+                })
+                .collect::<Result<Vec<(String, JsonSchema)>, FromJsonError>>()?,
+            Some(other) => return Err(FromJsonError::unexpected_type("object", other).with_key("properties"))
+        };
+
+        Ok(JsonSchema {
+            reject_all: false,
+            types,
+            properties,
+            required: load_keyword(obj, "required")?.unwrap_or_default(),
+            additional_properties: load_keyword::<JsonSchema>(obj, "additionalProperties")?.map(Box::new),
+            items: load_keyword::<JsonSchema>(obj, "items")?.map(Box::new),
+            enum_values: load_keyword(obj, "enum")?,
+            minimum: load_keyword(obj, "minimum")?,
+            maximum: load_keyword(obj, "maximum")?,
+            exclusive_minimum: load_keyword(obj, "exclusiveMinimum")?,
+            exclusive_maximum: load_keyword(obj, "exclusiveMaximum")?,
+            min_length: load_keyword(obj, "minLength")?,
+            max_length: load_keyword(obj, "maxLength")?,
+            min_items: load_keyword(obj, "minItems")?,
+            max_items: load_keyword(obj, "maxItems")?
+        })
+    }
+}
+
+fn check_length(length: usize, min: Option<usize>, max: Option<usize>, kinds: &mut Vec<SchemaViolationKind>) {
+    if let Some(min) = min.filter(|min| length < *min) {
+        kinds.push(SchemaViolationKind::TooShort { length, min });
+    }
+
+    if let Some(max) = max.filter(|max| length > *max) {
+        kinds.push(SchemaViolationKind::TooLong { length, max });
+    }
+}
+
+fn load_type(node: &JsonNode) -> Result<SchemaType, FromJsonError> {
+    let name = String::from_json(node)?;
+    SchemaType::from_name(&name).ok_or_else(|| FromJsonError::new(FromJsonErrorKind::UnknownVariant { found: name }))
+}
+
+fn load_keyword<T: FromJson>(obj: &JsonObject, key: &str) -> Result<Option<T>, FromJsonError> {
+    match obj.get(key) {
+        Some(node) => T::from_json(node).map(Some).map_err(|e| e.with_key(key)),
+        None => Ok(None)
+    }
+}
@@ -1,45 +1,13 @@
 #[cfg(test)]
 mod tests {
 
-    use crate::json::{lexer::{*}, self, parser::{JsonNode, JsonValueOps, JsonArray, JsonValue, JsonObject, parse_json}};
-
-    const HAPPY_TEST: &str = r#"{
-        "glossary": {
-            "title": "example glossary",
-            "GlossDiv": {
-                "title": "S",
-                "count": 5.123,
-                "hours": -1,
-                "is_true": false,
-                "test_null": null
-            }
-        },
-        "array": [1234567890123, -12.1, "S"]
-        }"#;
+    use std::collections::HashMap;
 
-    const SAD_TEST_UNTERMINATED_STR: &str = r#"{
-        "glossary: {
-            "title": "example glossary",
-            "GlossDiv": {
-                "title": "S",
-                "count": 5.123,
-                "hours": -1
-            }
-        },
-        "array": [1234567890123, -12.1, "S"]
-        }"#;
+    use glmath::glmath::{Vec3f, Quatf, Mat22f, Mat34f, vector::Vector};
 
-    const SAD_TEST_INVALID_FLT: &str = r#"{
-            "glossary": {
-                "title": "example glossary",
-                "GlossDiv": {
-                    "title": "S",
-                    "count": 5123e-3,
-                    "hours": -1
-                }
-            },
-            "array": [1234567890123, -12.1, "S"]
-        }"#;
+    use crate::test_corpus::{HAPPY_TEST, SAD_TEST_UNTERMINATED_STR, EXPONENT_FLT_TEST, VALID_DOCUMENTS};
+    use crate::json::typed::{ToJson, FromJson, FromJsonError, FromJsonErrorKind};
+    use crate::json::{lexer::{*, JsonOptions}, self, error::{JsonError, SourceLocation}, parser::{JsonNode, JsonValueOps, JsonArray, JsonValue, JsonObject, parse_json}, writer::{escape_json_string, JsonWriter, WriterConfig, FloatFormat, NewlineStyle, to_string_with_config}, reader::{JsonReader, JsonEvent}, pointer::{PointerMatch, parse_pointer}, patch::{PatchOperation, PatchError, PatchErrorKind, LayeredDocument}, schema::{JsonSchema, SchemaType, SchemaViolation, SchemaViolationKind}, diff::{diff, diff_with_options, format_report, ArrayDiffMode, DiffOptions, DiffEntry, DiffKind}, borrowed::{JsonNodeRef, parse_borrowed, parse_borrowed_with_options}};
 
     #[test]
     /// Just a simple test to verify that empty objects are working as expected. 
@@ -55,7 +23,7 @@ mod tests {
         let expected_object = JsonObject::new();
 
         match json_file {
-            Some(JsonNode::Array(array)) => {
+            Ok(JsonNode::Array(array)) => {
                 assert!(array.eq(&expected_array));
             }
             _ => assert!(false)
@@ -65,7 +33,7 @@ mod tests {
         let json_file = json::parser::parse_json(&mut lexer);
 
         match json_file {
-            Some(JsonNode::Object(object)) => {
+            Ok(JsonNode::Object(object)) => {
                 assert!(object.eq(&expected_object));
             }
             _ => assert!(false)
@@ -81,7 +49,7 @@ mod tests {
         let json_file = json::parser::parse_json(&mut lexer);
 
         match json_file {
-            Some(JsonNode::String(value)) => {
+            Ok(JsonNode::String(value)) => {
                 assert_eq!(value.get(), "test string");
             },
             _ => {
@@ -95,7 +63,7 @@ mod tests {
         let json_file = json::parser::parse_json(&mut lexer);
 
         match json_file {
-            Some(JsonNode::Number(value)) => {
+            Ok(JsonNode::Number(value)) => {
                 assert_eq!(*value.get(), -100123);
             }
             _ => {
@@ -109,7 +77,7 @@ mod tests {
         let json_file = json::parser::parse_json(&mut lexer);
 
         match json_file {
-            Some(JsonNode::Float(value)) => {
+            Ok(JsonNode::Float(value)) => {
                 assert_eq!(*value.get(), -1001.23);
             }
             _ => {
@@ -135,7 +103,7 @@ mod tests {
         expected_array.add(JsonNode::Array(expected_sub_arr));
 
         assert!(match json_file {
-            Some(JsonNode::Array(array)) => {
+            Ok(JsonNode::Array(array)) => {
                 array.eq(&expected_array)
 
             },
@@ -168,7 +136,7 @@ mod tests {
         expected_object.add("array", JsonNode::Array(expected_array));
 
         match json_file {
-            Some(JsonNode::Object(json_node)) => {
+            Ok(JsonNode::Object(json_node)) => {
                 assert!(json_node.eq(&expected_object));
             }
             _ => assert!(false)
@@ -180,7 +148,7 @@ mod tests {
         let mut lexer = JsonLexer::from_raw_json(SAD_TEST_UNTERMINATED_STR).unwrap();
 
         let mut token = Token::default();
-        lexer.next_token(&mut token);
+        let mut result = lexer.next_token(&mut token);
 
         let expected_token_types: Vec<TokenType> = vec![
             TokenType::Reserve { reserve_id: ReserveCode::OpenBrace },
@@ -188,19 +156,23 @@ mod tests {
         ];
         let mut current_token = 0;
 
-        while token.get_type() != TokenType::Undefined {
-            assert_eq!(token.get_type(), expected_token_types[current_token]);
+        while result.is_ok() && *token.get_type() != TokenType::Undefined {
+            assert_eq!(*token.get_type(), expected_token_types[current_token]);
             current_token += 1;
-            lexer.next_token(&mut token);
+            result = lexer.next_token(&mut token);
         }
+
+        assert_eq!(result, Err(JsonError::UnterminatedString {
+            location: SourceLocation { offset: 10, line: 2, column: 9 }
+        }));
     }
 
     #[test]
-    fn test_invalid_flt() {
-        let mut lexer = JsonLexer::from_raw_json(SAD_TEST_INVALID_FLT).unwrap();
+    fn test_exponent_flt() {
+        let mut lexer = JsonLexer::from_raw_json(EXPONENT_FLT_TEST).unwrap();
 
         let mut token = Token::default();
-        lexer.next_token(&mut token);
+        let mut result = lexer.next_token(&mut token);
 
         let expected_token_types: Vec<TokenType> = vec![
             TokenType::Reserve { reserve_id: ReserveCode::OpenBrace },
@@ -220,17 +192,35 @@ mod tests {
             TokenType::Reserve { reserve_id: ReserveCode::Comma },
             TokenType::String { value: "count".to_string() },
             TokenType::Reserve { reserve_id: ReserveCode::Colon },
-            TokenType::Number { value: 5123 },
+            TokenType::Float { value: 5.123 },
+            TokenType::Reserve { reserve_id: ReserveCode::Comma },
+            TokenType::String { value: "hours".to_string() },
+            TokenType::Reserve { reserve_id: ReserveCode::Colon },
+            TokenType::Number { value: -1 },
+            TokenType::Reserve { reserve_id: ReserveCode::CloseBrace },
+            TokenType::Reserve { reserve_id: ReserveCode::CloseBrace },
+            TokenType::Reserve { reserve_id: ReserveCode::Comma },
+            TokenType::String { value: "array".to_string() },
+            TokenType::Reserve { reserve_id: ReserveCode::Colon },
+            TokenType::Reserve { reserve_id: ReserveCode::OpenBracket },
+            TokenType::Number { value: 1234567890123 },
+            TokenType::Reserve { reserve_id: ReserveCode::Comma },
+            TokenType::Float { value: -12.1 },
+            TokenType::Reserve { reserve_id: ReserveCode::Comma },
+            TokenType::String { value: "S".to_string() },
+            TokenType::Reserve { reserve_id: ReserveCode::CloseBracket },
+            TokenType::Reserve { reserve_id: ReserveCode::CloseBrace },
         ];
         let mut current_token = 0;
 
-        while token.get_type() != TokenType::Undefined {
-            assert_eq!(token.get_type(), expected_token_types[current_token]);
+        while result.is_ok() && *token.get_type() != TokenType::Undefined {
+            assert_eq!(*token.get_type(), expected_token_types[current_token]);
             current_token += 1;
-            lexer.next_token(&mut token);
+            result = lexer.next_token(&mut token);
         }
 
         assert_eq!(current_token, expected_token_types.len());
+        assert_eq!(result, Ok(()));
     }
 
     #[test]
@@ -262,13 +252,26 @@ mod tests {
         let loaded_json = parse_json(&mut lexer);
 
         match loaded_json {
-            Some(JsonNode::Object(json_object)) => {
+            Ok(JsonNode::Object(json_object)) => {
                 assert!(json_object.eq(&expected_object))
             },
             _ => assert!(false)
         }
     }
 
+    #[test]
+    fn test_corpus_round_trip() {
+        for json in VALID_DOCUMENTS {
+            let node = parse_json(&mut JsonLexer::from_raw_json(json).unwrap()).unwrap();
+
+            // Compact and pretty printed output both parse back to the same tree.
+            for config in [WriterConfig::default(), WriterConfig::pretty()] {
+                let written = to_string_with_config(&node, config);
+                assert_eq!(parse_json(&mut JsonLexer::from_raw_json(&written).unwrap()).as_ref(), Ok(&node), "{}", json);
+            }
+        }
+    }
+
     // Load a json file from the system and see if we get the right
     // Token stream.
     #[test]
@@ -276,7 +279,7 @@ mod tests {
         let mut lexer = JsonLexer::from_raw_json(HAPPY_TEST).unwrap();
 
         let mut token = Token::default();
-        lexer.next_token(&mut token);
+        let mut result = lexer.next_token(&mut token);
 
         let expected_token_types: Vec<TokenType> = vec![
             TokenType::Reserve { reserve_id: ReserveCode::OpenBrace },
@@ -329,12 +332,1341 @@ mod tests {
         ];
         let mut current_token = 0;
 
-        while token.get_type() != TokenType::Undefined {
-            assert_eq!(token.get_type(), expected_token_types[current_token]);
+        while result.is_ok() && *token.get_type() != TokenType::Undefined {
+            assert_eq!(*token.get_type(), expected_token_types[current_token]);
             current_token += 1;
-            lexer.next_token(&mut token);
+            result = lexer.next_token(&mut token);
         }
 
         assert_eq!(current_token, expected_token_types.len());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error_locations() {
+        let parse_error = |json: &str| {
+            let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+            parse_json(&mut lexer).unwrap_err()
+        };
+
+        assert_eq!(parse_error("{\n  \"a\" 1\n}"), JsonError::UnexpectedToken {
+            expected: "':'",
+            found: "number 1".to_string(),
+            location: SourceLocation { offset: 8, line: 2, column: 7 }
+        });
+
+        assert_eq!(parse_error("[1, 2"), JsonError::UnexpectedEof {
+            expected: "',' or ']'",
+            location: SourceLocation { offset: 5, line: 1, column: 6 }
+        });
+
+        assert_eq!(parse_error("{\"a\": 1} []"), JsonError::TrailingData {
+            found: "'['".to_string(),
+            location: SourceLocation { offset: 9, line: 1, column: 10 }
+        });
+
+        assert_eq!(parse_error("[1, -]"), JsonError::InvalidNumber {
+            lexeme: "-".to_string(),
+            location: SourceLocation { offset: 4, line: 1, column: 5 }
+        });
+
+        assert_eq!(parse_error("{1: 2}"), JsonError::UnexpectedToken {
+            expected: "an object key",
+            found: "number 1".to_string(),
+            location: SourceLocation { offset: 1, line: 1, column: 2 }
+        });
+
+        // Columns count characters rather than bytes.
+        assert_eq!(parse_error("[\"é\" @]").location(), SourceLocation { offset: 6, line: 1, column: 6 });
+
+        // Literals at the very end of the input are still complete tokens.
+        let mut lexer = JsonLexer::from_raw_json("true").unwrap();
+        assert_eq!(parse_json(&mut lexer), Ok(JsonNode::Bool(JsonValue::new(true))));
+    }
+
+    #[test]
+    fn test_error_snippet() {
+        const BROKEN_CONFIG: &str = "{\n    \"xres\": 1000,\n    \"yres\" 1000\n}";
+
+        let mut lexer = JsonLexer::from_raw_json(BROKEN_CONFIG).unwrap();
+        let error = parse_json(&mut lexer).unwrap_err();
+
+        assert_eq!(error.to_string(), "expected ':' but found number 1000 at line 3, column 12");
+        assert_eq!(error.render_snippet(lexer.source()),
+            "error: expected ':' but found number 1000 at line 3, column 12\n  |\n3 |     \"yres\" 1000\n  |            ^\n");
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let parse_string = |json: &str| {
+            let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+            match parse_json(&mut lexer) {
+                Ok(JsonNode::String(value)) => Ok(value.get().clone()),
+                Ok(_) => panic!("Expected a string"),
+                Err(e) => Err(e)
+            }
+        };
+
+        assert_eq!(parse_string(r#""a\"b""#), Ok("a\"b".to_string()));
+        assert_eq!(parse_string(r#""\\ \/ \b \f \n \r \t""#), Ok("\\ / \u{8} \u{c} \n \r \t".to_string()));
+        assert_eq!(parse_string(r#""caf\u00e9 \u00C9""#), Ok("café É".to_string()));
+        assert_eq!(parse_string(r#""raw é stays""#), Ok("raw é stays".to_string()));
+        assert_eq!(parse_string(r#""\ud83d\ude00""#), Ok("😀".to_string()));
+        assert_eq!(parse_string(r#""ends with \\""#), Ok("ends with \\".to_string()));
+
+        // Escapes which are not part of the spec, lone surrogates, and short hex sequences are errors.
+        assert_eq!(parse_string(r#""\q""#), Err(JsonError::InvalidEscape {
+            sequence: "\\q".to_string(),
+            location: SourceLocation { offset: 1, line: 1, column: 2 }
+        }));
+        assert!(matches!(parse_string(r#""\ud83d""#), Err(JsonError::InvalidEscape { .. })));
+        assert!(matches!(parse_string(r#""\ude00\ud83d""#), Err(JsonError::InvalidEscape { .. })));
+        assert!(matches!(parse_string(r#""\ud83dA""#), Err(JsonError::InvalidEscape { .. })));
+        assert!(matches!(parse_string(r#""\u12G4""#), Err(JsonError::InvalidEscape { .. })));
+        assert!(matches!(parse_string(r#""\u12"#), Err(JsonError::InvalidEscape { .. })));
+        assert!(matches!(parse_string(r#""\"#), Err(JsonError::UnterminatedString { .. })));
+        assert!(matches!(parse_string("\"tab\tinside\""), Err(JsonError::ControlCharacterInString { found: '\t', .. })));
+    }
+
+    #[test]
+    fn test_string_escape_round_trip() {
+        const TRICKY_STRINGS: [&str; 6] = [
+            "plain",
+            "quote \" and backslash \\",
+            "line\nbreak\r\n\ttabbed",
+            "control \u{0} \u{1f} \u{8} \u{c}",
+            "unicode é 😀 \u{2028}",
+            "looks like an escape \\u0041"
+        ];
+
+        assert_eq!(escape_json_string("a\"b\\c\n\u{1}"), "a\\\"b\\\\c\\n\\u0001");
+
+        let mut object = JsonObject::new();
+        let mut array = JsonArray::new();
+
+        for tricky in TRICKY_STRINGS {
+            object.add(tricky, JsonNode::String(JsonValue::new(tricky.to_string())));
+            array.add(JsonNode::String(JsonValue::new(tricky.to_string())));
+        }
+
+        object.add("array", JsonNode::Array(array));
+
+        let written = object.to_string();
+        let mut lexer = JsonLexer::from_raw_json(&written).unwrap();
+
+        match parse_json(&mut lexer) {
+            Ok(JsonNode::Object(loaded)) => assert_eq!(loaded, object),
+            other => panic!("Failed to reload written json: {:?}", other)
+        }
+    }
+
+    #[test]
+    fn test_number_grammar() {
+        let parse_number = |json: &str| {
+            let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+            parse_json(&mut lexer)
+        };
+
+        assert_eq!(parse_number("1e-6"), Ok(JsonNode::Float(JsonValue::new(1e-6))));
+        assert_eq!(parse_number("1E+2"), Ok(JsonNode::Float(JsonValue::new(100.0))));
+        assert_eq!(parse_number("-0.5e1"), Ok(JsonNode::Float(JsonValue::new(-5.0))));
+        assert_eq!(parse_number("0"), Ok(JsonNode::Number(JsonValue::new(0))));
+        assert_eq!(parse_number("-0"), Ok(JsonNode::Number(JsonValue::new(0))));
+
+        // Integers keep full precision across the entire i64 and u64 range.
+        assert_eq!(parse_number("-9223372036854775808"), Ok(JsonNode::Number(JsonValue::new(i64::MIN))));
+        assert_eq!(parse_number("9223372036854775807"), Ok(JsonNode::Number(JsonValue::new(i64::MAX))));
+        assert_eq!(parse_number("9223372036854775808"), Ok(JsonNode::UnsignedNumber(JsonValue::new(9223372036854775808))));
+        assert_eq!(parse_number("18446744073709551615"), Ok(JsonNode::UnsignedNumber(JsonValue::new(u64::MAX))));
+
+        // Past that they fall back to floats.
+        assert_eq!(parse_number("18446744073709551616"), Ok(JsonNode::Float(JsonValue::new(18446744073709551616.0))));
+        assert_eq!(parse_number("-9223372036854775809"), Ok(JsonNode::Float(JsonValue::new(-9223372036854775809.0))));
+
+        for invalid in ["01", "-01", "1.", ".5", "1e", "1e+", "-", "+1", "1.e3", "-a", "1e400"] {
+            match parse_number(invalid) {
+                Err(JsonError::InvalidNumber { .. }) | Err(JsonError::UnexpectedCharacter { .. }) => {},
+                other => panic!("{} should not parse, got {:?}", invalid, other)
+            }
+        }
+
+        assert_eq!(parse_number("[01]"), Err(JsonError::InvalidNumber {
+            lexeme: "01".to_string(),
+            location: SourceLocation { offset: 1, line: 1, column: 2 }
+        }));
+    }
+
+    #[test]
+    fn test_number_round_trip() {
+        const NUMBERS: &str = "[0,-1,1.0,-0.25,1e-6,1e300,9223372036854775807,18446744073709551615,-9223372036854775808]";
+
+        let mut lexer = JsonLexer::from_raw_json(NUMBERS).unwrap();
+        let numbers = parse_json(&mut lexer).unwrap();
+
+        let mut lexer = JsonLexer::from_raw_json(&numbers.to_string()).unwrap();
+        assert_eq!(parse_json(&mut lexer), Ok(numbers));
+    }
+
+    #[test]
+    fn test_arbitrary_precision_numbers() {
+        const PRECISE: &str = r#"{"pi":3.14159265358979323846264338327950288,"big":123456789012345678901234567890,"small":-1E-400}"#;
+
+        let options = JsonOptions { arbitrary_precision: true, ..JsonOptions::default() };
+
+        let mut lexer = JsonLexer::from_raw_json(PRECISE).unwrap();
+        lexer.set_options(options.clone());
+
+        let loaded = parse_json(&mut lexer).unwrap();
+
+        match &loaded {
+            JsonNode::Object(object) => {
+                assert_eq!(object.get("pi"), Some(&JsonNode::RawNumber(JsonValue::new("3.14159265358979323846264338327950288".to_string()))));
+                assert_eq!(object.get("big"), Some(&JsonNode::RawNumber(JsonValue::new("123456789012345678901234567890".to_string()))));
+                assert_eq!(object.get("small"), Some(&JsonNode::RawNumber(JsonValue::new("-1E-400".to_string()))));
+            },
+            _ => panic!("Expected an object")
+        }
+
+        // Raw numbers are written back exactly as they were read.
+        let mut lexer = JsonLexer::from_raw_json(&loaded.to_string()).unwrap();
+        lexer.set_options(options);
+        assert_eq!(parse_json(&mut lexer), Ok(loaded));
+    }
+
+    #[test]
+    fn test_pretty_writer() {
+        const SETTINGS: &str = r#"{"window":{"yres":1000,"xres":1000,"title":"Snake"},"empty":{},"values":[1.0,true,null,[]],"volume":0.75}"#;
+
+        let mut lexer = JsonLexer::from_raw_json(SETTINGS).unwrap();
+        let settings = parse_json(&mut lexer).unwrap();
+
+        let mut config = WriterConfig::pretty();
+        config.sort_keys = true;
+
+        let written = to_string_with_config(&settings, config.clone());
+        assert_eq!(written, r#"{
+    "empty": {},
+    "values": [
+        1.0,
+        true,
+        null,
+        []
+    ],
+    "volume": 0.75,
+    "window": {
+        "title": "Snake",
+        "xres": 1000,
+        "yres": 1000
+    }
+}"#);
+
+        // Pretty output loads back to the same document.
+        let mut lexer = JsonLexer::from_raw_json(&written).unwrap();
+        assert_eq!(parse_json(&mut lexer).unwrap(), settings);
+
+        config.indent_width = 2;
+        config.newline = NewlineStyle::CrLf;
+        assert_eq!(to_string_with_config(&settings, config), written.replace("\n", "\r\n").replace("    ", "  "));
+    }
+
+    #[test]
+    fn test_writer_float_format() {
+        let mut floats = JsonArray::new();
+        floats.add(JsonNode::Float(JsonValue::new(0.1)));
+        floats.add(JsonNode::Float(JsonValue::new(2.0)));
+        floats.add(JsonNode::Float(JsonValue::new(-1.0 / 3.0)));
+        floats.add(JsonNode::Float(JsonValue::new(f64::NAN)));
+        let floats = JsonNode::Array(floats);
+
+        assert_eq!(to_string_with_config(&floats, WriterConfig::default()), "[0.1,2.0,-0.3333333333333333,null]");
+
+        let config = WriterConfig { float_format: FloatFormat::Fixed { decimals: 2 }, ..WriterConfig::default() };
+        assert_eq!(to_string_with_config(&floats, config), "[0.10,2.00,-0.33,null]");
+    }
+
+    #[test]
+    fn test_writer_streams_to_output() {
+        let mut object = JsonObject::new();
+        object.add("name", JsonNode::String(JsonValue::new("tab\there".to_string())));
+
+        let mut writer = JsonWriter::new(Vec::<u8>::new(), WriterConfig::pretty());
+        writer.write_object(&object).unwrap();
+
+        assert_eq!(writer.into_inner(), b"{\n    \"name\": \"tab\\there\"\n}");
+        assert_eq!(object.to_string(), r#"{"name":"tab\there"}"#);
+    }
+
+    #[test]
+    fn test_object_preserves_key_order() {
+        const CONFIG: &str = r#"{"window_config":{"yres":1000,"xres":1000,"title":"Snake"},"graphics":{"vsync":true},"resources":{},"game":{"update_tick_rate":120}}"#;
+
+        let mut lexer = JsonLexer::from_raw_json(CONFIG).unwrap();
+        let config = parse_json(&mut lexer).unwrap();
+
+        // Saving a loaded document writes the keys back in the same order.
+        assert_eq!(config.to_string(), CONFIG);
+
+        let mut object = match config {
+            JsonNode::Object(object) => object,
+            _ => panic!("Expected an object")
+        };
+
+        assert_eq!(object.keys().collect::<Vec<_>>(), ["window_config", "graphics", "resources", "game"]);
+
+        // Replacing a value keeps its position, new keys go on the end.
+        object.add("graphics", JsonNode::Null);
+        object.add("audio", JsonNode::Bool(JsonValue::new(true)));
+        assert_eq!(object.keys().collect::<Vec<_>>(), ["window_config", "graphics", "resources", "game", "audio"]);
+
+        // Removing keeps the remaining order, and lookups still find the entries that moved.
+        assert_eq!(object.remove("graphics"), Some(JsonNode::Null));
+        assert_eq!(object.remove("graphics"), None);
+        assert_eq!(object.keys().collect::<Vec<_>>(), ["window_config", "resources", "game", "audio"]);
+        assert_eq!(object.get("audio"), Some(&JsonNode::Bool(JsonValue::new(true))));
+        assert!(object.contains_key("game"));
+        assert_eq!(object.size(), 4);
+
+        for (_, node) in object.iter_mut() {
+            *node = JsonNode::Null;
+        }
+
+        assert_eq!(object.to_string(), r#"{"window_config":null,"resources":null,"game":null,"audio":null}"#);
+
+        // Key order does not affect equality.
+        let mut reordered = JsonObject::new();
+        for (key, node) in object.iter().rev() {
+            reordered.add(key, node.clone());
+        }
+
+        assert_eq!(reordered, object);
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    #[json(default)]
+    struct TestWindowConfig {
+        xres: u32,
+        yres: u32,
+        #[json(rename = "window_title")]
+        title: String,
+        #[json(required)]
+        resizable: bool,
+        clear_color: [f32; 3],
+        icon: Option<String>
+    }
+
+    impl Default for TestWindowConfig {
+        fn default() -> Self {
+            TestWindowConfig {
+                xres: 1920,
+                yres: 1080,
+                title: "Game Title".to_string(),
+                resizable: true,
+                clear_color: [0.0, 0.0, 0.0],
+                icon: None
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct TestAppConfig {
+        window_config: TestWindowConfig,
+        #[json(default = "120")]
+        update_tick_rate: i32,
+        #[json(default)]
+        textures: HashMap<String, String>,
+        spawn_points: Vec<Vec3f>,
+        camera: Option<Quatf>
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct TestWrapper<T> {
+        value: T
+    }
+
+    fn load_typed<T: FromJson>(json: &str) -> Result<T, FromJsonError> {
+        let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+        T::from_json(&parse_json(&mut lexer).unwrap())
+    }
+
+    #[test]
+    fn test_typed_primitives() {
+        assert_eq!(load_typed::<u8>("255"), Ok(255));
+        assert_eq!(load_typed::<u64>("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(load_typed::<f32>("1"), Ok(1.0));
+        assert_eq!(load_typed::<Option<bool>>("null"), Ok(None));
+        assert_eq!(load_typed::<Vec<i32>>("[1, 2, 3]"), Ok(vec![1, 2, 3]));
+        assert_eq!(load_typed::<Mat22f>("[[1, 0], [0, 1]]"), Ok(Mat22f::new()));
+        assert_eq!(load_typed::<Vec3f>("[1, 2, 3]"), Ok(Vec3f::new(1.0, 2.0, 3.0)));
+        assert_eq!(load_typed::<Mat34f>("[[1, 0, 0], [0, 1, 0], [0, 0, 1], [4, 5, 6]]").unwrap().column(3), Vector::new([4.0, 5.0, 6.0]));
+        assert_eq!(load_typed::<Vector<i32, 5>>("[1, 2, 3, 4, 5]"), Ok(Vector::new([1, 2, 3, 4, 5])));
+
+        assert_eq!(load_typed::<u8>("256").unwrap_err().to_string(), "/: 256 is out of range for u8");
+        assert_eq!(load_typed::<i32>("1.5").unwrap_err().to_string(), "/: expected integer, found number");
+        assert_eq!(load_typed::<u32>("60.0"), Ok(60));
+        assert_eq!(load_typed::<i8>("-1e2"), Ok(-100));
+        assert_eq!(load_typed::<u8>("256.0").unwrap_err().to_string(), "/: 256 is out of range for u8");
+        assert_eq!(load_typed::<Vec<String>>(r#"["a", 1]"#).unwrap_err().to_string(), "/1: expected string, found number");
+        assert_eq!(load_typed::<[f32; 2]>("[1]").unwrap_err().to_string(), "/: expected 2 elements, found 1");
+
+        let mut map = HashMap::new();
+        map.insert("b/c".to_string(), 1.5f32);
+        map.insert("a".to_string(), 0.1f32);
+
+        assert_eq!(map.to_json().to_string(), r#"{"a":0.1,"b/c":1.5}"#);
+        assert_eq!(load_typed::<HashMap<String, f32>>(r#"{"a":0.1,"b/c":1.5}"#), Ok(map));
+        assert_eq!(load_typed::<HashMap<String, f32>>(r#"{"a":0.1,"b/c":true}"#).unwrap_err().to_string(),
+            "/b~1c: expected number, found bool");
+    }
+
+    #[test]
+    fn test_derive_round_trip() {
+        let mut textures = HashMap::new();
+        textures.insert("tex_snake_head".to_string(), "./textures/snake_head.png".to_string());
+
+        let config = TestAppConfig {
+            window_config: TestWindowConfig {
+                xres: 1000,
+                yres: 800,
+                title: "Snake".to_string(),
+                resizable: false,
+                clear_color: [0.0, 1.0, 0.5],
+                icon: Some("icon.png".to_string())
+            },
+            update_tick_rate: 60,
+            textures,
+            spawn_points: vec![Vec3f::new(1.0, 2.0, 3.0), Vec3f::ZERO],
+            camera: Some(Quatf::IDENTITY)
+        };
+
+        let written = config.to_json();
+
+        match &written {
+            JsonNode::Object(object) => {
+                assert_eq!(object.keys().collect::<Vec<_>>(), ["window_config", "update_tick_rate", "textures", "spawn_points", "camera"]);
+                assert!(matches!(object.get("window_config"), Some(JsonNode::Object(window)) if window.contains_key("window_title")));
+            },
+            _ => panic!("Expected an object")
+        }
+
+        assert_eq!(TestAppConfig::from_json(&written), Ok(config));
+    }
+
+    #[test]
+    fn test_derive_generic_struct() {
+        let number = TestWrapper { value: 5 };
+        let names = TestWrapper { value: vec!["a".to_string(), "b".to_string()] };
+
+        assert_eq!(number.to_json().to_string(), r#"{"value":5}"#);
+        assert_eq!(load_typed::<TestWrapper<i32>>(&number.to_json().to_string()), Ok(number));
+        assert_eq!(TestWrapper::<Vec<String>>::from_json(&names.to_json()), Ok(names));
+        assert_eq!(load_typed::<TestWrapper<Option<u8>>>("{}"), Ok(TestWrapper { value: None }));
+    }
+
+    #[test]
+    fn test_derive_defaults_and_errors() {
+        let config = load_typed::<TestAppConfig>(r#"{
+            "window_config": { "xres": 1000, "resizable": false },
+            "spawn_points": []
+        }"#).unwrap();
+
+        // Missing fields come from the container default, the field default, or load as None.
+        assert_eq!(config.window_config, TestWindowConfig { xres: 1000, resizable: false, ..TestWindowConfig::default() });
+        assert_eq!(config.update_tick_rate, 120);
+        assert!(config.textures.is_empty());
+        assert_eq!(config.camera, None);
+
+        let error = load_typed::<TestAppConfig>(r#"{
+            "window_config": { "xres": "1000", "resizable": false },
+            "spawn_points": []
+        }"#).unwrap_err();
+
+        assert_eq!(error.pointer(), "/window_config/xres");
+        assert_eq!(error.to_string(), "/window_config/xres: expected integer, found string");
+
+        // Required fields must be present even when the struct has a default.
+        let error = load_typed::<TestAppConfig>(r#"{ "window_config": {}, "spawn_points": [] }"#).unwrap_err();
+        assert_eq!(error.to_string(), "/window_config/resizable: missing required field");
+
+        let error = load_typed::<TestAppConfig>(r#"{ "window_config": { "resizable": true } }"#).unwrap_err();
+        assert_eq!(error.kind, FromJsonErrorKind::MissingField);
+        assert_eq!(error.pointer(), "/spawn_points");
+
+        let error = load_typed::<TestAppConfig>(r#"{ "window_config": { "resizable": true }, "spawn_points": [{ "x": 1, "y": 2 }] }"#).unwrap_err();
+        assert_eq!(error.to_string(), "/spawn_points/0/z: missing required field");
+
+        let error = load_typed::<TestAppConfig>("[]").unwrap_err();
+        assert_eq!(error.to_string(), "/: expected object, found array");
+    }
+    #[test]
+    fn test_reader_events() {
+        let mut reader = JsonReader::new(r#"{ "title": "S", "sizes": [1, -2.5], "empty": {}, "on": true }"#.as_bytes());
+        let mut events = Vec::new();
+
+        while let Some(event) = reader.next_event().unwrap() {
+            events.push(event);
+        }
+
+        assert_eq!(events, vec![
+            JsonEvent::StartObject,
+            JsonEvent::Key("title".to_string()),
+            JsonEvent::Value(JsonNode::String(JsonValue::new("S".to_string()))),
+            JsonEvent::Key("sizes".to_string()),
+            JsonEvent::StartArray,
+            JsonEvent::Value(JsonNode::Number(JsonValue::new(1))),
+            JsonEvent::Value(JsonNode::Float(JsonValue::new(-2.5))),
+            JsonEvent::EndArray,
+            JsonEvent::Key("empty".to_string()),
+            JsonEvent::StartObject,
+            JsonEvent::EndObject,
+            JsonEvent::Key("on".to_string()),
+            JsonEvent::Value(JsonNode::Bool(JsonValue::new(true))),
+            JsonEvent::EndObject
+        ]);
+    }
+
+    #[test]
+    fn test_reader_small_buffer() {
+        // Tokens are split across many reads of the input.
+        for capacity in [1, 3, 4, 7] {
+            let mut reader = JsonReader::with_capacity(HAPPY_TEST.as_bytes(), capacity);
+            let node = reader.read_node().unwrap().unwrap();
+
+            assert_eq!(node, parse_json(&mut JsonLexer::from_raw_json(HAPPY_TEST).unwrap()).unwrap());
+            assert_eq!(reader.next_event().unwrap(), None);
+        }
+
+        let mut reader = JsonReader::with_capacity(r#"["café \"quoted\"", "😀"]"#.as_bytes(), 2);
+        assert_eq!(reader.read_node().unwrap().unwrap().to_string(), r#"["café \"quoted\"","😀"]"#);
+    }
+
+    #[test]
+    fn test_reader_selects_subtrees() {
+        let json = r#"{
+            "graphics": { "vsync": true },
+            "resources": {
+                "textures": ["a.png", "b.png"],
+                "shaders": { "game": { "vertex": "game.vs", "fragment": "game.fs" } }
+            }
+        }"#;
+
+        let mut reader = JsonReader::with_capacity(json.as_bytes(), 16);
+        let mut shaders = None;
+        let mut textures = Vec::new();
+
+        while let Some(event) = reader.next_event().unwrap() {
+            match event {
+                JsonEvent::Key(key) if key == "graphics" => assert!(reader.skip_value().unwrap()),
+                JsonEvent::Key(key) if key == "shaders" => shaders = reader.read_node().unwrap(),
+                JsonEvent::Key(key) if key == "textures" => {
+                    assert_eq!(reader.next_event().unwrap(), Some(JsonEvent::StartArray));
+
+                    while let Some(texture) = reader.read_node().unwrap() {
+                        textures.push((reader.current_pointer(), texture.to_string()));
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        assert_eq!(shaders.unwrap().to_string(), r#"{"game":{"vertex":"game.vs","fragment":"game.fs"}}"#);
+        assert_eq!(textures, vec![
+            ("/resources/textures/0".to_string(), r#""a.png""#.to_string()),
+            ("/resources/textures/1".to_string(), r#""b.png""#.to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_reader_errors() {
+        fn read_all(json: &str) -> JsonError {
+            let mut reader = JsonReader::with_capacity(json.as_bytes(), 4);
+
+            loop {
+                match reader.next_event() {
+                    Ok(Some(_)) => {},
+                    Ok(None) => panic!("expected an error for {}", json),
+                    Err(error) => return error
+                }
+            }
+        }
+
+        // The reader reports the same errors and locations as the parser.
+        for json in [
+            SAD_TEST_UNTERMINATED_STR,
+            "{\n  \"a\" 1 }",
+            "[1, 2,\n ]",
+            "{ \"a\": [1, 2 }",
+            "[truex]",
+            "[\"bad \\q escape\"]",
+            "[1.]",
+            "{} []",
+            "",
+            "[1, 2"
+        ] {
+            let expected = parse_json(&mut JsonLexer::from_raw_json(json).unwrap()).unwrap_err();
+            assert_eq!(read_all(json), expected, "{}", json);
+        }
+    }
+    #[test]
+    fn test_relaxed_json() {
+        let json = r#"{
+            // The window settings.
+            window_config: { xres: 1280, title: 'It\'s "relaxed"', },
+            /* Textures which are
+               not used yet. */
+            "textures": [
+                'grass.png',
+                // 'stone.png',
+            ],
+            $nullable_1: null,
+        }"#;
+
+        let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+        lexer.set_options(JsonOptions::relaxed());
+        let node = parse_json(&mut lexer).unwrap();
+
+        assert_eq!(node.to_string(), r#"{"window_config":{"xres":1280,"title":"It's \"relaxed\""},"textures":["grass.png"],"$nullable_1":null}"#);
+
+        // The streaming reader accepts the same documents.
+        let mut reader = JsonReader::with_capacity(json.as_bytes(), 3);
+        reader.set_options(JsonOptions::relaxed());
+        assert_eq!(reader.read_node().unwrap(), Some(node));
+        assert_eq!(reader.next_event().unwrap(), None);
+    }
+
+    #[test]
+    fn test_relaxed_json_errors() {
+        fn parse_error(json: &str, options: JsonOptions) -> JsonError {
+            let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+            lexer.set_options(options.clone());
+            let error = parse_json(&mut lexer).unwrap_err();
+
+            let mut reader = JsonReader::with_capacity(json.as_bytes(), 2);
+            reader.set_options(options);
+            let mut reader_error = None;
+
+            while reader_error.is_none() {
+                reader_error = reader.next_event().err();
+            }
+
+            assert_eq!(reader_error, Some(error.clone()), "{}", json);
+            error
+        }
+
+        // Strict mode stays the default.
+        assert_eq!(parse_error("[1, // one\n 2]", JsonOptions::default()).message(), "unexpected character '/'");
+        assert_eq!(parse_error("[1, 2,]", JsonOptions::default()).message(), "expected a json node but found ']'");
+        assert_eq!(parse_error("{ \"a\": 1, }", JsonOptions::default()).message(), "expected an object key but found '}'");
+        assert_eq!(parse_error("{ a: 1 }", JsonOptions::default()).message(), "unexpected character 'a'");
+        assert_eq!(parse_error("['a']", JsonOptions::default()).message(), r"unexpected character '\''");
+        assert_eq!(parse_error(r#"["\'"]"#, JsonOptions::default()).message(), r"invalid escape sequence '\''");
+
+        let error = parse_error("[1,\n  /* open", JsonOptions::relaxed());
+        assert_eq!(error, JsonError::UnterminatedComment { location: SourceLocation { offset: 6, line: 2, column: 3 } });
+
+        // Only a single trailing comma is allowed, and unquoted words are only keys.
+        assert_eq!(parse_error("[1,,]", JsonOptions::relaxed()).message(), "expected a json node but found ','");
+        assert_eq!(parse_error("{ a: b }", JsonOptions::relaxed()).message(), "expected a json node but found identifier b");
+        assert_eq!(parse_error("[1 / 2]", JsonOptions::relaxed()).message(), "unexpected character '/'");
+    }
+    #[test]
+    fn test_json_pointer() {
+        let mut node = parse_json(&mut JsonLexer::from_raw_json(r#"{
+            "graphics": { "clear_color": { "r": 0.25, "g": 1, "b": "0" }, "vsync": true },
+            "resources": { "textures": { "snake": "snake.png", "food": "food.png" } },
+            "spawns": [{ "x": 1 }, { "x": 2.0 }, { "x": 2.5 }],
+            "a/b": { "~c": 7 },
+            "": -1
+        }"#).unwrap()).unwrap();
+
+        assert_eq!(node.pointer(""), Some(&node));
+        assert_eq!(node.get_f64("/graphics/clear_color/r"), Some(0.25));
+        assert_eq!(node.get_f64("/graphics/clear_color/g"), Some(1.0));
+        assert_eq!(node.get_f64("/graphics/clear_color/b"), None);
+        assert_eq!(node.get_str("/graphics/clear_color/b"), Some("0"));
+        assert_eq!(node.get_bool("/graphics/vsync"), Some(true));
+        assert_eq!(node.get_i64("/spawns/1/x"), Some(2));
+        assert_eq!(node.get_i64("/spawns/2/x"), None);
+        assert_eq!(node.get_u64("/"), None);
+        assert_eq!(node.get_i64("/"), Some(-1));
+        assert_eq!(node.get_i64("/a~1b/~0c"), Some(7));
+
+        // Missing keys, bad indices and malformed pointers find nothing.
+        for pointer in ["/graphics/missing", "/spawns/3/x", "/spawns/01/x", "/spawns/-", "graphics", "/a~2b", "/graphics/vsync/x"] {
+            assert_eq!(node.pointer(pointer), None, "{}", pointer);
+        }
+
+        *node.pointer_mut("/spawns/0/x").unwrap() = JsonNode::Float(JsonValue::new(4.5));
+        assert_eq!(node.get_f64("/spawns/0/x"), Some(4.5));
+        assert!(node.pointer_mut("/spawns/0/y").is_none());
+
+        assert_eq!(parse_pointer("/a~1b/~0c/"), Some(vec!["a/b".to_string(), "~c".to_string(), String::new()]));
+    }
+
+    #[test]
+    fn test_json_query() {
+        let node = parse_json(&mut JsonLexer::from_raw_json(r#"{
+            "resources": { "textures": { "snake": "snake.png", "a/b": "ab.png" } },
+            "spawns": [{ "x": 1 }, { "y": 2 }, { "x": 3 }]
+        }"#).unwrap()).unwrap();
+
+        let textures: Vec<(String, String)> = node.query("/resources/textures/*").into_iter()
+            .map(|found| (found.path, found.node.to_string()))
+            .collect();
+
+        assert_eq!(textures, vec![
+            ("/resources/textures/snake".to_string(), r#""snake.png""#.to_string()),
+            ("/resources/textures/a~1b".to_string(), r#""ab.png""#.to_string())
+        ]);
+
+        let spawn_x: Vec<(String, Option<i64>)> = node.query("/spawns/*/x").into_iter()
+            .map(|found| (found.path, found.node.as_i64()))
+            .collect();
+
+        assert_eq!(spawn_x, vec![("/spawns/0/x".to_string(), Some(1)), ("/spawns/2/x".to_string(), Some(3))]);
+
+        assert_eq!(node.query("/spawns/1/y"), vec![PointerMatch { path: "/spawns/1/y".to_string(), node: node.pointer("/spawns/1/y").unwrap() }]);
+        assert_eq!(node.query("/*/*").len(), 4);
+        assert!(node.query("/missing/*").is_empty());
+    }
+    fn parse(json: &str) -> JsonNode {
+        parse_json(&mut JsonLexer::from_raw_json(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        // Examples from RFC 7396, appendix A.
+        let cases = [
+            (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+            (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+            (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":{"b":"c"}}"#, r#"{"a":{"b":"d","c":null}}"#, r#"{"a":{"b":"d"}}"#),
+            (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+            (r#"{"a":"foo"}"#, "null", "null"),
+            (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+            (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+            ("{}", r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#)
+        ];
+
+        for (original, patch, result) in cases {
+            let mut node = parse(original);
+            node.merge_patch(&parse(patch));
+            assert_eq!(node.to_string(), result, "{} + {}", original, patch);
+        }
+
+        // A merge diff turns the source into the target.
+        let source = parse(r#"{ "window": { "xres": 1920, "title": "Game" }, "vsync": true, "tags": [1, 2] }"#);
+        let target = parse(r#"{ "window": { "xres": 1920.0, "title": "Debug", "resizable": true }, "tags": [1] }"#);
+        let patch = source.merge_diff(&target);
+
+        assert_eq!(patch.to_string(), r#"{"vsync":null,"window":{"title":"Debug","resizable":true},"tags":[1]}"#);
+
+        let mut patched = source.clone();
+        patched.merge_patch(&patch);
+        assert_eq!(patched.merge_diff(&target).to_string(), "{}");
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let mut node = parse(r#"{ "foo": ["bar", "baz"], "count": 1 }"#);
+
+        let operations = Vec::<PatchOperation>::from_json(&parse(r#"[
+            { "op": "test", "path": "/count", "value": 1.0 },
+            { "op": "add", "path": "/foo/1", "value": "qux" },
+            { "op": "add", "path": "/foo/-", "value": "end" },
+            { "op": "remove", "path": "/foo/0" },
+            { "op": "replace", "path": "/count", "value": { "a": 1 } },
+            { "op": "copy", "from": "/count", "path": "/copied" },
+            { "op": "move", "from": "/count/a", "path": "/moved" },
+            { "op": "add", "path": "/a~1b", "value": null }
+        ]"#)).unwrap();
+
+        node.apply_patch(&operations).unwrap();
+        assert_eq!(node.to_string(), r#"{"foo":["qux","baz","end"],"count":{},"copied":{"a":1},"moved":1,"a/b":null}"#);
+
+        // Failed patches leave the node untouched.
+        let before = node.clone();
+        let failures = [
+            (r#"{ "op": "test", "path": "/moved", "value": "1" }"#, PatchErrorKind::TestFailed { path: "/moved".to_string() }),
+            (r#"{ "op": "remove", "path": "/missing" }"#, PatchErrorKind::PathNotFound { path: "/missing".to_string() }),
+            (r#"{ "op": "add", "path": "/foo/4", "value": 1 }"#, PatchErrorKind::PathNotFound { path: "/foo/4".to_string() }),
+            (r#"{ "op": "add", "path": "/missing/a", "value": 1 }"#, PatchErrorKind::PathNotFound { path: "/missing/a".to_string() }),
+            (r#"{ "op": "replace", "path": "foo", "value": 1 }"#, PatchErrorKind::InvalidPointer { path: "foo".to_string() }),
+            (r#"{ "op": "move", "from": "/copied", "path": "/copied/b" }"#, PatchErrorKind::MoveIntoChild { from: "/copied".to_string(), path: "/copied/b".to_string() })
+        ];
+
+        for (failing, kind) in failures {
+            let operations = vec![
+                PatchOperation::Remove { path: "/foo".to_string() },
+                PatchOperation::from_json(&parse(failing)).unwrap()
+            ];
+
+            assert_eq!(node.apply_patch(&operations), Err(PatchError { operation: 1, kind }), "{}", failing);
+            assert_eq!(node, before);
+        }
+
+        let error = node.apply_patch(&[PatchOperation::Remove { path: "/missing".to_string() }]).unwrap_err();
+        assert_eq!(error.to_string(), r#"operation 0: no value at "/missing""#);
+
+        let error = PatchOperation::from_json(&parse(r#"{ "op": "delete", "path": "/a" }"#)).unwrap_err();
+        assert_eq!(error.to_string(), r#"/op: unknown value "delete""#);
+
+        let error = PatchOperation::from_json(&parse(r#"{ "op": "add", "path": "/a" }"#)).unwrap_err();
+        assert_eq!(error.to_string(), "/value: missing required field");
+    }
+
+    #[test]
+    fn test_json_patch_diff() {
+        let source = parse(r#"{ "a": 1, "b": { "c": [1, 2, 3], "d": "x" }, "e/f": true }"#);
+        let target = parse(r#"{ "a": 1.0, "b": { "c": [1, 5], "g": null }, "h": [] }"#);
+        let operations = source.patch_diff(&target);
+
+        assert_eq!(operations.to_json().to_string(), concat!(
+            r#"[{"op":"replace","path":"/b/c/1","value":5},{"op":"remove","path":"/b/c/2"},"#,
+            r#"{"op":"remove","path":"/b/d"},{"op":"add","path":"/b/g","value":null},"#,
+            r#"{"op":"remove","path":"/e~1f"},{"op":"add","path":"/h","value":[]}]"#));
+
+        let mut patched = source.clone();
+        patched.apply_patch(&operations).unwrap();
+        assert!(patched.patch_diff(&target).is_empty());
+
+        // Growing arrays appends in order, and a different type replaces the whole value.
+        let operations = parse("[1]").patch_diff(&parse("[1, 2, 3]"));
+        assert_eq!(operations.to_json().to_string(), r#"[{"op":"add","path":"/1","value":2},{"op":"add","path":"/2","value":3}]"#);
+        assert_eq!(parse("[1]").patch_diff(&parse("{}")), vec![PatchOperation::Replace { path: String::new(), value: parse("{}") }]);
+    }
+
+    #[test]
+    fn test_layered_document() {
+        let mut config = LayeredDocument::new();
+        config.add_layer("app_config.json", &parse(r#"{
+            "window_config": { "xres": 1920, "yres": 1080, "title": "Snake" },
+            "graphics": { "clear_color": { "r": 0.0, "g": 0.0, "b": 0.0 }, "vsync": true },
+            "textures": ["a.png"]
+        }"#));
+        config.add_layer("app_config.linux.json", &parse(r#"{ "graphics": { "vsync": false }, "textures": ["b.png", "c.png"] }"#));
+        config.add_layer("app_config.local.json", &parse(r#"{
+            "window_config": { "xres": 1280, "title": null },
+            "graphics": { "clear_color": 0.5 }
+        }"#));
+
+        assert_eq!(config.node().to_string(), concat!(
+            r#"{"window_config":{"xres":1280,"yres":1080},"graphics":{"clear_color":0.5,"vsync":false},"#,
+            r#""textures":["b.png","c.png"]}"#));
+
+        assert_eq!(config.sources(), vec![
+            ("/window_config/xres".to_string(), "app_config.local.json"),
+            ("/window_config/yres".to_string(), "app_config.json"),
+            ("/graphics/clear_color".to_string(), "app_config.local.json"),
+            ("/graphics/vsync".to_string(), "app_config.linux.json"),
+            ("/textures".to_string(), "app_config.linux.json")
+        ]);
+
+        assert_eq!(config.source_of("/textures/1"), Some("app_config.linux.json"));
+        assert_eq!(config.source_of("/window_config/title"), None);
+        assert_eq!(config.source_of("/graphics/clear_color/r"), Some("app_config.local.json"));
+
+        // Turning a value back into an object forgets the old source.
+        config.add_layer("extra", &parse(r#"{ "graphics": { "clear_color": { "r": 1 } } }"#));
+        assert_eq!(config.source_of("/graphics/clear_color"), None);
+        assert_eq!(config.source_of("/graphics/clear_color/r"), Some("extra"));
+    }
+    #[test]
+    fn test_schema_validation() {
+        let schema = JsonSchema::from_json(&parse(r#"{
+            "title": "Window",
+            "type": "object",
+            "properties": {
+                "xres": { "type": "integer", "minimum": 1 },
+                "scale": { "type": "number", "exclusiveMinimum": 0, "maximum": 4 },
+                "title": { "type": "string", "minLength": 1, "maxLength": 8 },
+                "mode": { "enum": ["windowed", "fullscreen", 0] },
+                "tags": { "type": "array", "items": { "type": ["string", "null"] }, "maxItems": 2 },
+                "debug": true,
+                "removed": false
+            },
+            "required": ["xres", "title"],
+            "additionalProperties": false
+        }"#)).unwrap();
+
+        assert_eq!(schema.types, vec![SchemaType::Object]);
+        assert!(schema.is_valid(&parse(r#"{ "xres": 1000, "title": "Snake", "mode": 0.0, "tags": ["a", null], "debug": [] }"#)));
+        assert!(schema.is_valid(&parse(r#"{ "xres": 1000.0, "title": "Snake", "scale": 4 }"#)));
+
+        // Every violation is reported, not just the first.
+        let violations = schema.validate(&parse(r#"{
+            "xres": "1000",
+            "scale": 0,
+            "title": "A long title",
+            "mode": "borderless",
+            "tags": ["a", 1, "c"],
+            "removed": 1,
+            "fullscreen": true
+        }"#));
+
+        let messages: Vec<String> = violations.iter().map(|violation| violation.to_string()).collect();
+        assert_eq!(messages, vec![
+            "/xres: expected integer, found string",
+            "/scale: 0 must be greater than 0",
+            "/title: length 12 is longer than the maximum of 8",
+            r#"/mode: expected one of "windowed", "fullscreen", 0"#,
+            "/tags: length 3 is longer than the maximum of 2",
+            "/tags/1: expected string or null, found number",
+            "/removed: value is not allowed",
+            "/fullscreen: unknown property"
+        ]);
+
+        assert_eq!(schema.validate(&parse(r#"{ "xres": 0.5 }"#)), vec![
+            SchemaViolation { path: "/title".to_string(), kind: SchemaViolationKind::MissingProperty },
+            SchemaViolation { path: "/xres".to_string(), kind: SchemaViolationKind::WrongType { expected: "integer".to_string(), found: "number" } }
+        ]);
+
+        assert_eq!(schema.validate(&parse("[]"))[0].to_string(), "/: expected object, found array");
+
+        let any_value = JsonSchema::from_json(&parse("{}")).unwrap();
+        assert!(any_value.is_valid(&parse(r#"[1, "a", null]"#)));
+        assert!(!JsonSchema::from_json(&parse("false")).unwrap().is_valid(&JsonNode::Null));
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let error = JsonSchema::from_json(&parse(r#"{ "properties": { "xres": { "type": "int" } } }"#)).unwrap_err();
+        assert_eq!(error.to_string(), r#"/properties/xres/type: unknown value "int""#);
+
+        let error = JsonSchema::from_json(&parse(r#"{ "type": ["string", 1] }"#)).unwrap_err();
+        assert_eq!(error.to_string(), "/type/1: expected string, found number");
+
+        let error = JsonSchema::from_json(&parse(r#"{ "items": { "minItems": -1 } }"#)).unwrap_err();
+        assert_eq!(error.pointer(), "/items/minItems");
+
+        let error = JsonSchema::from_json(&parse("1")).unwrap_err();
+        assert_eq!(error.to_string(), "/: expected schema, found number");
+    }
+    #[test]
+    fn test_diff_objects() {
+        let old = parse(r#"{
+            "window_config": { "xres": 1280, "yres": 720, "title": "Snake" },
+            "graphics": { "clear_color": { "r": 0.1, "g": 0.2, "b": 0.3 } },
+            "removed": [1, 2],
+            "a/b": 1
+        }"#);
+        let new = parse(r#"{
+            "window_config": { "xres": 1920, "yres": 720.0, "title": "Snake" },
+            "graphics": { "clear_color": { "r": 0.1, "g": 0.2, "b": 0.3 }, "vsync": true },
+            "a/b": "one"
+        }"#);
+
+        let entries = diff(&old, &new);
+
+        assert_eq!(entries, vec![
+            DiffEntry { path: "/window_config/xres".to_string(), kind: DiffKind::Changed { old: parse("1280"), new: parse("1920") } },
+            DiffEntry { path: "/graphics/vsync".to_string(), kind: DiffKind::Added { value: parse("true") } },
+            DiffEntry { path: "/removed".to_string(), kind: DiffKind::Removed { value: parse("[1, 2]") } },
+            DiffEntry { path: "/a~1b".to_string(), kind: DiffKind::Changed { old: parse("1"), new: parse(r#""one""#) } }
+        ]);
+
+        assert!(diff(&old, &old).is_empty());
+
+        // Every path in the diff can be looked up in the document it came from.
+        for entry in &entries {
+            match &entry.kind {
+                DiffKind::Removed { value } => assert_eq!(old.pointer(&entry.path), Some(value)),
+                DiffKind::Added { value } => assert_eq!(new.pointer(&entry.path), Some(value)),
+                DiffKind::Changed { old: old_value, new: new_value } => {
+                    assert_eq!(old.pointer(&entry.path), Some(old_value));
+                    assert_eq!(new.pointer(&entry.path), Some(new_value));
+                }
+            }
+        }
+
+        // Replacing the whole document, or a container with a different type, is a single change.
+        assert_eq!(diff(&parse("[1]"), &parse(r#"{ "a": 1 }"#)), vec![
+            DiffEntry { path: String::new(), kind: DiffKind::Changed { old: parse("[1]"), new: parse(r#"{ "a": 1 }"#) } }
+        ]);
+    }
+
+    #[test]
+    fn test_diff_arrays() {
+        let old = parse(r#"{ "enemies": [
+            { "name": "bat", "hp": 5 },
+            { "name": "rat", "hp": 3 },
+            { "name": "ogre", "hp": 40 }
+        ] }"#);
+        let new = parse(r#"{ "enemies": [
+            { "name": "slime", "hp": 8 },
+            { "name": "bat", "hp": 5 },
+            { "name": "ogre", "hp": 45 }
+        ] }"#);
+
+        // By index every element looks changed.
+        assert_eq!(diff(&old, &new).len(), 5);
+
+        let options = DiffOptions { arrays: ArrayDiffMode::ByKey("name".to_string()), ..DiffOptions::default() };
+
+        assert_eq!(diff_with_options(&old, &new, &options), vec![
+            DiffEntry { path: "/enemies/1".to_string(), kind: DiffKind::Removed { value: parse(r#"{ "name": "rat", "hp": 3 }"#) } },
+            DiffEntry { path: "/enemies/2/hp".to_string(), kind: DiffKind::Changed { old: parse("40"), new: parse("45") } },
+            DiffEntry { path: "/enemies/0".to_string(), kind: DiffKind::Added { value: parse(r#"{ "name": "slime", "hp": 8 }"#) } }
+        ]);
+
+        // Reordering alone is not a change when elements are matched by key.
+        let reordered = parse(r#"{ "enemies": [
+            { "name": "ogre", "hp": 40 },
+            { "name": "bat", "hp": 5 },
+            { "name": "rat", "hp": 3 }
+        ] }"#);
+
+        assert!(diff_with_options(&old, &reordered, &options).is_empty());
+
+        // Arrays with missing or duplicate keys fall back to comparing by index.
+        let duplicates = parse(r#"{ "enemies": [{ "name": "bat", "hp": 5 }, { "name": "bat", "hp": 6 }] }"#);
+
+        assert_eq!(diff_with_options(&old, &duplicates, &options), vec![
+            DiffEntry { path: "/enemies/1/name".to_string(), kind: DiffKind::Changed { old: parse(r#""rat""#), new: parse(r#""bat""#) } },
+            DiffEntry { path: "/enemies/1/hp".to_string(), kind: DiffKind::Changed { old: parse("3"), new: parse("6") } },
+            DiffEntry { path: "/enemies/2".to_string(), kind: DiffKind::Removed { value: parse(r#"{ "name": "ogre", "hp": 40 }"#) } }
+        ]);
+
+        // Overrides pick the mode per array, with wildcards.
+        let old_levels = parse(r#"{ "levels": [{ "spawns": [{ "id": 1 }, { "id": 2 }] }], "tags": ["a", "b"] }"#);
+        let new_levels = parse(r#"{ "levels": [{ "spawns": [{ "id": 2 }] }], "tags": ["b"] }"#);
+        let options = DiffOptions {
+            array_overrides: vec![("/levels/*/spawns".to_string(), ArrayDiffMode::ByKey("id".to_string()))],
+            ..DiffOptions::default()
+        };
+
+        assert_eq!(diff_with_options(&old_levels, &new_levels, &options), vec![
+            DiffEntry { path: "/levels/0/spawns/0".to_string(), kind: DiffKind::Removed { value: parse(r#"{ "id": 1 }"#) } },
+            DiffEntry { path: "/tags/0".to_string(), kind: DiffKind::Changed { old: parse(r#""a""#), new: parse(r#""b""#) } },
+            DiffEntry { path: "/tags/1".to_string(), kind: DiffKind::Removed { value: parse(r#""b""#) } }
+        ]);
+
+        // Keys pair up by value however their numbers are written, the same as values are compared.
+        assert!(diff(&parse(r#"{"a": 1}"#), &parse(r#"{"a": 1.0}"#)).is_empty());
+
+        let options = DiffOptions { arrays: ArrayDiffMode::ByKey("id".to_string()), ..DiffOptions::default() };
+        let old_spawns = parse(r#"[{ "id": 1, "pos": { "x": 0, "y": 2 } }, { "id": { "a": 1, "b": 2 } }]"#);
+        let new_spawns = parse(r#"[{ "id": { "b": 2.0, "a": 1 } }, { "id": 1.0, "pos": { "x": 4, "y": 2 } }]"#);
+
+        assert_eq!(diff_with_options(&old_spawns, &new_spawns, &options), vec![
+            DiffEntry { path: "/1/pos/x".to_string(), kind: DiffKind::Changed { old: parse("0"), new: parse("4") } }
+        ]);
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let old = parse(r#"{ "xres": 1280, "title": "Snake", "icon": "snake.png" }"#);
+        let new = parse(r#"{ "xres": 1920, "title": "Snake", "vsync": true }"#);
+
+        assert_eq!(format_report(&diff(&old, &new)), "~ /xres: 1280 -> 1920\n- /icon: \"snake.png\"\n+ /vsync: true\n1 added, 1 removed, 1 changed");
+        assert_eq!(format_report(&diff(&old, &old)), "no changes");
+        assert_eq!(diff(&parse("1"), &parse("2"))[0].to_string(), "~ /: 1 -> 2");
+    }
+    fn parse_with(json: &str, options: JsonOptions) -> Result<JsonNode, JsonError> {
+        let mut lexer = JsonLexer::from_raw_json(json).unwrap();
+        lexer.set_options(options);
+        parse_json(&mut lexer)
+    }
+
+    /// Reads every event of the document, returning the first error.
+    fn read_all(json: &[u8], options: JsonOptions) -> Result<(), JsonError> {
+        let mut reader = JsonReader::with_capacity(json, 7);
+        reader.set_options(options);
+
+        while reader.next_event()?.is_some() {}
+        Ok(())
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+
+        assert!(parse_with(&nested(DEFAULT_MAX_DEPTH), JsonOptions::default()).is_ok());
+        assert!(read_all(nested(DEFAULT_MAX_DEPTH).as_bytes(), JsonOptions::default()).is_ok());
+
+        let expected = JsonError::NestingTooDeep {
+            max_depth: DEFAULT_MAX_DEPTH,
+            location: SourceLocation { offset: DEFAULT_MAX_DEPTH, line: 1, column: DEFAULT_MAX_DEPTH + 1 }
+        };
+
+        // Far deeper than the stack could hold if the limit were not checked.
+        assert_eq!(parse_with(&"[".repeat(1_000_000), JsonOptions::default()), Err(expected.clone()));
+        assert_eq!(read_all("[".repeat(1_000_000).as_bytes(), JsonOptions::default()), Err(expected));
+
+        let options = JsonOptions { max_depth: 2, ..JsonOptions::default() };
+        let error = parse_with(r#"{ "a": [1, { "b": 2 }] }"#, options.clone()).unwrap_err();
+
+        assert_eq!(error.to_string(), "objects and arrays are nested deeper than the limit of 2 at line 1, column 12");
+        assert_eq!(read_all(r#"{ "a": [1, { "b": 2 }] }"#.as_bytes(), options.clone()), Err(error));
+        assert!(parse_with(r#"{ "a": [1, 2] }"#, options).is_ok());
+
+        // A zero depth only allows a single value.
+        let options = JsonOptions { max_depth: 0, ..JsonOptions::default() };
+        assert!(parse_with("5", options.clone()).is_ok());
+        assert!(matches!(parse_with("[]", options), Err(JsonError::NestingTooDeep { .. })));
+    }
+
+    #[test]
+    fn test_string_and_document_limits() {
+        let options = JsonOptions { max_string_length: 4, ..JsonOptions::relaxed() };
+
+        // The length is counted after escapes are decoded.
+        assert!(parse_with(r#"["abcd", "ABCD", 'ab\'c']"#, options.clone()).is_ok());
+        assert!(read_all(r#"["abcd", "ABCD", 'ab\'c']"#.as_bytes(), options.clone()).is_ok());
+
+        for json in [r#"["abcde"]"#, r#"{ "abcde": 1 }"#, "{ abcde: 1 }", r#"["abcAB"]"#] {
+            let error = parse_with(json, options.clone()).unwrap_err();
+
+            assert!(matches!(error, JsonError::StringTooLong { max_length: 4, .. }), "{}", json);
+            assert_eq!(error.location().offset, json.find(['"', 'a']).unwrap(), "{}", json);
+            assert_eq!(read_all(json.as_bytes(), options.clone()), Err(error), "{}", json);
+        }
+
+        // The reader gives up on a huge string without buffering all of it.
+        let huge = format!("\"{}\"", "x".repeat(100_000));
+        let mut reader = JsonReader::new(huge.as_bytes());
+        reader.set_options(options.clone());
+
+        assert!(matches!(reader.next_event(), Err(JsonError::StringTooLong { .. })));
+        assert!(reader.location().offset < 100);
+
+        // Numbers are limited to the same length, which keeps the reader from buffering a huge run of digits.
+        assert!(parse_with("[1234, -123, 1e10]", options.clone()).is_ok());
+        assert!(read_all(b"[1234, -123, 1e10]", options.clone()).is_ok());
+
+        for json in ["[12345]", "[-1234]", "{ a: 1.234 }"] {
+            let error = parse_with(json, options.clone()).unwrap_err();
+
+            assert!(matches!(error, JsonError::NumberTooLong { max_length: 4, .. }), "{}", json);
+            assert_eq!(error.location().offset, json.find(['-', '1']).unwrap(), "{}", json);
+            assert_eq!(read_all(json.as_bytes(), options.clone()), Err(error), "{}", json);
+        }
+
+        let huge = format!("[{}]", "9".repeat(100_000));
+        let mut reader = JsonReader::new(huge.as_bytes());
+        reader.set_options(options.clone());
+
+        assert_eq!(reader.next_event(), Ok(Some(JsonEvent::StartArray)));
+        assert_eq!(reader.next_event(), Err(JsonError::NumberTooLong { max_length: 4, location: SourceLocation { offset: 1, line: 1, column: 2 } }));
+        assert!(reader.location().offset < 100);
+
+        let options = JsonOptions { max_document_size: 10, ..JsonOptions::default() };
+        let expected = JsonError::DocumentTooLarge { max_size: 10, location: SourceLocation { offset: 10, line: 1, column: 11 } };
+
+        assert!(parse_with("[1, 2, 3]", options.clone()).is_ok());
+        assert!(read_all(b"[1, 2, 3]", options.clone()).is_ok());
+        assert_eq!(parse_with("[1, 2, 3, 4]", options.clone()), Err(expected.clone()));
+        assert_eq!(read_all(b"[1, 2, 3, 4]", options.clone()), Err(expected.clone()));
+
+        // Trailing whitespace counts towards the size as well.
+        assert_eq!(parse_with("[1, 2, 3]       ", options), Err(expected));
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        // Inputs which once crashed, or could crash, the lexer, parser or reader.
+        // Each one is run with every combination of options and must return instead of panicking.
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions");
+        let mut count = 0;
+
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+
+            let bytes = std::fs::read(&path).unwrap();
+            let json = String::from_utf8_lossy(&bytes);
+
+            for flags in 0..16 {
+                let options = JsonOptions {
+                    arbitrary_precision: flags & 1 != 0,
+                    relaxed: flags & 2 != 0,
+                    max_depth: if flags & 4 != 0 { 8 } else { DEFAULT_MAX_DEPTH },
+                    max_string_length: if flags & 8 != 0 { 16 } else { usize::MAX },
+                    ..JsonOptions::default()
+                };
+
+                match parse_with(&json, options.clone()) {
+                    // Anything which parses must still parse once written out.
+                    Ok(node) => assert!(parse_with(&node.to_string(), options.clone()).is_ok(), "{:?}", path),
+                    Err(error) => {
+                        error.render_snippet(&json);
+                    }
+                }
+
+                let _ = read_all(&bytes, options);
+            }
+
+            count += 1;
+        }
+
+        assert!(count > 0);
+    }
+    #[test]
+    fn test_json_macro() {
+        let title = "Snake".to_string();
+        let xres: u32 = 1920;
+        let key = "dynamic";
+
+        let built = crate::json!({
+            "window_config": { "xres": xres, "yres": 1080, "title": title.clone(), "resizable": true },
+            "spawn_points": [[0, 0], [4.5, -2], []],
+            "empty": {},
+            "icon": null,
+            "textures": ["snake.png", null, { "path": "apple.png" },],
+            (key): xres / 2 + 1,
+            "maybe": Option::<i32>::None,
+        });
+
+        let parsed = parse(r#"{
+            "window_config": { "xres": 1920, "yres": 1080, "title": "Snake", "resizable": true },
+            "spawn_points": [[0, 0], [4.5, -2], []],
+            "empty": {},
+            "icon": null,
+            "textures": ["snake.png", null, { "path": "apple.png" }],
+            "dynamic": 961,
+            "maybe": null
+        }"#);
+
+        assert_eq!(built, parsed);
+
+        // Keys stay in the order they were written.
+        let keys: Vec<&String> = match &built {
+            JsonNode::Object(obj) => obj.keys().collect(),
+            _ => panic!("expected an object")
+        };
+        assert_eq!(keys, ["window_config", "spawn_points", "empty", "icon", "textures", "dynamic", "maybe"]);
+
+        assert_eq!(crate::json!(null), JsonNode::Null);
+        assert_eq!(crate::json!([]), JsonNode::Array(JsonArray::new()));
+        assert_eq!(crate::json!(-5), JsonNode::Number(JsonValue::new(-5)));
+        assert_eq!(crate::json!("text"), JsonNode::String(JsonValue::new("text".to_string())));
+        assert_eq!(crate::json!([1, 2].len()), JsonNode::Number(JsonValue::new(2)));
+    }
+
+    #[test]
+    fn test_node_from_values() {
+        assert_eq!(JsonNode::from(5i64), parse("5"));
+        assert_eq!(JsonNode::from(u64::MAX), JsonNode::UnsignedNumber(JsonValue::new(u64::MAX)));
+        assert_eq!(JsonNode::from(0.1f32), parse("0.1"));
+        assert_eq!(JsonNode::from(2.5), parse("2.5"));
+        assert_eq!(JsonNode::from(false), parse("false"));
+        assert_eq!(JsonNode::from("a \"quote\""), parse(r#""a \"quote\"""#));
+        assert_eq!(JsonNode::from(String::from("owned")), parse(r#""owned""#));
+        assert_eq!(JsonNode::from(()), JsonNode::Null);
+        assert_eq!(JsonNode::from(Some(3)), parse("3"));
+        assert_eq!(JsonNode::from(None::<bool>), JsonNode::Null);
+        assert_eq!(JsonNode::from(vec![vec![1, 2], vec![]]), parse("[[1, 2], []]"));
+        assert_eq!(JsonNode::from(&["a", "b"][..]), parse(r#"["a", "b"]"#));
+
+        let textures = HashMap::from([("b", "b.png"), ("a", "a.png")]);
+        let node = JsonNode::from(textures);
+
+        assert_eq!(node, parse(r#"{ "a": "a.png", "b": "b.png" }"#));
+        assert_eq!(node.to_string(), r#"{"a":"a.png","b":"b.png"}"#);
+    }
+
+    #[test]
+    fn test_try_from_node() {
+        let node = crate::json!({ "count": 300, "ratio": 0.5, "name": "S", "on": true, "big": u64::MAX });
+
+        assert_eq!(i32::try_from(node.pointer("/count").unwrap()), Ok(300));
+        assert_eq!(f32::try_from(node.pointer("/count").unwrap()), Ok(300.0));
+        assert_eq!(f64::try_from(node.pointer("/ratio").unwrap()), Ok(0.5));
+        assert_eq!(bool::try_from(node.pointer("/on").unwrap()), Ok(true));
+        assert_eq!(String::try_from(node.pointer("/name").unwrap()), Ok("S".to_string()));
+        assert_eq!(<&str>::try_from(node.pointer("/name").unwrap()), Ok("S"));
+        assert_eq!(u64::try_from(node.pointer("/big").unwrap()), Ok(u64::MAX));
+        assert_eq!(<&JsonObject>::try_from(&node).map(|obj| obj.size()), Ok(5));
+
+        let error = u8::try_from(node.pointer("/count").unwrap()).unwrap_err();
+        assert_eq!(error.kind, FromJsonErrorKind::OutOfRange { value: "300".to_string(), target: "u8" });
+
+        let error = i64::try_from(node.pointer("/big").unwrap()).unwrap_err();
+        assert_eq!(error.kind, FromJsonErrorKind::OutOfRange { value: u64::MAX.to_string(), target: "i64" });
+
+        let error = i32::try_from(node.pointer("/ratio").unwrap()).unwrap_err();
+        assert_eq!(error.kind, FromJsonErrorKind::UnexpectedType { expected: "integer", found: "number" });
+
+        let error = <&str>::try_from(node.pointer("/on").unwrap()).unwrap_err();
+        assert_eq!(error.to_string(), "/: expected string, found bool");
+
+        assert!(<&JsonArray>::try_from(&node).is_err());
+    }
+    #[test]
+    fn test_borrowed_parse() {
+        let json = r#"{ "title": "plain", "escaped": "tab\there", "nums": [1, -2.5, 18446744073709551615], "on": true, "none": null }"#;
+        let node = parse_borrowed(json).unwrap();
+
+        assert_eq!(node.to_node(), parse(json));
+
+        // Strings without escapes point into the source, escaped strings are decoded into their own buffer.
+        let title = node.get("title").unwrap();
+        assert!(matches!(title, JsonNodeRef::String(std::borrow::Cow::Borrowed("plain"))));
+        assert!(std::ptr::eq(title.as_str().unwrap().as_ptr(), json[json.find("plain").unwrap()..].as_ptr()));
+        assert!(matches!(node.get("escaped"), Some(JsonNodeRef::String(std::borrow::Cow::Owned(value))) if value == "tab\there"));
+
+        match &node {
+            JsonNodeRef::Object(entries) => assert!(entries.iter().all(|(key, _)| matches!(key, std::borrow::Cow::Borrowed(_)))),
+            _ => panic!("expected an object")
+        }
+
+        assert_eq!(node.pointer("/nums/1").and_then(|num| num.as_f64()), Some(-2.5));
+        assert_eq!(node.pointer("/nums/2").and_then(|num| num.as_u64()), Some(u64::MAX));
+        assert_eq!(node.pointer("/nums/0").and_then(|num| num.as_i64()), Some(1));
+        assert_eq!(node.pointer("/on").and_then(|on| on.as_bool()), Some(true));
+        assert_eq!(node.pointer("/none"), Some(&JsonNodeRef::Null));
+        assert_eq!(node.pointer("/nums/3"), None);
+
+        // The last of a repeated key wins, the same as the owned parser.
+        let repeated = r#"{ "a": 1, "b": 2, "a": 3 }"#;
+        assert_eq!(parse_borrowed(repeated).unwrap().get("a"), Some(&JsonNodeRef::Number(3)));
+        assert_eq!(parse_borrowed(repeated).unwrap().to_node(), parse(repeated));
+
+        let options = JsonOptions { arbitrary_precision: true, ..JsonOptions::relaxed() };
+        let relaxed = "{ key: 'single', raw: 1.10, list: [1, 2,], } // done";
+        let node = parse_borrowed_with_options(relaxed, &options).unwrap();
+
+        assert_eq!(node.get("raw"), Some(&JsonNodeRef::RawNumber("1.10")));
+        assert_eq!(node.get("key").and_then(|key| key.as_str()), Some("single"));
+        assert_eq!(node.to_node(), parse_with(relaxed, options).unwrap());
+    }
+
+    #[test]
+    fn test_borrowed_matches_owned() {
+        let mut documents: Vec<String> = [
+            HAPPY_TEST, SAD_TEST_UNTERMINATED_STR, EXPONENT_FLT_TEST,
+            "", "  ", "[", "[1,]", "{,}", r#"{"a" 1}"#, r#"{"a": 1 "b": 2}"#, "[1 2]", "[1] [2]", "nul", "'single'",
+            r#"{ key: 1 }"#, r#""é😀""#, r#""bad \x escape""#, "\"line\nbreak\"", "[-]", "/* open",
+            r#"{ "a": [1, { "b": 2 }] }"#, r#"["abcdefghijklmnopqrstuvwxyz", "short"]"#
+        ].iter().chain(VALID_DOCUMENTS.iter()).map(|json| json.to_string()).collect();
+
+        // Every input which once broke the parser as well.
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions");
+
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().is_some_and(|extension| extension == "json") {
+                documents.push(String::from_utf8_lossy(&std::fs::read(&path).unwrap()).to_string());
+            }
+        }
+
+        for json in &documents {
+            for flags in 0..16 {
+                let options = JsonOptions {
+                    arbitrary_precision: flags & 1 != 0,
+                    relaxed: flags & 2 != 0,
+                    max_depth: if flags & 4 != 0 { 2 } else { DEFAULT_MAX_DEPTH },
+                    max_string_length: if flags & 8 != 0 { 16 } else { usize::MAX },
+                    ..JsonOptions::default()
+                };
+
+                let borrowed = parse_borrowed_with_options(json, &options).map(|node| node.to_node());
+                assert_eq!(borrowed, parse_with(json, options), "{:?}", json);
+            }
+        }
+    }
+}
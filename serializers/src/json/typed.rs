@@ -0,0 +1,435 @@
+use std::{collections::HashMap, fmt::{Display, Formatter}, hash::Hash};
+
+use glmath::glmath::{vec2::Vec2, vec3::Vec3, vec4::Vec4, quat::Quat, vector::Vector, matrix::Matrix};
+
+use crate::json::parser::{JsonNode, JsonObject, JsonArray, JsonValue, JsonValueOps};
+use crate::json::pointer::escape_pointer_token;
+
+pub use serializers_derive::{ToJson, FromJson};
+
+/// Converts a rust value into a json node.
+pub trait ToJson {
+    fn to_json(&self) -> JsonNode;
+}
+
+/// Loads a rust value from a json node.
+pub trait FromJson: Sized {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError>;
+
+    /// The value used when a struct field is missing from its object.
+    /// Returning None means the field is required.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// The reason a json node could not be loaded into a value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FromJsonErrorKind {
+    /// The node was not of the expected json type.
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str
+    },
+    /// A required key was not in the object.
+    MissingField,
+    /// The number does not fit in the requested type.
+    OutOfRange {
+        value: String,
+        target: &'static str
+    },
+    /// A string was not one of the accepted values.
+    UnknownVariant {
+        found: String
+    },
+    Custom(String)
+}
+
+/// An error loading a typed value, along with the json pointer to the node which caused it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FromJsonError {
+    /// The keys and indices leading to the error, innermost last.
+    path: Vec<String>,
+    pub kind: FromJsonErrorKind
+}
+
+impl FromJsonError {
+    pub fn new(kind: FromJsonErrorKind) -> FromJsonError {
+        FromJsonError { path: Vec::new(), kind }
+    }
+
+    pub fn unexpected_type(expected: &'static str, found: &JsonNode) -> FromJsonError {
+        FromJsonError::new(FromJsonErrorKind::UnexpectedType { expected, found: json_type_name(found) })
+    }
+
+    pub fn missing_field(name: &str) -> FromJsonError {
+        FromJsonError::new(FromJsonErrorKind::MissingField).with_key(name)
+    }
+
+    pub fn custom(message: &str) -> FromJsonError {
+        FromJsonError::new(FromJsonErrorKind::Custom(message.to_string()))
+    }
+
+    /// Marks the error as having happened inside the value of the given object key.
+    pub fn with_key(mut self, key: &str) -> FromJsonError {
+        self.path.insert(0, key.to_string());
+        self
+    }
+
+    /// Marks the error as having happened inside the given array element.
+    pub fn with_index(mut self, index: usize) -> FromJsonError {
+        self.path.insert(0, index.to_string());
+        self
+    }
+
+    /// Returns the json pointer of the node which failed to load, ex: "/window_config/xres".
+    pub fn pointer(&self) -> String {
+        self.path.iter()
+            .map(|segment| format!("/{}", escape_pointer_token(segment)))
+            .collect()
+    }
+}
+
+impl Display for FromJsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pointer = self.pointer();
+        write!(f, "{}: ", if pointer.is_empty() { "/" } else { &pointer })?;
+
+        match &self.kind {
+            FromJsonErrorKind::UnexpectedType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+            FromJsonErrorKind::MissingField => write!(f, "missing required field"),
+            FromJsonErrorKind::OutOfRange { value, target } => write!(f, "{} is out of range for {}", value, target),
+            FromJsonErrorKind::UnknownVariant { found } => write!(f, "unknown value \"{}\"", found),
+            FromJsonErrorKind::Custom(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+/// Returns the name of the json type held by the node, used in error messages.
+pub fn json_type_name(node: &JsonNode) -> &'static str {
+    match node {
+        JsonNode::Object(_) => "object",
+        JsonNode::Array(_) => "array",
+        JsonNode::Number(_) | JsonNode::UnsignedNumber(_) | JsonNode::Float(_) | JsonNode::RawNumber(_) => "number",
+        JsonNode::Bool(_) => "bool",
+        JsonNode::String(_) => "string",
+        JsonNode::Null => "null"
+    }
+}
+
+impl ToJson for JsonNode {
+    fn to_json(&self) -> JsonNode {
+        self.clone()
+    }
+}
+
+impl FromJson for JsonNode {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        Ok(node.clone())
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> JsonNode {
+        JsonNode::Bool(JsonValue::new(*self))
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        match node {
+            JsonNode::Bool(value) => Ok(*value.get()),
+            _ => Err(FromJsonError::unexpected_type("bool", node))
+        }
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonNode {
+        JsonNode::String(JsonValue::new(self.clone()))
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonNode {
+        JsonNode::String(JsonValue::new(self.to_string()))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        match node {
+            JsonNode::String(value) => Ok(value.get().clone()),
+            _ => Err(FromJsonError::unexpected_type("string", node))
+        }
+    }
+}
+
+/// Integers load from any integer node as long as the value fits in the target type.
+/// Floats without a fractional part load too, ex: 60.0, the same numbers a schema's "integer" type accepts.
+macro_rules! impl_json_integer {
+    ($($int:ty),*) => {
+        $(
+            impl ToJson for $int {
+                fn to_json(&self) -> JsonNode {
+                    match i64::try_from(*self) {
+                        Ok(value) => JsonNode::Number(JsonValue::new(value)),
+                        Err(_) => JsonNode::UnsignedNumber(JsonValue::new(*self as u64))
+                    }
+                }
+            }
+
+            impl FromJson for $int {
+                fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+                    let out_of_range = |value: String| FromJsonError::new(FromJsonErrorKind::OutOfRange {
+                        value,
+                        target: stringify!($int)
+                    });
+
+                    // Whole floats are converted through the widest integer which holds them.
+                    let from_whole = |text: String| match (node.as_i64(), node.as_u64()) {
+                        (Some(value), _) => <$int>::try_from(value).map_err(|_| out_of_range(text)),
+                        (None, Some(value)) => <$int>::try_from(value).map_err(|_| out_of_range(text)),
+                        (None, None) => Err(out_of_range(text))
+                    };
+
+                    match node {
+                        JsonNode::Number(value) => <$int>::try_from(*value.get()).map_err(|_| out_of_range(value.to_string())),
+                        JsonNode::UnsignedNumber(value) => <$int>::try_from(*value.get()).map_err(|_| out_of_range(value.to_string())),
+                        JsonNode::Float(value) if value.get().fract() == 0.0 => from_whole(value.to_string()),
+                        JsonNode::RawNumber(value) => value.get().parse::<$int>()
+                            .or_else(|_| from_whole(value.get().clone())),
+                        _ => Err(FromJsonError::unexpected_type("integer", node))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_json_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Floats load from any number node, integers are converted.
+macro_rules! impl_json_float {
+    ($($flt:ty),*) => {
+        $(
+            impl FromJson for $flt {
+                fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+                    match node {
+                        JsonNode::Number(value) => Ok(*value.get() as $flt),
+                        JsonNode::UnsignedNumber(value) => Ok(*value.get() as $flt),
+                        JsonNode::Float(value) => Ok(*value.get() as $flt),
+                        JsonNode::RawNumber(value) => value.get().parse::<$flt>()
+                            .map_err(|_| FromJsonError::new(FromJsonErrorKind::OutOfRange {
+                                value: value.get().clone(),
+                                target: stringify!($flt)
+                            })),
+                        _ => Err(FromJsonError::unexpected_type("number", node))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_json_float!(f32, f64);
+
+impl ToJson for f32 {
+    fn to_json(&self) -> JsonNode {
+        // Going through the shortest text of the f32 keeps 0.1 as 0.1 instead of 0.10000000149011612.
+        JsonNode::Float(JsonValue::new(self.to_string().parse::<f64>().unwrap_or(*self as f64)))
+    }
+}
+
+impl ToJson for f64 {
+    fn to_json(&self) -> JsonNode {
+        JsonNode::Float(JsonValue::new(*self))
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonNode {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for Box<T> {
+    fn to_json(&self) -> JsonNode {
+        (**self).to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        T::from_json(node).map(Box::new)
+    }
+}
+
+/// None is written as null. Missing fields and null both load as None.
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonNode {
+        match self {
+            Some(value) => value.to_json(),
+            None => JsonNode::Null
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        match node {
+            JsonNode::Null => Ok(None),
+            _ => T::from_json(node).map(Some)
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonNode {
+        let mut array = JsonArray::new();
+
+        for value in self {
+            array.add(value.to_json());
+        }
+
+        JsonNode::Array(array)
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonNode {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        match node {
+            JsonNode::Array(array) => array.iter()
+                .enumerate()
+                .map(|(index, element)| T::from_json(element).map_err(|e| e.with_index(index)))
+                .collect(),
+            _ => Err(FromJsonError::unexpected_type("array", node))
+        }
+    }
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N] {
+    fn to_json(&self) -> JsonNode {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: FromJson, const N: usize> FromJson for [T; N] {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        let values = Vec::<T>::from_json(node)?;
+        let length = values.len();
+
+        values.try_into().map_err(|_| FromJsonError::custom(&format!("expected {} elements, found {}", N, length)))
+    }
+}
+
+/// Maps are stored as objects. Keys are written in sorted order so the output is stable.
+impl<K: AsRef<str> + Ord, V: ToJson> ToJson for HashMap<K, V> {
+    fn to_json(&self) -> JsonNode {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut object = JsonObject::new();
+
+        for (key, value) in entries {
+            object.add(key.as_ref(), value.to_json());
+        }
+
+        JsonNode::Object(object)
+    }
+}
+
+impl<K: From<String> + Eq + Hash, V: FromJson> FromJson for HashMap<K, V> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        match node {
+            JsonNode::Object(object) => object.iter()
+                .map(|(key, value)| {
+                    V::from_json(value)
+                        .map(|value| (K::from(key.clone()), value))
+                        .map_err(|e| e.with_key(key))
+                })
+                .collect(),
+            _ => Err(FromJsonError::unexpected_type("object", node))
+        }
+    }
+}
+
+/// Loads a named component of a vector or quaternion.
+/// Vectors can be written as objects with named components or as arrays.
+fn load_component<T: FromJson>(node: &JsonNode, name: &str, index: usize) -> Result<T, FromJsonError> {
+    match node {
+        JsonNode::Object(object) => match object.get(name) {
+            Some(component) => T::from_json(component).map_err(|e| e.with_key(name)),
+            None => Err(FromJsonError::missing_field(name))
+        },
+        JsonNode::Array(array) => match array.get(index) {
+            Some(component) => T::from_json(component).map_err(|e| e.with_index(index)),
+            None => Err(FromJsonError::custom(&format!("expected {} components, found {}", index + 1, array.size())))
+        },
+        _ => Err(FromJsonError::unexpected_type("object or array", node))
+    }
+}
+
+/// Implements the json traits for the vector and quaternion types as objects of their components.
+macro_rules! impl_json_components {
+    ($vector:ident, $($component:ident: $index:literal),*) => {
+        impl<T: PartialOrd + Copy + ToJson> ToJson for $vector<T> {
+            fn to_json(&self) -> JsonNode {
+                let mut object = JsonObject::new();
+                $( object.add(stringify!($component), self.$component.to_json()); )*
+                JsonNode::Object(object)
+            }
+        }
+
+        impl<T: PartialOrd + Copy + FromJson> FromJson for $vector<T> {
+            fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+                Ok($vector {
+                    $( $component: load_component(node, stringify!($component), $index)?, )*
+                })
+            }
+        }
+    };
+}
+
+impl_json_components!(Vec2, x: 0, y: 1);
+impl_json_components!(Vec3, x: 0, y: 1, z: 2);
+impl_json_components!(Vec4, x: 0, y: 1, z: 2, w: 3);
+impl_json_components!(Quat, x: 0, y: 1, z: 2, w: 3);
+
+/// Matrices of every size, including `Mat22`, `Mat33` and `Mat44`, are nested arrays in the same layout as `data`.
+impl<T: PartialOrd + Copy + ToJson, const R: usize, const C: usize> ToJson for Matrix<T, R, C> {
+    fn to_json(&self) -> JsonNode {
+        self.data.to_json()
+    }
+}
+
+impl<T: PartialOrd + Copy + FromJson, const R: usize, const C: usize> FromJson for Matrix<T, R, C> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        Ok(Matrix { data: FromJson::from_json(node)? })
+    }
+}
+
+/// Unlike the sized vectors the components of a `Vector` have no names, so it is an array.
+impl<T: PartialOrd + Copy + ToJson, const N: usize> ToJson for Vector<T, N> {
+    fn to_json(&self) -> JsonNode {
+        self.data.to_json()
+    }
+}
+
+impl<T: PartialOrd + Copy + FromJson, const N: usize> FromJson for Vector<T, N> {
+    fn from_json(node: &JsonNode) -> Result<Self, FromJsonError> {
+        Ok(Vector { data: FromJson::from_json(node)? })
+    }
+}
@@ -0,0 +1,238 @@
+use std::io::Write;
+
+use crate::json::parser::{JsonNode, JsonObject, JsonArray, JsonValueOps};
+
+/// How floating point numbers are formatted when written.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FloatFormat {
+    /// The shortest text which reads back as the exact same float.
+    /// A decimal point or exponent is always included so the value reloads as a float.
+    #[default]
+    Shortest,
+    /// A fixed number of digits after the decimal point.
+    /// Note: with zero decimals the value will be reloaded as an integer.
+    Fixed {
+        decimals: usize
+    }
+}
+
+/// The line ending written between lines of pretty printed json.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NewlineStyle {
+    #[default]
+    Lf,
+    CrLf
+}
+
+impl NewlineStyle {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NewlineStyle::Lf => "\n",
+            NewlineStyle::CrLf => "\r\n"
+        }
+    }
+}
+
+/// Settings which control how a json document is written.
+/// The default writes everything on a single line, the same as `to_string`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WriterConfig {
+    /// The number of spaces each nesting level is indented by.
+    /// Zero writes the whole document on one line.
+    pub indent_width: usize,
+    /// Writes object keys in sorted order instead of the order they are stored in.
+    pub sort_keys: bool,
+    pub float_format: FloatFormat,
+    pub newline: NewlineStyle
+}
+
+impl WriterConfig {
+    /// Readable output: four space indents, one value per line.
+    pub fn pretty() -> WriterConfig {
+        WriterConfig {
+            indent_width: 4,
+            ..WriterConfig::default()
+        }
+    }
+}
+
+/// Writes json nodes directly to any output stream.
+pub struct JsonWriter<W: Write> {
+    output: W,
+    config: WriterConfig
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(output: W, config: WriterConfig) -> JsonWriter<W> {
+        JsonWriter { output, config }
+    }
+
+    /// Writes a complete json node and everything it contains.
+    pub fn write_node(&mut self, node: &JsonNode) -> std::io::Result<()> {
+        self.write_node_at_depth(node, 0)
+    }
+
+    pub fn write_object(&mut self, obj: &JsonObject) -> std::io::Result<()> {
+        self.write_object_at_depth(obj, 0)
+    }
+
+    pub fn write_array(&mut self, arr: &JsonArray) -> std::io::Result<()> {
+        self.write_array_at_depth(arr, 0)
+    }
+
+    /// Returns the underlying output stream.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_node_at_depth(&mut self, node: &JsonNode, depth: usize) -> std::io::Result<()> {
+        match node {
+            JsonNode::Object(obj) => self.write_object_at_depth(obj, depth),
+            JsonNode::Array(arr) => self.write_array_at_depth(arr, depth),
+            JsonNode::Number(num) => write!(self.output, "{}", num.get()),
+            JsonNode::UnsignedNumber(num) => write!(self.output, "{}", num.get()),
+            JsonNode::Float(num) => self.write_float(*num.get()),
+            JsonNode::RawNumber(num) => self.output.write_all(num.get().as_bytes()),
+            JsonNode::Bool(val) => write!(self.output, "{}", val.get()),
+            JsonNode::String(str) => write_quoted_string(&mut self.output, str.get()),
+            JsonNode::Null => self.output.write_all(b"null")
+        }
+    }
+
+    fn write_object_at_depth(&mut self, obj: &JsonObject, depth: usize) -> std::io::Result<()> {
+        if obj.size() == 0 {
+            return self.output.write_all(b"{}");
+        }
+
+        let mut entries: Vec<(&String, &JsonNode)> = obj.iter().collect();
+
+        if self.config.sort_keys {
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+        }
+
+        let separator: &[u8] = if self.is_pretty() { b": " } else { b":" };
+
+        self.output.write_all(b"{")?;
+
+        for (index, (key, node)) in entries.into_iter().enumerate() {
+            if index > 0 {
+                self.output.write_all(b",")?;
+            }
+
+            self.write_line_break(depth + 1)?;
+            write_quoted_string(&mut self.output, key)?;
+            self.output.write_all(separator)?;
+            self.write_node_at_depth(node, depth + 1)?;
+        }
+
+        self.write_line_break(depth)?;
+        self.output.write_all(b"}")
+    }
+
+    fn write_array_at_depth(&mut self, arr: &JsonArray, depth: usize) -> std::io::Result<()> {
+        if arr.size() == 0 {
+            return self.output.write_all(b"[]");
+        }
+
+        self.output.write_all(b"[")?;
+
+        for (index, node) in arr.iter().enumerate() {
+            if index > 0 {
+                self.output.write_all(b",")?;
+            }
+
+            self.write_line_break(depth + 1)?;
+            self.write_node_at_depth(node, depth + 1)?;
+        }
+
+        self.write_line_break(depth)?;
+        self.output.write_all(b"]")
+    }
+
+    /// Json has no representation for NaN or infinity, so they are written as null.
+    fn write_float(&mut self, value: f64) -> std::io::Result<()> {
+        if !value.is_finite() {
+            return self.output.write_all(b"null");
+        }
+
+        match self.config.float_format {
+            FloatFormat::Shortest => write!(self.output, "{:?}", value),
+            FloatFormat::Fixed { decimals } => write!(self.output, "{:.*}", decimals, value)
+        }
+    }
+
+    fn is_pretty(&self) -> bool {
+        self.config.indent_width > 0
+    }
+
+    /// Starts a new line indented to the given depth when pretty printing.
+    fn write_line_break(&mut self, depth: usize) -> std::io::Result<()> {
+        if !self.is_pretty() {
+            return Ok(());
+        }
+
+        self.output.write_all(self.config.newline.as_str().as_bytes())?;
+
+        for _ in 0..(depth * self.config.indent_width) {
+            self.output.write_all(b" ")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a node to a string with the given settings.
+pub fn to_string_with_config(node: &JsonNode, config: WriterConfig) -> String {
+    write_to_string(config, |writer| writer.write_node(node))
+}
+
+pub(crate) fn write_to_string<F>(config: WriterConfig, write: F) -> String
+    where F: FnOnce(&mut JsonWriter<Vec<u8>>) -> std::io::Result<()>
+{
+    let mut writer = JsonWriter::new(Vec::<u8>::new(), config);
+
+    // Writing to memory cannot fail, and the writer only produces valid utf-8.
+    write(&mut writer).unwrap();
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+/// Escapes a string so that it can be written between quotes in a json document.
+/// Quotes, backslashes and control characters are escaped, everything else is written as is.
+pub fn escape_json_string(value: &str) -> String {
+    let mut escaped = Vec::<u8>::with_capacity(value.len());
+    write_escaped_string(&mut escaped, value).unwrap();
+    String::from_utf8(escaped).unwrap()
+}
+
+fn write_quoted_string<W: Write>(output: &mut W, value: &str) -> std::io::Result<()> {
+    output.write_all(b"\"")?;
+    write_escaped_string(output, value)?;
+    output.write_all(b"\"")
+}
+
+fn write_escaped_string<W: Write>(output: &mut W, value: &str) -> std::io::Result<()> {
+    // Runs of characters which need no escaping are written in one go.
+    let mut run_start = 0;
+
+    for (index, ch) in value.char_indices() {
+        if ch != '"' && ch != '\\' && (ch as u32) >= 0x20 {
+            continue;
+        }
+
+        output.write_all(&value.as_bytes()[run_start..index])?;
+        run_start = index + ch.len_utf8();
+
+        match ch {
+            '"' => output.write_all(b"\\\"")?,
+            '\\' => output.write_all(b"\\\\")?,
+            '\n' => output.write_all(b"\\n")?,
+            '\r' => output.write_all(b"\\r")?,
+            '\t' => output.write_all(b"\\t")?,
+            '\u{8}' => output.write_all(b"\\b")?,
+            '\u{c}' => output.write_all(b"\\f")?,
+            _ => write!(output, "\\u{:04x}", ch as u32)?
+        }
+    }
+
+    output.write_all(&value.as_bytes()[run_start..])
+}
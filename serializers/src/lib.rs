@@ -1 +1,8 @@
-pub mod json;
\ No newline at end of file
+// Lets the derive macros refer to this crate by name from inside of it.
+extern crate self as serializers;
+
+pub mod json;
+pub mod msgpack;
+
+#[cfg(test)]
+mod test_corpus;
@@ -0,0 +1,213 @@
+use std::io::{ErrorKind, Read};
+
+use crate::json::parser::{JsonNode, JsonObject, JsonArray, JsonValue};
+use crate::json::typed::FromJson;
+use crate::msgpack::error::MsgPackError;
+
+/// Arrays and maps nested deeper than this are rejected instead of overflowing the stack.
+pub const MAX_DEPTH: usize = 128;
+
+/// Reads MessagePack values from any input stream as json nodes.
+pub struct MsgPackReader<R: Read> {
+    input: R,
+    /// The number of bytes read so far.
+    offset: usize
+}
+
+impl<R: Read> MsgPackReader<R> {
+    pub fn new(input: R) -> MsgPackReader<R> {
+        MsgPackReader { input, offset: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads the next complete value from the input.
+    pub fn read_node(&mut self) -> Result<JsonNode, MsgPackError> {
+        self.read_node_at_depth(0)
+    }
+
+    /// Returns the underlying input stream.
+    pub fn into_inner(self) -> R {
+        self.input
+    }
+
+    fn read_node_at_depth(&mut self, depth: usize) -> Result<JsonNode, MsgPackError> {
+        let marker_offset = self.offset;
+        let marker = self.read_u8()?;
+
+        let node = match marker {
+            0x00..=0x7f => JsonNode::Number(JsonValue::new(marker as i64)),
+            0x80..=0x8f => self.read_map((marker & 0x0f) as usize, depth)?,
+            0x90..=0x9f => self.read_array((marker & 0x0f) as usize, depth)?,
+            0xa0..=0xbf => self.read_str((marker & 0x1f) as usize)?,
+            0xc0 => JsonNode::Null,
+            0xc2 => JsonNode::Bool(JsonValue::new(false)),
+            0xc3 => JsonNode::Bool(JsonValue::new(true)),
+            0xc4..=0xc6 => return Err(MsgPackError::UnsupportedType { name: "binary", offset: marker_offset }),
+            0xc7..=0xc9 | 0xd4..=0xd8 => return Err(MsgPackError::UnsupportedType { name: "extension", offset: marker_offset }),
+            0xca => {
+                let value = f32::from_be_bytes(self.read_bytes()?) as f64;
+                float_node(value, marker_offset)?
+            },
+            0xcb => {
+                let value = f64::from_be_bytes(self.read_bytes()?);
+                float_node(value, marker_offset)?
+            },
+            0xcc => JsonNode::Number(JsonValue::new(u8::from_be_bytes(self.read_bytes()?) as i64)),
+            0xcd => JsonNode::Number(JsonValue::new(u16::from_be_bytes(self.read_bytes()?) as i64)),
+            0xce => JsonNode::Number(JsonValue::new(u32::from_be_bytes(self.read_bytes()?) as i64)),
+            0xcf => {
+                // Integers are kept as a Number whenever they fit, the same as the json lexer.
+                let value = u64::from_be_bytes(self.read_bytes()?);
+
+                match i64::try_from(value) {
+                    Ok(value) => JsonNode::Number(JsonValue::new(value)),
+                    Err(_) => JsonNode::UnsignedNumber(JsonValue::new(value))
+                }
+            },
+            0xd0 => JsonNode::Number(JsonValue::new(i8::from_be_bytes(self.read_bytes()?) as i64)),
+            0xd1 => JsonNode::Number(JsonValue::new(i16::from_be_bytes(self.read_bytes()?) as i64)),
+            0xd2 => JsonNode::Number(JsonValue::new(i32::from_be_bytes(self.read_bytes()?) as i64)),
+            0xd3 => JsonNode::Number(JsonValue::new(i64::from_be_bytes(self.read_bytes()?))),
+            0xd9 => {
+                let length = u8::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_str(length)?
+            },
+            0xda => {
+                let length = u16::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_str(length)?
+            },
+            0xdb => {
+                let length = u32::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_str(length)?
+            },
+            0xdc => {
+                let length = u16::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_array(length, depth)?
+            },
+            0xdd => {
+                let length = u32::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_array(length, depth)?
+            },
+            0xde => {
+                let length = u16::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_map(length, depth)?
+            },
+            0xdf => {
+                let length = u32::from_be_bytes(self.read_bytes()?) as usize;
+                self.read_map(length, depth)?
+            },
+            0xe0..=0xff => JsonNode::Number(JsonValue::new(marker as i8 as i64)),
+            0xc1 => return Err(MsgPackError::InvalidMarker { marker, offset: marker_offset })
+        };
+
+        Ok(node)
+    }
+
+    fn read_array(&mut self, length: usize, depth: usize) -> Result<JsonNode, MsgPackError> {
+        if depth >= MAX_DEPTH {
+            return Err(MsgPackError::NestingTooDeep { offset: self.offset });
+        }
+
+        let mut array = JsonArray::new();
+
+        for _ in 0..length {
+            array.add(self.read_node_at_depth(depth + 1)?);
+        }
+
+        Ok(JsonNode::Array(array))
+    }
+
+    fn read_map(&mut self, length: usize, depth: usize) -> Result<JsonNode, MsgPackError> {
+        if depth >= MAX_DEPTH {
+            return Err(MsgPackError::NestingTooDeep { offset: self.offset });
+        }
+
+        let mut obj = JsonObject::new();
+
+        for _ in 0..length {
+            let key_offset = self.offset;
+
+            let key = match self.read_node_at_depth(depth + 1)? {
+                JsonNode::String(key) => key,
+                _ => return Err(MsgPackError::NonStringKey { offset: key_offset })
+            };
+
+            obj.add(&key.to_string(), self.read_node_at_depth(depth + 1)?);
+        }
+
+        Ok(JsonNode::Object(obj))
+    }
+
+    fn read_str(&mut self, length: usize) -> Result<JsonNode, MsgPackError> {
+        let str_offset = self.offset;
+        let mut bytes = Vec::new();
+
+        // The buffer grows as the data arrives, so a corrupt length cannot allocate huge amounts up front.
+        match (&mut self.input).take(length as u64).read_to_end(&mut bytes) {
+            Ok(read) => self.offset += read,
+            Err(error) => return Err(self.io_error(error))
+        }
+
+        if bytes.len() < length {
+            return Err(MsgPackError::UnexpectedEof { offset: self.offset });
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(value) => Ok(JsonNode::String(JsonValue::new(value))),
+            Err(_) => Err(MsgPackError::InvalidUtf8 { offset: str_offset })
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, MsgPackError> {
+        Ok(u8::from_be_bytes(self.read_bytes()?))
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], MsgPackError> {
+        let mut bytes = [0; N];
+
+        match self.input.read_exact(&mut bytes) {
+            Ok(()) => {
+                self.offset += N;
+                Ok(bytes)
+            },
+            Err(error) => Err(self.io_error(error))
+        }
+    }
+
+    fn io_error(&self, error: std::io::Error) -> MsgPackError {
+        match error.kind() {
+            ErrorKind::UnexpectedEof => MsgPackError::UnexpectedEof { offset: self.offset },
+            kind => MsgPackError::Io { kind, message: error.to_string(), offset: self.offset }
+        }
+    }
+}
+
+fn float_node(value: f64, offset: usize) -> Result<JsonNode, MsgPackError> {
+    if value.is_finite() {
+        Ok(JsonNode::Float(JsonValue::new(value)))
+    }
+    else {
+        Err(MsgPackError::NonFiniteFloat { offset })
+    }
+}
+
+/// Decodes a complete MessagePack document. Nothing may follow the first value.
+pub fn decode(bytes: &[u8]) -> Result<JsonNode, MsgPackError> {
+    let mut reader = MsgPackReader::new(bytes);
+    let node = reader.read_node()?;
+
+    if reader.offset() < bytes.len() {
+        return Err(MsgPackError::TrailingData { offset: reader.offset() });
+    }
+
+    Ok(node)
+}
+
+/// Decodes a complete MessagePack document into any value which can be loaded from json.
+pub fn from_msgpack<T: FromJson>(bytes: &[u8]) -> Result<T, MsgPackError> {
+    Ok(T::from_json(&decode(bytes)?)?)
+}
@@ -0,0 +1,176 @@
+use std::io::Write;
+
+use crate::json::parser::{JsonNode, JsonObject, JsonArray, JsonValueOps};
+use crate::json::typed::ToJson;
+
+/// Writes json nodes to any output stream in the MessagePack format.
+/// Every value uses the smallest encoding which holds it exactly.
+pub struct MsgPackWriter<W: Write> {
+    output: W
+}
+
+impl<W: Write> MsgPackWriter<W> {
+    pub fn new(output: W) -> MsgPackWriter<W> {
+        MsgPackWriter { output }
+    }
+
+    /// Writes a complete node and everything it contains.
+    /// Raw numbers are written as the closest integer or float, since MessagePack has no arbitrary precision numbers.
+    pub fn write_node(&mut self, node: &JsonNode) -> std::io::Result<()> {
+        match node {
+            JsonNode::Object(obj) => self.write_object(obj),
+            JsonNode::Array(arr) => self.write_array(arr),
+            JsonNode::Number(num) => self.write_int(*num.get()),
+            JsonNode::UnsignedNumber(num) => self.write_uint(*num.get()),
+            JsonNode::Float(num) => self.write_float(*num.get()),
+            JsonNode::RawNumber(num) => {
+                if let Ok(value) = num.get().parse::<i64>() {
+                    self.write_int(value)
+                }
+                else if let Ok(value) = num.get().parse::<u64>() {
+                    self.write_uint(value)
+                }
+                else {
+                    // The lexer only produces valid json numbers, which always parse as a float.
+                    self.write_float(num.get().parse::<f64>().unwrap_or_default())
+                }
+            },
+            JsonNode::Bool(val) => self.output.write_all(if *val.get() { &[0xc3] } else { &[0xc2] }),
+            JsonNode::String(str) => self.write_str(str.get()),
+            JsonNode::Null => self.output.write_all(&[0xc0])
+        }
+    }
+
+    pub fn write_object(&mut self, obj: &JsonObject) -> std::io::Result<()> {
+        self.write_length(obj.size(), 0x80, 0xde)?;
+
+        for (key, node) in obj.iter() {
+            self.write_str(key)?;
+            self.write_node(node)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_array(&mut self, arr: &JsonArray) -> std::io::Result<()> {
+        self.write_length(arr.size(), 0x90, 0xdc)?;
+
+        for node in arr.iter() {
+            self.write_node(node)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying output stream.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_int(&mut self, value: i64) -> std::io::Result<()> {
+        if value >= 0 {
+            return self.write_uint(value as u64);
+        }
+
+        // Negative fixints hold -32 to -1 in the marker itself.
+        if value >= -32 {
+            self.output.write_all(&[value as i8 as u8])
+        }
+        else if let Ok(value) = i8::try_from(value) {
+            self.write_marked(0xd0, &value.to_be_bytes())
+        }
+        else if let Ok(value) = i16::try_from(value) {
+            self.write_marked(0xd1, &value.to_be_bytes())
+        }
+        else if let Ok(value) = i32::try_from(value) {
+            self.write_marked(0xd2, &value.to_be_bytes())
+        }
+        else {
+            self.write_marked(0xd3, &value.to_be_bytes())
+        }
+    }
+
+    fn write_uint(&mut self, value: u64) -> std::io::Result<()> {
+        if value < 0x80 {
+            self.output.write_all(&[value as u8])
+        }
+        else if let Ok(value) = u8::try_from(value) {
+            self.write_marked(0xcc, &value.to_be_bytes())
+        }
+        else if let Ok(value) = u16::try_from(value) {
+            self.write_marked(0xcd, &value.to_be_bytes())
+        }
+        else if let Ok(value) = u32::try_from(value) {
+            self.write_marked(0xce, &value.to_be_bytes())
+        }
+        else {
+            self.write_marked(0xcf, &value.to_be_bytes())
+        }
+    }
+
+    /// Floats which survive the round trip through an f32 are written in half the space.
+    /// NaN and infinity are written as nil, the same as the json writer writes them as null,
+    /// since the decoder only accepts the finite floats json can hold.
+    fn write_float(&mut self, value: f64) -> std::io::Result<()> {
+        if !value.is_finite() {
+            return self.output.write_all(&[0xc0]);
+        }
+
+        if (value as f32) as f64 == value {
+            self.write_marked(0xca, &(value as f32).to_be_bytes())
+        }
+        else {
+            self.write_marked(0xcb, &value.to_be_bytes())
+        }
+    }
+
+    fn write_str(&mut self, value: &str) -> std::io::Result<()> {
+        let length = value.len();
+
+        if length < 32 {
+            self.output.write_all(&[0xa0 | length as u8])?;
+        }
+        else if let Ok(length) = u8::try_from(length) {
+            self.write_marked(0xd9, &length.to_be_bytes())?;
+        }
+        else {
+            self.write_length(length, 0xa0, 0xda)?;
+        }
+
+        self.output.write_all(value.as_bytes())
+    }
+
+    /// Writes the length of a string, array or map. Lengths below 16 fit in the fixed marker,
+    /// larger lengths use the 16 bit marker, or the 32 bit marker which always follows it.
+    fn write_length(&mut self, length: usize, fixed_marker: u8, marker_16: u8) -> std::io::Result<()> {
+        if length < 16 {
+            self.output.write_all(&[fixed_marker | length as u8])
+        }
+        else if let Ok(length) = u16::try_from(length) {
+            self.write_marked(marker_16, &length.to_be_bytes())
+        }
+        else {
+            // MessagePack cannot hold more than u32::MAX items, which would not fit in memory as json anyway.
+            self.write_marked(marker_16 + 1, &(length as u32).to_be_bytes())
+        }
+    }
+
+    fn write_marked(&mut self, marker: u8, bytes: &[u8]) -> std::io::Result<()> {
+        self.output.write_all(&[marker])?;
+        self.output.write_all(bytes)
+    }
+}
+
+/// Encodes a node as MessagePack.
+pub fn encode(node: &JsonNode) -> Vec<u8> {
+    let mut writer = MsgPackWriter::new(Vec::<u8>::new());
+
+    // Writing to memory cannot fail.
+    writer.write_node(node).unwrap();
+    writer.into_inner()
+}
+
+/// Encodes any value with a json representation as MessagePack.
+pub fn to_msgpack<T: ToJson + ?Sized>(value: &T) -> Vec<u8> {
+    encode(&value.to_json())
+}
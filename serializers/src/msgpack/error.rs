@@ -0,0 +1,94 @@
+use std::fmt::{Display, Formatter};
+
+use crate::json::typed::FromJsonError;
+
+/// Every error which can be produced while decoding a MessagePack document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MsgPackError {
+    /// The input ended in the middle of a value.
+    UnexpectedEof {
+        offset: usize
+    },
+    /// Reading from the input stream failed.
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+        offset: usize
+    },
+    /// The marker byte 0xc1, which is never used.
+    InvalidMarker {
+        marker: u8,
+        offset: usize
+    },
+    /// A MessagePack type with no json equivalent, like binary data or extension types.
+    UnsupportedType {
+        name: &'static str,
+        offset: usize
+    },
+    /// Only maps with string keys can be loaded as json objects.
+    NonStringKey {
+        offset: usize
+    },
+    InvalidUtf8 {
+        offset: usize
+    },
+    /// Json has no representation for NaN or infinity.
+    NonFiniteFloat {
+        offset: usize
+    },
+    /// Arrays and maps are nested deeper than the decoder allows.
+    NestingTooDeep {
+        offset: usize
+    },
+    /// A complete value was decoded, but more bytes followed it.
+    TrailingData {
+        offset: usize
+    },
+    /// The document decoded, but could not be loaded into the requested type.
+    Typed(FromJsonError)
+}
+
+impl MsgPackError {
+    /// Returns the byte offset in the input where the error occurred.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            MsgPackError::UnexpectedEof { offset } => Some(*offset),
+            MsgPackError::Io { offset, .. } => Some(*offset),
+            MsgPackError::InvalidMarker { offset, .. } => Some(*offset),
+            MsgPackError::UnsupportedType { offset, .. } => Some(*offset),
+            MsgPackError::NonStringKey { offset } => Some(*offset),
+            MsgPackError::InvalidUtf8 { offset } => Some(*offset),
+            MsgPackError::NonFiniteFloat { offset } => Some(*offset),
+            MsgPackError::NestingTooDeep { offset } => Some(*offset),
+            MsgPackError::TrailingData { offset } => Some(*offset),
+            MsgPackError::Typed(_) => None
+        }
+    }
+}
+
+impl Display for MsgPackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MsgPackError::UnexpectedEof { .. } => write!(f, "unexpected end of the input")?,
+            MsgPackError::Io { message, .. } => write!(f, "unable to read the input: {}", message)?,
+            MsgPackError::InvalidMarker { marker, .. } => write!(f, "invalid marker 0x{:02x}", marker)?,
+            MsgPackError::UnsupportedType { name, .. } => write!(f, "{} values cannot be loaded as json", name)?,
+            MsgPackError::NonStringKey { .. } => write!(f, "map keys must be strings")?,
+            MsgPackError::InvalidUtf8 { .. } => write!(f, "string is not valid utf-8")?,
+            MsgPackError::NonFiniteFloat { .. } => write!(f, "float is not a finite number")?,
+            MsgPackError::NestingTooDeep { .. } => write!(f, "values are nested too deeply")?,
+            MsgPackError::TrailingData { .. } => write!(f, "unexpected data after the end of the document")?,
+            MsgPackError::Typed(error) => return write!(f, "{}", error)
+        }
+
+        write!(f, " at byte {}", self.offset().unwrap_or_default())
+    }
+}
+
+impl std::error::Error for MsgPackError {}
+
+impl From<FromJsonError> for MsgPackError {
+    fn from(error: FromJsonError) -> Self {
+        MsgPackError::Typed(error)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod encoder;
+pub mod decoder;
+mod tests;
\ No newline at end of file
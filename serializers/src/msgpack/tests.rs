@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use crate::test_corpus::VALID_DOCUMENTS;
+    use crate::json::typed::{ToJson, FromJson};
+    use crate::json::{lexer::JsonLexer, parser::{JsonNode, JsonValue, JsonArray, JsonObject, parse_json}, writer::{to_string_with_config, WriterConfig}};
+    use crate::msgpack::{encoder::{MsgPackWriter, encode, to_msgpack}, decoder::{MsgPackReader, decode, from_msgpack, MAX_DEPTH}, error::MsgPackError};
+
+    fn parse(json: &str) -> JsonNode {
+        parse_json(&mut JsonLexer::from_raw_json(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_corpus() {
+        for json in VALID_DOCUMENTS {
+            let node = parse(json);
+            let decoded = decode(&encode(&node)).unwrap();
+
+            assert_eq!(decoded, node, "{}", json);
+
+            // The decoded tree writes out the same json as the original.
+            assert_eq!(to_string_with_config(&decoded, WriterConfig::default()), to_string_with_config(&node, WriterConfig::default()));
+        }
+    }
+
+    #[test]
+    fn test_non_finite_floats() {
+        let mut array = JsonArray::new();
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            array.add(JsonNode::Float(JsonValue::new(value)));
+        }
+
+        // Floats json can't hold become nil, the same as the json writer writes null.
+        let node = JsonNode::Array(array);
+        let bytes = encode(&node);
+        assert_eq!(bytes, vec![0x93, 0xc0, 0xc0, 0xc0]);
+
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded, parse("[null, null, null]"));
+        assert_eq!(to_string_with_config(&decoded, WriterConfig::default()), to_string_with_config(&node, WriterConfig::default()));
+    }
+
+    #[test]
+    fn test_scalar_encodings() {
+        let cases: Vec<(JsonNode, Vec<u8>)> = vec![
+            (JsonNode::Null, vec![0xc0]),
+            (JsonNode::Bool(JsonValue::new(false)), vec![0xc2]),
+            (JsonNode::Bool(JsonValue::new(true)), vec![0xc3]),
+            (JsonNode::Number(JsonValue::new(0)), vec![0x00]),
+            (JsonNode::Number(JsonValue::new(127)), vec![0x7f]),
+            (JsonNode::Number(JsonValue::new(128)), vec![0xcc, 0x80]),
+            (JsonNode::Number(JsonValue::new(256)), vec![0xcd, 0x01, 0x00]),
+            (JsonNode::Number(JsonValue::new(65536)), vec![0xce, 0x00, 0x01, 0x00, 0x00]),
+            (JsonNode::Number(JsonValue::new(1 << 32)), vec![0xcf, 0, 0, 0, 1, 0, 0, 0, 0]),
+            (JsonNode::Number(JsonValue::new(-1)), vec![0xff]),
+            (JsonNode::Number(JsonValue::new(-32)), vec![0xe0]),
+            (JsonNode::Number(JsonValue::new(-33)), vec![0xd0, 0xdf]),
+            (JsonNode::Number(JsonValue::new(-129)), vec![0xd1, 0xff, 0x7f]),
+            (JsonNode::Number(JsonValue::new(-32769)), vec![0xd2, 0xff, 0xff, 0x7f, 0xff]),
+            (JsonNode::Number(JsonValue::new(i64::MIN)), vec![0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0]),
+            (JsonNode::UnsignedNumber(JsonValue::new(u64::MAX)), vec![0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            // Floats which fit in an f32 exactly use the smaller encoding.
+            (JsonNode::Float(JsonValue::new(1.5)), vec![0xca, 0x3f, 0xc0, 0x00, 0x00]),
+            (JsonNode::Float(JsonValue::new(0.1)), vec![0xcb, 0x3f, 0xb9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]),
+            (JsonNode::String(JsonValue::new("abc".to_string())), vec![0xa3, b'a', b'b', b'c']),
+        ];
+
+        for (node, bytes) in cases {
+            assert_eq!(encode(&node), bytes, "{:?}", node);
+            assert_eq!(decode(&bytes).unwrap(), node);
+        }
+
+        // Raw numbers have no MessagePack equivalent, so they come back as plain numbers.
+        assert_eq!(encode(&JsonNode::RawNumber(JsonValue::new("42".to_string()))), vec![0x2a]);
+        assert_eq!(encode(&JsonNode::RawNumber(JsonValue::new("1.5".to_string()))), vec![0xca, 0x3f, 0xc0, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_length_encodings() {
+        // The headers of a string, array and map of each size.
+        let sizes: [(usize, [&[u8]; 3]); 5] = [
+            (0, [&[0xa0], &[0x90], &[0x80]]),
+            (15, [&[0xaf], &[0x9f], &[0x8f]]),
+            (31, [&[0xbf], &[0xdc, 0x00, 31], &[0xde, 0x00, 31]]),
+            (255, [&[0xd9, 0xff], &[0xdc, 0x00, 0xff], &[0xde, 0x00, 0xff]]),
+            (70000, [&[0xdb, 0x00, 0x01, 0x11, 0x70], &[0xdd, 0x00, 0x01, 0x11, 0x70], &[0xdf, 0x00, 0x01, 0x11, 0x70]]),
+        ];
+
+        for (size, headers) in sizes {
+            let str_node = JsonNode::String(JsonValue::new("x".repeat(size)));
+            let mut array = JsonArray::new();
+            let mut obj = JsonObject::new();
+
+            for index in 0..size {
+                array.add(JsonNode::Null);
+                obj.add(&index.to_string(), JsonNode::Null);
+            }
+
+            let nodes = [str_node, JsonNode::Array(array), JsonNode::Object(obj)];
+
+            for (node, header) in nodes.into_iter().zip(headers) {
+                let bytes = encode(&node);
+
+                assert!(bytes.starts_with(header), "size {}", size);
+                assert_eq!(decode(&bytes).unwrap(), node);
+            }
+        }
+
+        // Strings of 256 bytes or more skip str8.
+        assert!(encode(&JsonNode::String(JsonValue::new("x".repeat(256)))).starts_with(&[0xda, 0x01, 0x00]));
+    }
+
+    #[test]
+    fn test_decode_wider_encodings() {
+        // Other encoders may not use the smallest encoding, which must still load.
+        assert_eq!(decode(&[0xcd, 0x00, 0x01]).unwrap(), JsonNode::Number(JsonValue::new(1)));
+        assert_eq!(decode(&[0xd0, 0x05]).unwrap(), JsonNode::Number(JsonValue::new(5)));
+        assert_eq!(decode(&[0xd9, 0x01, b'a']).unwrap(), JsonNode::String(JsonValue::new("a".to_string())));
+        assert_eq!(decode(&[0xdc, 0x00, 0x01, 0xc0]).unwrap(), parse("[null]"));
+        assert_eq!(decode(&[0xdf, 0x00, 0x00, 0x00, 0x01, 0xa1, b'k', 0xc3]).unwrap(), parse(r#"{ "k": true }"#));
+    }
+
+    #[test]
+    fn test_stream_multiple_values() {
+        let mut writer = MsgPackWriter::new(Vec::new());
+        writer.write_node(&parse("[1, 2]")).unwrap();
+        writer.write_node(&parse(r#"{ "a": "b" }"#)).unwrap();
+
+        let bytes = writer.into_inner();
+        let mut reader = MsgPackReader::new(bytes.as_slice());
+
+        assert_eq!(reader.read_node().unwrap(), parse("[1, 2]"));
+        assert_eq!(reader.offset(), 3);
+        assert_eq!(reader.read_node().unwrap(), parse(r#"{ "a": "b" }"#));
+        assert_eq!(reader.offset(), bytes.len());
+        assert_eq!(reader.read_node(), Err(MsgPackError::UnexpectedEof { offset: bytes.len() }));
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct TestSave {
+        name: String,
+        level: u32,
+        position: [f32; 2],
+        inventory: HashMap<String, i64>,
+        checkpoint: Option<String>
+    }
+
+    #[test]
+    fn test_typed_round_trip() {
+        let save = TestSave {
+            name: "player".to_string(),
+            level: 12,
+            position: [1.5, -3.25],
+            inventory: HashMap::from([("apples".to_string(), 3), ("coins".to_string(), -40)]),
+            checkpoint: None
+        };
+
+        let bytes = to_msgpack(&save);
+
+        assert_eq!(from_msgpack::<TestSave>(&bytes).unwrap(), save);
+        assert!(bytes.len() < save.to_json().to_string().len());
+
+        // Errors from loading the typed value keep their json pointer.
+        let error = from_msgpack::<TestSave>(&encode(&parse(r#"{ "name": "player", "level": "high" }"#))).unwrap_err();
+
+        assert!(matches!(error, MsgPackError::Typed(_)));
+        assert!(error.to_string().starts_with("/level"), "{}", error);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode(&[]), Err(MsgPackError::UnexpectedEof { offset: 0 }));
+        assert_eq!(decode(&[0xcd, 0x01]), Err(MsgPackError::UnexpectedEof { offset: 1 }));
+        assert_eq!(decode(&[0xa3, b'a', b'b']), Err(MsgPackError::UnexpectedEof { offset: 3 }));
+        assert_eq!(decode(&[0x92, 0x01]), Err(MsgPackError::UnexpectedEof { offset: 2 }));
+        assert_eq!(decode(&[0xc1]), Err(MsgPackError::InvalidMarker { marker: 0xc1, offset: 0 }));
+        assert_eq!(decode(&[0x91, 0xc4, 0x01, 0x00]), Err(MsgPackError::UnsupportedType { name: "binary", offset: 1 }));
+        assert_eq!(decode(&[0xd4, 0x01, 0x00]), Err(MsgPackError::UnsupportedType { name: "extension", offset: 0 }));
+        assert_eq!(decode(&[0x81, 0x01, 0xc0]), Err(MsgPackError::NonStringKey { offset: 1 }));
+        assert_eq!(decode(&[0xa2, 0xff, 0xfe]), Err(MsgPackError::InvalidUtf8 { offset: 1 }));
+        assert_eq!(decode(&[0xc0, 0xc0]), Err(MsgPackError::TrailingData { offset: 1 }));
+        assert_eq!(decode(&[0x91, 0xca, 0x7f, 0xc0, 0x00, 0x00]), Err(MsgPackError::NonFiniteFloat { offset: 1 }));
+        assert_eq!(decode(&[0xcb, 0xff, 0xf0, 0, 0, 0, 0, 0, 0]), Err(MsgPackError::NonFiniteFloat { offset: 0 }));
+
+        // A huge length in a tiny input fails without allocating the whole length first.
+        assert_eq!(decode(&[0xdb, 0xff, 0xff, 0xff, 0xff, b'a']), Err(MsgPackError::UnexpectedEof { offset: 6 }));
+        assert_eq!(decode(&[0xdd, 0xff, 0xff, 0xff, 0xff]), Err(MsgPackError::UnexpectedEof { offset: 5 }));
+
+        let deep = vec![0x91; MAX_DEPTH + 1];
+        assert_eq!(decode(&deep), Err(MsgPackError::NestingTooDeep { offset: MAX_DEPTH + 1 }));
+
+        assert_eq!(MsgPackError::InvalidMarker { marker: 0xc1, offset: 4 }.to_string(), "invalid marker 0xc1 at byte 4");
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        // Inputs which once crashed, or could crash, the decoder. Each one must return instead of panicking.
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions");
+        let mut count = 0;
+
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().is_none_or(|extension| extension != "msgpack") {
+                continue;
+            }
+
+            if let Ok(node) = decode(&std::fs::read(&path).unwrap()) {
+                assert_eq!(decode(&encode(&node)).unwrap(), node, "{:?}", path);
+            }
+
+            count += 1;
+        }
+
+        assert!(count > 0);
+    }
+}
@@ -0,0 +1,51 @@
+//! Documents shared by the json and MessagePack tests, so both check the same inputs.
+
+pub const HAPPY_TEST: &str = r#"{
+        "glossary": {
+            "title": "example glossary",
+            "GlossDiv": {
+                "title": "S",
+                "count": 5.123,
+                "hours": -1,
+                "is_true": false,
+                "test_null": null
+            }
+        },
+        "array": [1234567890123, -12.1, "S"]
+        }"#;
+
+pub const SAD_TEST_UNTERMINATED_STR: &str = r#"{
+        "glossary: {
+            "title": "example glossary",
+            "GlossDiv": {
+                "title": "S",
+                "count": 5.123,
+                "hours": -1
+            }
+        },
+        "array": [1234567890123, -12.1, "S"]
+        }"#;
+
+pub const EXPONENT_FLT_TEST: &str = r#"{
+            "glossary": {
+                "title": "example glossary",
+                "GlossDiv": {
+                    "title": "S",
+                    "count": 5123e-3,
+                    "hours": -1
+                }
+            },
+            "array": [1234567890123, -12.1, "S"]
+        }"#;
+
+/// Documents which parse, covering every kind of value and the edges of each number encoding.
+pub const VALID_DOCUMENTS: [&str; 8] = [
+    HAPPY_TEST,
+    EXPONENT_FLT_TEST,
+    r#"{ "small": 1.5e-10, "large": 6.02e23, "negative": -2.5E+3 }"#,
+    r#"[0, 127, 128, 255, 256, 65535, 65536, 4294967295, 4294967296, 18446744073709551615]"#,
+    r#"[-1, -32, -33, -128, -129, -32768, -32769, -2147483648, -2147483649, -9223372036854775808]"#,
+    r#"{ "empty_object": {}, "empty_array": [], "empty_string": "", "unicode": "café 😀" }"#,
+    r#"[[[[[["nested"]]]]], { "a": { "b": { "c": [true, false, null] } } }]"#,
+    r#""just a string""#
+];
@@ -1,8 +1,10 @@
 use core_engine::{self, engine::GameManager, shader_program::{ShaderProgram, ShaderUniforms}, mesh::{Mesh2D, DrawableMesh}, texture::Texture, MouseKeyboardInputControl};
-use glmath::glmath::Vec2f;
+use glmath::glmath::{Vec2f, geometry::Aabb2f};
 use core_engine::render_pipeline::*;
 use rand::Rng;
 use timer::Stopwatch;
+use tween::{easing::Easing, tween::{Tween, Repeat, Animation}, group::Sequence};
+use std::{cell::Cell, rc::Rc};
 
 struct SnakeRenderPipeline {
     background_mesh: Mesh2D,
@@ -16,9 +18,14 @@ struct SnakeRenderPipeline {
     // The last movement direction is set once the movement direction changes from the x to y axis or vice versa. It is cleared once it's consumed.
     last_movement_direction: Vec2f,
     location_pos: i32,
+    location_scale: i32,
     speed: i32,
     update_count: i32,
     next_segment_pos: Option<Vec2f>,
+    // The food pops in when it spawns and then pulses, scaling its tile.
+    food_animation: Sequence,
+    food_scale: Rc<Cell<f32>>,
+    food_timer: Stopwatch,
     game_over: bool,
 }
 
@@ -43,6 +50,15 @@ impl SnakeRenderPipeline {
         let head_texture = game_manager.resources.texture_resources.get_registry("tex_snake_head").unwrap().clone();
         let food_texture = game_manager.resources.texture_resources.get_registry("tex_snake_food").unwrap().clone();
 
+        let food_scale = Rc::new(Cell::new(0.0));
+        let food_animation = Sequence::new()
+            .then(Tween::new(0.0, 1.0, 0.3).with_easing(Easing::BackOut).with_output(food_scale.clone()))
+            .then(Tween::new(1.0, 0.8, 0.5)
+                .with_easing(Easing::SineInOut)
+                .with_yoyo()
+                .with_repeat(Repeat::Forever)
+                .with_output(food_scale.clone()));
+
         SnakeRenderPipeline { 
             background_mesh: mesh,
             gui_shader,
@@ -54,9 +70,13 @@ impl SnakeRenderPipeline {
             movement_direction: Vec2f::new(0.0, 1.0),
             last_movement_direction: Vec2f::new(0.0, 0.0),
             location_pos: 0,
+            location_scale: 0,
             speed: 9,
             update_count: 0,
             next_segment_pos: None,
+            food_animation,
+            food_scale,
+            food_timer: Stopwatch::new(),
             game_over: false,
         }
     }
@@ -72,12 +92,15 @@ impl SnakeRenderPipeline {
         let y = thread_rng.gen_range(lower_range..=upper_range);
 
         self.next_segment_pos = Some(Vec2f::new(x as f32 * self.tile_size, y as f32 * self.tile_size));
+
+        self.food_animation.reset();
+        self.food_timer.start();
     }
 
     /// Checks whether the position collides with the square.
     fn check_collision(&mut self, pos: Vec2f, sq: Vec2f) -> bool {
-        (pos.x >= sq.x - self.tile_size / 2.0) && (pos.x <= sq.x + self.tile_size / 2.0) && 
-            (pos.y >= sq.y - self.tile_size / 2.0) && (pos.y <= sq.y + self.tile_size / 2.0)
+        let half_tile_size = self.tile_size / 2.0;
+        Aabb2f::from_center(sq, Vec2f::new(half_tile_size, half_tile_size)).contains_point(pos)
     }
 
     fn handle_movement(&mut self, direction: Vec2f) {
@@ -134,10 +157,9 @@ impl RenderPipelineHandler for SnakeRenderPipeline {
         self.gui_shader.bind();
 
         self.location_pos = self.gui_shader.get_uniform_location("pos");
-        let location_scale = self.gui_shader.get_uniform_location("scale");
+        self.location_scale = self.gui_shader.get_uniform_location("scale");
         let location_gui_texture = self.gui_shader.get_uniform_location("guiTexture");
 
-        self.gui_shader.load_vec2(location_scale, glmath::glmath::Vec2f::new(self.tile_size / 2.0, self.tile_size / 2.0));
         self.gui_shader.load_int(location_gui_texture, 0);
     }
 
@@ -146,6 +168,9 @@ impl RenderPipelineHandler for SnakeRenderPipeline {
     }
 
     fn execute(&self) {
+        let half_tile_size = self.tile_size / 2.0;
+        self.gui_shader.load_vec2(self.location_scale, Vec2f::new(half_tile_size, half_tile_size));
+
         // Render the snake head.
         self.head_texture.bind(0);
         self.gui_shader.load_vec2(self.location_pos, self.pos[0]);
@@ -161,6 +186,8 @@ impl RenderPipelineHandler for SnakeRenderPipeline {
         self.food_texture.bind(0);
         match self.next_segment_pos {
             Some(segment_pos) => {
+                let food_size = half_tile_size * self.food_scale.get();
+                self.gui_shader.load_vec2(self.location_scale, Vec2f::new(food_size, food_size));
                 self.gui_shader.load_vec2(self.location_pos, segment_pos);
                 self.background_mesh.render();
             }
@@ -169,6 +196,8 @@ impl RenderPipelineHandler for SnakeRenderPipeline {
     }
 
     fn update(&mut self, input: &Box<dyn MouseKeyboardInputControl>) {
+        self.food_animation.update(&self.food_timer);
+
         self.update_count += 1;
         if self.update_count >= self.speed && !self.game_over {
             self.handle_movement(self.movement_direction);
@@ -195,8 +224,10 @@ impl RenderPipelineHandler for SnakeRenderPipeline {
 }
 
 fn main() {
+    // Platform and per developer overrides are merged over the base config when they exist.
+    let platform_config = format!("app_config.{}.json", std::env::consts::OS);
     let game_manager = GameManager::from_conf
-        ("./res", "app_config.json");
+        ("./res", &["app_config.json", &platform_config, "app_config.local.json"]);
 
     match game_manager {
         Some(mut game_manager) => {
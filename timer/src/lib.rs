@@ -1,5 +1,10 @@
 use std::{time::SystemTime};
 
+/// Anything which measures the time passed since it started, ex: to drive animations.
+pub trait TimeSource {
+    fn elapsed_seconds(&self) -> f64;
+}
+
 /// Class holding information about a start and stoptime.
 /// The timer trait can be used to get duration information.
 pub struct Stopwatch {
@@ -53,4 +58,10 @@ impl Stopwatch {
         0.0
     }
 
-}
\ No newline at end of file
+}
+
+impl TimeSource for Stopwatch {
+    fn elapsed_seconds(&self) -> f64 {
+        Stopwatch::elapsed_seconds(self)
+    }
+}
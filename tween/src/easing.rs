@@ -0,0 +1,249 @@
+use std::f32::consts::PI;
+
+/// Robert Penner's easing functions, each maps a progress from 0 to 1 onto how far the value has moved.
+/// Every curve starts at 0 and ends at 1, back and elastic curves overshoot in between.
+/// In curves start slowly, out curves end slowly and in out curves do both.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut
+}
+
+impl Easing {
+    /// Eases a progress, which is clamped between 0 and 1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => sine_out(t),
+            Easing::SineInOut => sine_in_out(t),
+            Easing::QuadIn => power_in(t, 2),
+            Easing::QuadOut => power_out(t, 2),
+            Easing::QuadInOut => power_in_out(t, 2),
+            Easing::CubicIn => power_in(t, 3),
+            Easing::CubicOut => power_out(t, 3),
+            Easing::CubicInOut => power_in_out(t, 3),
+            Easing::QuartIn => power_in(t, 4),
+            Easing::QuartOut => power_out(t, 4),
+            Easing::QuartInOut => power_in_out(t, 4),
+            Easing::QuintIn => power_in(t, 5),
+            Easing::QuintOut => power_out(t, 5),
+            Easing::QuintInOut => power_in_out(t, 5),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => expo_out(t),
+            Easing::ExpoInOut => expo_in_out(t),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => circ_out(t),
+            Easing::CircInOut => circ_in_out(t),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => back_out(t),
+            Easing::BackInOut => back_in_out(t),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::ElasticInOut => elastic_in_out(t),
+            Easing::BounceIn => bounce_in(t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => bounce_in_out(t)
+        }
+    }
+
+    pub const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::SineIn, Easing::SineOut, Easing::SineInOut,
+        Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut,
+        Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut,
+        Easing::QuartIn, Easing::QuartOut, Easing::QuartInOut,
+        Easing::QuintIn, Easing::QuintOut, Easing::QuintInOut,
+        Easing::ExpoIn, Easing::ExpoOut, Easing::ExpoInOut,
+        Easing::CircIn, Easing::CircOut, Easing::CircInOut,
+        Easing::BackIn, Easing::BackOut, Easing::BackInOut,
+        Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut,
+        Easing::BounceIn, Easing::BounceOut, Easing::BounceInOut];
+}
+
+/// How far back curves pull back before moving, about 10% of the distance.
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+pub fn sine_in(t: f32) -> f32 {
+    1.0 - (t * PI / 2.0).cos()
+}
+
+pub fn sine_out(t: f32) -> f32 {
+    (t * PI / 2.0).sin()
+}
+
+pub fn sine_in_out(t: f32) -> f32 {
+    (1.0 - (t * PI).cos()) / 2.0
+}
+
+/// t to a power, ex: 2 for quad and 5 for quint.
+pub fn power_in(t: f32, power: i32) -> f32 {
+    t.powi(power)
+}
+
+pub fn power_out(t: f32, power: i32) -> f32 {
+    1.0 - (1.0 - t).powi(power)
+}
+
+pub fn power_in_out(t: f32, power: i32) -> f32 {
+    if t < 0.5 {
+        power_in(t * 2.0, power) / 2.0
+    }
+    else {
+        0.5 + power_out(t * 2.0 - 1.0, power) / 2.0
+    }
+}
+
+pub fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 { 0.0 } else { 2.0f32.powf(10.0 * t - 10.0) }
+}
+
+pub fn expo_out(t: f32) -> f32 {
+    if t >= 1.0 { 1.0 } else { 1.0 - 2.0f32.powf(-10.0 * t) }
+}
+
+pub fn expo_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        expo_in(t * 2.0) / 2.0
+    }
+    else {
+        0.5 + expo_out(t * 2.0 - 1.0) / 2.0
+    }
+}
+
+pub fn circ_in(t: f32) -> f32 {
+    1.0 - (1.0 - t * t).sqrt()
+}
+
+pub fn circ_out(t: f32) -> f32 {
+    (1.0 - (t - 1.0) * (t - 1.0)).sqrt()
+}
+
+pub fn circ_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        circ_in(t * 2.0) / 2.0
+    }
+    else {
+        0.5 + circ_out(t * 2.0 - 1.0) / 2.0
+    }
+}
+
+pub fn back_in(t: f32) -> f32 {
+    t * t * ((BACK_OVERSHOOT + 1.0) * t - BACK_OVERSHOOT)
+}
+
+pub fn back_out(t: f32) -> f32 {
+    1.0 - back_in(1.0 - t)
+}
+
+/// Pulls back by the same distance as `back_in`, the overshoot is scaled up since each half covers half the distance.
+pub fn back_in_out(t: f32) -> f32 {
+    let overshoot = BACK_OVERSHOOT * 1.525;
+
+    if t < 0.5 {
+        let t = t * 2.0;
+        t * t * ((overshoot + 1.0) * t - overshoot) / 2.0
+    }
+    else {
+        let t = t * 2.0 - 2.0;
+        (t * t * ((overshoot + 1.0) * t + overshoot) + 2.0) / 2.0
+    }
+}
+
+pub fn elastic_in(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t;
+    }
+
+    -2.0f32.powf(10.0 * t - 10.0) * ((10.0 * t - 10.75) * 2.0 * PI / 3.0).sin()
+}
+
+pub fn elastic_out(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t;
+    }
+
+    2.0f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * 2.0 * PI / 3.0).sin() + 1.0
+}
+
+pub fn elastic_in_out(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t;
+    }
+
+    let wave = ((20.0 * t - 11.125) * 2.0 * PI / 4.5).sin();
+
+    if t < 0.5 {
+        -2.0f32.powf(20.0 * t - 10.0) * wave / 2.0
+    }
+    else {
+        2.0f32.powf(-20.0 * t + 10.0) * wave / 2.0 + 1.0
+    }
+}
+
+pub fn bounce_in(t: f32) -> f32 {
+    1.0 - bounce_out(1.0 - t)
+}
+
+/// Falls and bounces 3 times, each bounce a quarter of the height of the last.
+pub fn bounce_out(t: f32) -> f32 {
+    const STRENGTH: f32 = 7.5625;
+    const WIDTH: f32 = 2.75;
+
+    if t < 1.0 / WIDTH {
+        STRENGTH * t * t
+    }
+    else if t < 2.0 / WIDTH {
+        let t = t - 1.5 / WIDTH;
+        STRENGTH * t * t + 0.75
+    }
+    else if t < 2.5 / WIDTH {
+        let t = t - 2.25 / WIDTH;
+        STRENGTH * t * t + 0.9375
+    }
+    else {
+        let t = t - 2.625 / WIDTH;
+        STRENGTH * t * t + 0.984375
+    }
+}
+
+pub fn bounce_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        bounce_in(t * 2.0) / 2.0
+    }
+    else {
+        0.5 + bounce_out(t * 2.0 - 1.0) / 2.0
+    }
+}
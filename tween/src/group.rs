@@ -0,0 +1,203 @@
+use crate::tween::{Animation, Repeat};
+
+/// Plays animations one after another, the time left over when one finishes goes to the next.
+#[derive(Default)]
+pub struct Sequence {
+    animations: Vec<Box<dyn Animation>>,
+    current: usize,
+    repeat: Repeat,
+    plays: u32,
+    time: f32,
+    finished: bool,
+    on_complete: Option<Box<dyn FnMut()>>
+}
+
+impl Sequence {
+    pub fn new() -> Sequence {
+        Sequence::default()
+    }
+
+    /// Adds an animation to play after the ones added so far.
+    pub fn then<A: Animation + 'static>(mut self, animation: A) -> Sequence {
+        self.animations.push(Box::new(animation));
+        self
+    }
+
+    /// With a repeat every animation is reset and the sequence plays again from the first.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Sequence {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Called once the last animation of the last play finishes.
+    pub fn on_complete<F: FnMut() + 'static>(mut self, callback: F) -> Sequence {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Resets from the last animation to the first, so tweens sharing an output leave it at the first one's start.
+    fn reset_animations(&mut self) {
+        for animation in self.animations.iter_mut().rev() {
+            animation.reset();
+        }
+
+        self.current = 0;
+    }
+}
+
+impl Animation for Sequence {
+    fn advance(&mut self, seconds: f32) -> f32 {
+        if self.finished {
+            return seconds;
+        }
+
+        let mut seconds = seconds.max(0.0);
+        let mut restarted_with = None;
+        self.time += seconds;
+
+        loop {
+            while let Some(animation) = self.animations.get_mut(self.current) {
+                seconds = animation.advance(seconds);
+
+                if !animation.is_finished() {
+                    return 0.0;
+                }
+
+                self.current += 1;
+            }
+
+            self.plays += 1;
+
+            // A play which took no time would repeat forever without using up the seconds.
+            if !self.repeat.plays_again(self.plays) || restarted_with.is_some_and(|before| seconds >= before) {
+                break;
+            }
+
+            restarted_with = Some(seconds);
+            self.reset_animations();
+        }
+
+        self.time -= seconds;
+        self.finished = true;
+
+        if let Some(on_complete) = &mut self.on_complete {
+            on_complete();
+        }
+
+        seconds
+    }
+
+    fn time(&self) -> f32 {
+        self.time
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn reset(&mut self) {
+        self.reset_animations();
+        self.plays = 0;
+        self.time = 0.0;
+        self.finished = false;
+    }
+}
+
+/// Plays animations at the same time, finishing once the longest does.
+#[derive(Default)]
+pub struct Parallel {
+    animations: Vec<Box<dyn Animation>>,
+    repeat: Repeat,
+    plays: u32,
+    time: f32,
+    finished: bool,
+    on_complete: Option<Box<dyn FnMut()>>
+}
+
+impl Parallel {
+    pub fn new() -> Parallel {
+        Parallel::default()
+    }
+
+    /// Adds an animation to play alongside the ones added so far.
+    pub fn with<A: Animation + 'static>(mut self, animation: A) -> Parallel {
+        self.animations.push(Box::new(animation));
+        self
+    }
+
+    /// With a repeat every animation is reset and they play again together, once the longest has finished.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Parallel {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Called once every animation of the last play has finished.
+    pub fn on_complete<F: FnMut() + 'static>(mut self, callback: F) -> Parallel {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+}
+
+impl Animation for Parallel {
+    fn advance(&mut self, seconds: f32) -> f32 {
+        if self.finished {
+            return seconds;
+        }
+
+        let mut seconds = seconds.max(0.0);
+        let mut restarted_with = None;
+        self.time += seconds;
+
+        loop {
+            // Animations which finished earlier leave all of the seconds over, so the longest leaves the least.
+            let left_over = self.animations.iter_mut()
+                .map(|animation| animation.advance(seconds))
+                .fold(seconds, f32::min);
+
+            if !self.animations.iter().all(|animation| animation.is_finished()) {
+                return 0.0;
+            }
+
+            self.plays += 1;
+            seconds = left_over;
+
+            // A play which took no time would repeat forever without using up the seconds.
+            if !self.repeat.plays_again(self.plays) || restarted_with.is_some_and(|before| seconds >= before) {
+                break;
+            }
+
+            restarted_with = Some(seconds);
+
+            for animation in &mut self.animations {
+                animation.reset();
+            }
+        }
+
+        self.time -= seconds;
+        self.finished = true;
+
+        if let Some(on_complete) = &mut self.on_complete {
+            on_complete();
+        }
+
+        seconds
+    }
+
+    fn time(&self) -> f32 {
+        self.time
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn reset(&mut self) {
+        for animation in &mut self.animations {
+            animation.reset();
+        }
+
+        self.plays = 0;
+        self.time = 0.0;
+        self.finished = false;
+    }
+}
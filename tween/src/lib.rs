@@ -0,0 +1,6 @@
+pub mod easing;
+pub mod tween;
+pub mod group;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,238 @@
+use std::{cell::Cell, rc::Rc, f32::consts::PI};
+
+use glmath::glmath::{*, vec3::Vec3, vector::Vector, matrix::Matrix};
+use glmath::{assert_approx_eq, assert_abs_diff_eq};
+use timer::TimeSource;
+
+use crate::easing::*;
+use crate::tween::{Tween, Tweenable, Repeat, Animation};
+use crate::group::{Sequence, Parallel};
+
+/// A time source which only moves when told to.
+struct ManualTime(f64);
+
+impl TimeSource for ManualTime {
+    fn elapsed_seconds(&self) -> f64 {
+        self.0
+    }
+}
+
+fn counter() -> (Rc<Cell<u32>>, impl FnMut() + 'static) {
+    let count = Rc::new(Cell::new(0));
+    let shared = count.clone();
+    (count, move || shared.set(shared.get() + 1))
+}
+
+#[test]
+fn test_easing() {
+    for easing in Easing::ALL {
+        assert_abs_diff_eq!(easing.apply(0.0), 0.0, 1e-6);
+        assert_abs_diff_eq!(easing.apply(1.0), 1.0, 1e-6);
+        assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+        assert_eq!(easing.apply(2.0), easing.apply(1.0));
+    }
+
+    assert_eq!(Easing::Linear.apply(0.3), 0.3);
+    assert_approx_eq!(Easing::QuadIn.apply(0.5), 0.25);
+    assert_approx_eq!(Easing::CubicOut.apply(0.5), 0.875);
+    assert_approx_eq!(Easing::QuartInOut.apply(0.25), 0.03125);
+    assert_approx_eq!(Easing::SineInOut.apply(0.5), 0.5);
+    assert_approx_eq!(Easing::ExpoIn.apply(0.5), 0.03125);
+    assert_abs_diff_eq!(Easing::CircOut.apply(0.5), 0.8660254, 1e-6);
+
+    // The in out curves are symmetric, the out curves mirror the in curves.
+    for t in [0.1, 0.3, 0.45] {
+        for power in 2..=5 {
+            assert_abs_diff_eq!(power_in_out(t, power), 1.0 - power_in_out(1.0 - t, power), 1e-6);
+            assert_abs_diff_eq!(power_out(t, power), 1.0 - power_in(1.0 - t, power), 1e-6);
+        }
+
+        assert_abs_diff_eq!(back_in_out(t), 1.0 - back_in_out(1.0 - t), 1e-6);
+        assert_abs_diff_eq!(elastic_in_out(t), 1.0 - elastic_in_out(1.0 - t), 1e-6);
+        assert_abs_diff_eq!(bounce_in_out(t), 1.0 - bounce_in_out(1.0 - t), 1e-6);
+        assert_abs_diff_eq!(expo_out(t), 1.0 - expo_in(1.0 - t), 1e-6);
+    }
+
+    // Back pulls back by about 10% and elastic overshoots, bounce stays within the range.
+    let lowest = |easing: Easing| (0..=1000).map(|i| easing.apply(i as f32 / 1000.0)).fold(f32::MAX, f32::min);
+    assert_abs_diff_eq!(lowest(Easing::BackIn), -0.1, 0.001);
+    assert_abs_diff_eq!(lowest(Easing::BackInOut), -0.1, 0.001);
+    assert!(lowest(Easing::ElasticIn) < -0.3);
+    assert!(lowest(Easing::BounceOut) >= 0.0);
+    assert_approx_eq!(bounce_out(1.0 / 2.75), 1.0);
+    assert_approx_eq!(bounce_out(2.0 / 2.75), 1.0);
+}
+
+#[test]
+fn test_tween() {
+    let (completed, on_complete) = counter();
+    let mut tween = Tween::new(Vec2f::ZERO, Vec2f::new(10.0, -4.0), 2.0)
+        .with_easing(Easing::QuadIn)
+        .on_complete(on_complete);
+
+    assert_eq!(tween.value(), Vec2f::ZERO);
+    assert_eq!(tween.advance(1.0), 0.0);
+    assert_approx_eq!(tween.value(), Vec2f::new(2.5, -1.0));
+    assert!(!tween.is_finished());
+
+    assert_eq!(tween.advance(1.5), 0.5);
+    assert_eq!(tween.value(), Vec2f::new(10.0, -4.0));
+    assert!(tween.is_finished());
+    assert_eq!(tween.time(), 2.0);
+    assert_eq!(completed.get(), 1);
+
+    // Finished tweens leave every second over and don't complete again.
+    assert_eq!(tween.advance(1.0), 1.0);
+    assert_eq!(completed.get(), 1);
+
+    tween.reset();
+    assert_eq!(tween.value(), Vec2f::ZERO);
+    assert!(!tween.is_finished());
+
+    // Driven by a time source, which started with the tween.
+    let mut time = ManualTime(0.5);
+    tween.update(&time);
+    assert_approx_eq!(tween.value(), Vec2f::new(0.625, -0.25));
+    time.0 = 0.25;
+    tween.update(&time);
+    assert_approx_eq!(tween.value(), Vec2f::new(0.625, -0.25));
+    time.0 = 3.0;
+    tween.update(&time);
+    assert!(tween.is_finished());
+    assert_eq!(completed.get(), 2);
+
+    // Zero length tweens jump to the end.
+    let mut instant = Tween::new(1.0f32, 2.0, 0.0);
+    assert_eq!(instant.advance(0.25), 0.25);
+    assert_eq!(instant.value(), 2.0);
+}
+
+#[test]
+fn test_tween_repeat() {
+    let (completed, on_complete) = counter();
+    let mut yoyo = Tween::new(0.0f32, 1.0, 1.0)
+        .with_yoyo()
+        .with_repeat(Repeat::Times(2))
+        .on_complete(on_complete);
+
+    assert_eq!(yoyo.play_duration(), 2.0);
+
+    let expected = [0.5, 1.0, 0.5, 0.0, 0.5, 1.0, 0.5];
+
+    for value in expected {
+        assert_eq!(yoyo.advance(0.5), 0.0);
+        assert_approx_eq!(yoyo.value(), value);
+    }
+
+    assert_eq!(completed.get(), 0);
+    assert_eq!(yoyo.advance(1.0), 0.5);
+    assert_eq!(yoyo.value(), 0.0);
+    assert_eq!(completed.get(), 1);
+
+    let mut looping = Tween::new(0.0f64, 4.0, 1.0).with_repeat(Repeat::Forever).on_complete(|| panic!("never completes"));
+    assert_eq!(looping.advance(10.25), 0.0);
+    assert_approx_eq!(looping.value(), 1.0);
+    assert!(!looping.is_finished());
+}
+
+#[test]
+fn test_tweenable() {
+    assert_approx_eq!(f32::tween(1.0, 3.0, 0.25), 1.5);
+    assert_approx_eq!(f32::tween(1.0, 3.0, -0.5), 0.0);
+    assert_approx_eq!(Vec3::<f64>::tween(Vec3::<f64>::ZERO, Vec3::<f64>::new(2.0, 4.0, 6.0), 0.5), Vec3::<f64>::new(1.0, 2.0, 3.0));
+    assert_approx_eq!(Vec4f::tween(Vec4f::ZERO, Vec4f::new(1.0, 1.0, 1.0, 1.0), 0.5), Vec4f::new(0.5, 0.5, 0.5, 0.5));
+    assert_approx_eq!(Vector::tween(Vector::new([0.0f32; 5]), Vector::new([1.0; 5]), 0.2), Vector::new([0.2; 5]));
+    assert_approx_eq!(Mat22f::tween(Mat22f::ZERO, Mat22f::IDENTITY, 0.5), Matrix { data: [[0.5, 0.0], [0.0, 0.5]] });
+
+    // Rotations blend with `Quat::slerp`, halfway is half of the turn.
+    let quarter_turn = Quatf::from_axis_angle(Vec3f::Y, PI / 2.0);
+    assert_abs_diff_eq!(Quatf::tween(Quatf::IDENTITY, quarter_turn, 0.5), Quatf::from_axis_angle(Vec3f::Y, PI / 4.0), 1e-6);
+
+    let a = Transformf::from_translation(Vec3f::new(0.0, 2.0, 0.0));
+    let b = Transformf::new(Vec3f::new(4.0, 2.0, 0.0), quarter_turn, Vec3f::new(3.0, 3.0, 3.0));
+    let halfway = Transformf::tween(a, b, 0.5);
+    assert_approx_eq!(halfway.translation, Vec3f::new(2.0, 2.0, 0.0));
+    assert_approx_eq!(halfway.scale, Vec3f::new(2.0, 2.0, 2.0));
+}
+
+#[test]
+fn test_groups() {
+    let (completed, on_complete) = counter();
+    let first = Tween::new(0.0f32, 1.0, 1.0);
+    let second = Tween::new(Vec2f::ZERO, Vec2f::X, 2.0);
+    let (position, scale) = (first.output(), second.output());
+
+    let mut sequence = Sequence::new().then(first).then(second).on_complete(on_complete);
+
+    assert_eq!(sequence.advance(0.5), 0.0);
+    assert_approx_eq!(position.get(), 0.5);
+    assert_eq!(scale.get(), Vec2f::ZERO);
+
+    // The half second left by the first tween goes to the second.
+    sequence.advance(1.0);
+    assert_eq!(position.get(), 1.0);
+    assert_approx_eq!(scale.get(), Vec2f::new(0.25, 0.0));
+
+    assert_eq!(sequence.advance(2.0), 0.5);
+    assert_eq!(scale.get(), Vec2f::X);
+    assert!(sequence.is_finished());
+    assert_eq!(sequence.time(), 3.0);
+    assert_eq!(completed.get(), 1);
+
+    // Groups nest, and repeat by resetting what they hold.
+    let (completed, on_complete) = counter();
+    let fade = Tween::new(1.0f32, 0.0, 1.0);
+    let grow = Tween::new(1.0f32, 2.0, 0.5).with_yoyo();
+    let (alpha, size) = (fade.output(), grow.output());
+
+    let mut group = Sequence::new()
+        .then(Parallel::new().with(fade).with(grow))
+        .then(Tween::new(0.0f32, 0.0, 0.5))
+        .with_repeat(Repeat::Times(3))
+        .on_complete(on_complete);
+
+    group.advance(0.5);
+    assert_approx_eq!(alpha.get(), 0.5);
+    assert_approx_eq!(size.get(), 2.0);
+
+    group.advance(1.25);
+    assert_approx_eq!(alpha.get(), 0.75);
+    assert_approx_eq!(size.get(), 1.5);
+
+    assert_eq!(group.advance(3.0), 0.25);
+    assert_eq!(completed.get(), 1);
+    assert_eq!(group.time(), 4.5);
+
+    group.reset();
+    assert_eq!(alpha.get(), 1.0);
+    assert!(!group.is_finished());
+
+    // Tweens one after another on the same value.
+    let value = Rc::new(Cell::new(0.0f32));
+    let mut steps = Sequence::new()
+        .then(Tween::new(0.0f32, 1.0, 1.0).with_output(value.clone()))
+        .then(Tween::new(1.0f32, 3.0, 1.0).with_output(value.clone()));
+
+    steps.advance(1.5);
+    assert_approx_eq!(value.get(), 2.0);
+    steps.reset();
+    assert_eq!(value.get(), 0.0);
+
+    // Groups which take no time finish rather than repeating forever.
+    let mut empty = Parallel::new().with_repeat(Repeat::Forever);
+    assert_eq!(empty.advance(1.0), 1.0);
+    assert!(empty.is_finished());
+
+    let mut instant = Sequence::new().then(Tween::new(0.0f32, 1.0, 0.0)).with_repeat(Repeat::Forever);
+    assert_eq!(instant.advance(1.0), 1.0);
+    assert!(instant.is_finished());
+
+    // A looping sequence keeps playing through many plays in a single step.
+    let mut patrol = Sequence::new()
+        .then(Tween::new(0.0f32, 1.0, 1.0))
+        .then(Tween::new(1.0f32, 0.0, 1.0))
+        .with_repeat(Repeat::Forever);
+
+    assert_eq!(patrol.advance(101.5), 0.0);
+    assert!(!patrol.is_finished());
+}
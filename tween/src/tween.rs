@@ -0,0 +1,264 @@
+use std::{cell::Cell, rc::Rc};
+
+use glmath::glmath::{*, vec2::Vec2, vec3::Vec3, vec4::Vec4, quat::Quat, vector::Vector, matrix::Matrix, transform::Transform};
+use glmath::glmath::geometry::Scalar;
+use timer::TimeSource;
+
+use crate::easing::Easing;
+
+/// The values a tween can move between.
+pub trait Tweenable: Copy {
+    /// The value a fraction of the way from one value to the other.
+    /// Overshooting easings pass fractions below 0 and above 1, which should carry on past the ends.
+    fn tween(from: Self, to: Self, fraction: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn tween(from: f32, to: f32, fraction: f32) -> f32 {
+        from + (to - from) * fraction
+    }
+}
+
+impl Tweenable for f64 {
+    fn tween(from: f64, to: f64, fraction: f32) -> f64 {
+        from + (to - from) * fraction as f64
+    }
+}
+
+macro_rules! impl_tweenable_vector {
+    ($($vector:ident),*) => {
+        $(
+            impl<T: Scalar> Tweenable for $vector<T> {
+                fn tween(from: $vector<T>, to: $vector<T>, fraction: f32) -> $vector<T> {
+                    from + (to - from) * T::from_f64(fraction as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_tweenable_vector!(Vec2, Vec3, Vec4);
+
+impl<T: Scalar, const N: usize> Tweenable for Vector<T, N> {
+    fn tween(from: Vector<T, N>, to: Vector<T, N>, fraction: f32) -> Vector<T, N> {
+        from + (to - from) * T::from_f64(fraction as f64)
+    }
+}
+
+/// Each element moves on its own, so a tween between two rotation matrices passes through ones which
+/// aren't rotations, tween a `Quat` or `Transform` instead.
+impl<T: Scalar, const R: usize, const C: usize> Tweenable for Matrix<T, R, C> {
+    fn tween(from: Matrix<T, R, C>, to: Matrix<T, R, C>, fraction: f32) -> Matrix<T, R, C> {
+        let fraction = T::from_f64(fraction as f64);
+        let mut result = from;
+
+        for (column, to) in result.data.iter_mut().zip(to.data) {
+            for (value, to) in column.iter_mut().zip(to) {
+                *value = *value + (to - *value) * fraction;
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: Scalar> Tweenable for Quat<T> {
+    fn tween(from: Quat<T>, to: Quat<T>, fraction: f32) -> Quat<T> {
+        Quat::<T>::slerp(from, to, T::from_f64(fraction as f64))
+    }
+}
+
+impl<T: Scalar> Tweenable for Transform<T> {
+    fn tween(from: Transform<T>, to: Transform<T>, fraction: f32) -> Transform<T> {
+        Transform::<T>::interpolate(&from, &to, T::from_f64(fraction as f64))
+    }
+}
+
+/// How many times an animation plays before it finishes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Repeat {
+    #[default]
+    Once,
+    Times(u32),
+    Forever
+}
+
+impl Repeat {
+    /// Whether another play follows the given number of finished plays.
+    pub(crate) fn plays_again(self, finished: u32) -> bool {
+        match self {
+            Repeat::Once => false,
+            Repeat::Times(times) => finished < times,
+            Repeat::Forever => true
+        }
+    }
+}
+
+/// Anything which plays over time, a `Tween` or a group of animations.
+pub trait Animation {
+    /// Moves forward by the given seconds.
+    /// Returns the seconds left over after the animation finished, which a sequence passes on to its next animation.
+    /// Returns 0 while the animation is still playing, and all of the seconds once it has finished.
+    fn advance(&mut self, seconds: f32) -> f32;
+
+    /// The seconds played so far, which stops increasing once the animation finishes.
+    fn time(&self) -> f32;
+
+    fn is_finished(&self) -> bool;
+
+    /// Goes back to the start without calling any callbacks, ex: to play the animation again.
+    fn reset(&mut self);
+
+    /// Moves forward to the elapsed time of a `Stopwatch` or `GameTick`, which started when the animation did.
+    fn update(&mut self, source: &dyn TimeSource) {
+        let seconds = source.elapsed_seconds() as f32 - self.time();
+
+        if seconds > 0.0 {
+            self.advance(seconds);
+        }
+    }
+}
+
+/// Moves a value from one value to another over time, ex: a position, a colour or a rotation.
+/// The current value can be read from the tween, or from its output once the tween is moved into a group.
+pub struct Tween<T: Tweenable> {
+    from: T,
+    to: T,
+    duration: f32,
+    easing: Easing,
+    repeat: Repeat,
+    yoyo: bool,
+    time: f32,
+    finished: bool,
+    output: Rc<Cell<T>>,
+    on_complete: Option<Box<dyn FnMut()>>
+}
+
+impl<T: Tweenable> Tween<T> {
+    /// A tween moving at a constant speed, which plays once.
+    pub fn new(from: T, to: T, duration: f32) -> Tween<T> {
+        Tween::<T> {
+            from,
+            to,
+            duration: duration.max(0.0),
+            easing: Easing::Linear,
+            repeat: Repeat::Once,
+            yoyo: false,
+            time: 0.0,
+            finished: false,
+            output: Rc::new(Cell::new(from)),
+            on_complete: None
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Tween<T> {
+        self.easing = easing;
+        self
+    }
+
+    /// With a repeat the tween jumps back to the start after each play, unless it yoyos.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Tween<T> {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Plays back from the end to the start after reaching the end, so each play takes twice the duration.
+    pub fn with_yoyo(mut self) -> Tween<T> {
+        self.yoyo = true;
+        self
+    }
+
+    /// Called once the last play finishes, never for a tween which repeats forever.
+    pub fn on_complete<F: FnMut() + 'static>(mut self, callback: F) -> Tween<T> {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Writes the value into a shared output instead of its own, ex: so the tweens of a sequence
+    /// can move the same value one after another.
+    pub fn with_output(mut self, output: Rc<Cell<T>>) -> Tween<T> {
+        output.set(self.from);
+        self.output = output;
+        self
+    }
+
+    pub fn value(&self) -> T {
+        self.output.get()
+    }
+
+    /// Shares the current value, which keeps updating while the tween plays inside of a `Sequence` or `Parallel`.
+    pub fn output(&self) -> Rc<Cell<T>> {
+        self.output.clone()
+    }
+
+    /// The seconds each play takes, including the way back when it yoyos.
+    pub fn play_duration(&self) -> f32 {
+        if self.yoyo { self.duration * 2.0 } else { self.duration }
+    }
+
+    /// The value a number of seconds into a play.
+    fn value_at(&self, play_time: f32) -> T {
+        if self.duration <= 0.0 {
+            return if self.yoyo { self.from } else { self.to };
+        }
+
+        let mut progress = play_time / self.duration;
+
+        if self.yoyo && progress > 1.0 {
+            progress = 2.0 - progress;
+        }
+
+        T::tween(self.from, self.to, self.easing.apply(progress))
+    }
+}
+
+impl<T: Tweenable> Animation for Tween<T> {
+    fn advance(&mut self, seconds: f32) -> f32 {
+        if self.finished {
+            return seconds;
+        }
+
+        let play_duration = self.play_duration();
+        self.time += seconds.max(0.0);
+
+        let plays = if play_duration > 0.0 { (self.time / play_duration) as u32 } else { u32::MAX };
+
+        if plays == 0 || self.repeat.plays_again(plays) {
+            self.output.set(self.value_at(self.time % play_duration));
+            return 0.0;
+        }
+
+        // Finished, every play after the last is left over.
+        let last_play = match self.repeat {
+            Repeat::Times(times) => times.max(1),
+            _ => 1
+        };
+
+        let end = play_duration * last_play as f32;
+        let left_over = self.time - end;
+
+        self.time = end;
+        self.finished = true;
+        self.output.set(self.value_at(play_duration));
+
+        if let Some(on_complete) = &mut self.on_complete {
+            on_complete();
+        }
+
+        left_over
+    }
+
+    fn time(&self) -> f32 {
+        self.time
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn reset(&mut self) {
+        self.time = 0.0;
+        self.finished = false;
+        self.output.set(self.from);
+    }
+}